@@ -78,7 +78,7 @@ def_sysfn! {
 
     @SYS_LOCK_PREEMPTION
     sys_lock_preemption(
-        lock_level: ApexLockLevel,
+        lock_level: *mut ApexLockLevel,
     ) -> ApexReturnCode
 
     @SYS_UNLOCK_PREEMPTION
@@ -108,6 +108,367 @@ def_sysfn! {
     ) -> ApexReturnCode
 }
 
+def_sysfn! {
+    @SYS_TIMED_WAIT
+    sys_timed_wait(
+        delay_time: ApexSystemTime,
+    ) -> ApexReturnCode
+
+    @SYS_PERIODIC_WAIT
+    sys_periodic_wait() -> ApexReturnCode
+
+    @SYS_GET_TIME
+    sys_get_time(
+        time: *mut ApexSystemTime,
+    ) -> ApexReturnCode
+
+    @SYS_REPLENISH
+    sys_replenish(
+        budget_time: ApexSystemTime,
+    ) -> ApexReturnCode
+}
+
+def_sysfn! {
+    @SYS_CREATE_SAMPLING_PORT
+    sys_create_sampling_port(
+        name: *const ApexSamplingPortName,
+        max_message_size: ApexMessageSize,
+        port_direction: ApexPortDirection,
+        refresh_period: ApexSystemTime,
+        id: *mut ApexSamplingPortId,
+    ) -> ApexReturnCode
+
+    @SYS_WRITE_SAMPLING_MESSAGE
+    sys_write_sampling_message(
+        id: ApexSamplingPortId,
+        message: *const ApexByte,
+        length: ApexMessageSize,
+    ) -> ApexReturnCode
+
+    @SYS_READ_SAMPLING_MESSAGE
+    sys_read_sampling_message(
+        id: ApexSamplingPortId,
+        message: *mut ApexByte,
+        length: *mut ApexMessageSize,
+        validity: *mut ApexValidity,
+    ) -> ApexReturnCode
+
+    @SYS_GET_SAMPLING_PORT_ID
+    sys_get_sampling_port_id(
+        name: *const ApexSamplingPortName,
+        id: *mut ApexSamplingPortId,
+    ) -> ApexReturnCode
+
+    @SYS_GET_SAMPLING_PORT_STATUS
+    sys_get_sampling_port_status(
+        id: ApexSamplingPortId,
+        status: *mut ApexSamplingPortStatus,
+    ) -> ApexReturnCode
+}
+
+def_sysfn! {
+    @SYS_CREATE_QUEUING_PORT
+    sys_create_queuing_port(
+        name: *const ApexQueuingPortName,
+        max_message_size: ApexMessageSize,
+        max_nb_message: ApexMessageRange,
+        port_direction: ApexPortDirection,
+        queuing_discipline: ApexQueueDiscipline,
+        id: *mut ApexQueuingPortId,
+    ) -> ApexReturnCode
+
+    @SYS_SEND_QUEUING_MESSAGE
+    sys_send_queuing_message(
+        id: ApexQueuingPortId,
+        message: *const ApexByte,
+        length: ApexMessageSize,
+        timeout: ApexSystemTime,
+    ) -> ApexReturnCode
+
+    @SYS_RECEIVE_QUEUING_MESSAGE
+    sys_receive_queuing_message(
+        id: ApexQueuingPortId,
+        timeout: ApexSystemTime,
+        message: *mut ApexByte,
+        length: *mut ApexMessageSize,
+    ) -> ApexReturnCode
+
+    @SYS_GET_QUEUING_PORT_ID
+    sys_get_queuing_port_id(
+        name: *const ApexQueuingPortName,
+        id: *mut ApexQueuingPortId,
+    ) -> ApexReturnCode
+
+    @SYS_GET_QUEUING_PORT_STATUS
+    sys_get_queuing_port_status(
+        id: ApexQueuingPortId,
+        status: *mut ApexQueuingPortStatus,
+    ) -> ApexReturnCode
+
+    @SYS_CLEAR_QUEUING_PORT
+    sys_clear_queuing_port(
+        id: ApexQueuingPortId,
+    ) -> ApexReturnCode
+}
+
+def_sysfn! {
+    @SYS_CREATE_BUFFER
+    sys_create_buffer(
+        name: *const ApexBufferName,
+        max_message_size: ApexMessageSize,
+        max_nb_message: ApexMessageRange,
+        queuing_discipline: ApexQueueDiscipline,
+        id: *mut ApexBufferId,
+    ) -> ApexReturnCode
+
+    @SYS_SEND_BUFFER
+    sys_send_buffer(
+        id: ApexBufferId,
+        message: *const ApexByte,
+        length: ApexMessageSize,
+        timeout: ApexSystemTime,
+    ) -> ApexReturnCode
+
+    @SYS_RECEIVE_BUFFER
+    sys_receive_buffer(
+        id: ApexBufferId,
+        timeout: ApexSystemTime,
+        message: *mut ApexByte,
+        length: *mut ApexMessageSize,
+    ) -> ApexReturnCode
+
+    @SYS_GET_BUFFER_ID
+    sys_get_buffer_id(
+        name: *const ApexBufferName,
+        id: *mut ApexBufferId,
+    ) -> ApexReturnCode
+
+    @SYS_GET_BUFFER_STATUS
+    sys_get_buffer_status(
+        id: ApexBufferId,
+        status: *mut ApexBufferStatus,
+    ) -> ApexReturnCode
+}
+
+def_sysfn! {
+    @SYS_CREATE_BLACKBOARD
+    sys_create_blackboard(
+        name: *const ApexBlackboardName,
+        max_message_size: ApexMessageSize,
+        id: *mut ApexBlackboardId,
+    ) -> ApexReturnCode
+
+    @SYS_DISPLAY_BLACKBOARD
+    sys_display_blackboard(
+        id: ApexBlackboardId,
+        message: *const ApexByte,
+        length: ApexMessageSize,
+    ) -> ApexReturnCode
+
+    @SYS_READ_BLACKBOARD
+    sys_read_blackboard(
+        id: ApexBlackboardId,
+        timeout: ApexSystemTime,
+        message: *mut ApexByte,
+        length: *mut ApexMessageSize,
+    ) -> ApexReturnCode
+
+    @SYS_CLEAR_BLACKBOARD
+    sys_clear_blackboard(
+        id: ApexBlackboardId,
+    ) -> ApexReturnCode
+
+    @SYS_GET_BLACKBOARD_ID
+    sys_get_blackboard_id(
+        name: *const ApexBlackboardName,
+        id: *mut ApexBlackboardId,
+    ) -> ApexReturnCode
+
+    @SYS_GET_BLACKBOARD_STATUS
+    sys_get_blackboard_status(
+        id: ApexBlackboardId,
+        status: *mut ApexBlackboardStatus,
+    ) -> ApexReturnCode
+}
+
+def_sysfn! {
+    @SYS_CREATE_SEMAPHORE
+    sys_create_semaphore(
+        name: *const ApexSemaphoreName,
+        current_value: ApexSemaphoreValue,
+        maximum_value: ApexSemaphoreValue,
+        queuing_discipline: ApexQueueDiscipline,
+        id: *mut ApexSemaphoreId,
+    ) -> ApexReturnCode
+
+    @SYS_WAIT_SEMAPHORE
+    sys_wait_semaphore(
+        id: ApexSemaphoreId,
+        timeout: ApexSystemTime,
+    ) -> ApexReturnCode
+
+    @SYS_SIGNAL_SEMAPHORE
+    sys_signal_semaphore(
+        id: ApexSemaphoreId,
+    ) -> ApexReturnCode
+
+    @SYS_GET_SEMAPHORE_ID
+    sys_get_semaphore_id(
+        name: *const ApexSemaphoreName,
+        id: *mut ApexSemaphoreId,
+    ) -> ApexReturnCode
+
+    @SYS_GET_SEMAPHORE_STATUS
+    sys_get_semaphore_status(
+        id: ApexSemaphoreId,
+        status: *mut ApexSemaphoreStatus,
+    ) -> ApexReturnCode
+}
+
+def_sysfn! {
+    @SYS_CREATE_EVENT
+    sys_create_event(
+        name: *const ApexEventName,
+        id: *mut ApexEventId,
+    ) -> ApexReturnCode
+
+    @SYS_SET_EVENT
+    sys_set_event(
+        id: ApexEventId,
+    ) -> ApexReturnCode
+
+    @SYS_RESET_EVENT
+    sys_reset_event(
+        id: ApexEventId,
+    ) -> ApexReturnCode
+
+    @SYS_WAIT_EVENT
+    sys_wait_event(
+        id: ApexEventId,
+        timeout: ApexSystemTime,
+    ) -> ApexReturnCode
+
+    @SYS_GET_EVENT_ID
+    sys_get_event_id(
+        name: *const ApexEventName,
+        id: *mut ApexEventId,
+    ) -> ApexReturnCode
+
+    @SYS_GET_EVENT_STATUS
+    sys_get_event_status(
+        id: ApexEventId,
+        status: *mut ApexEventStatus,
+    ) -> ApexReturnCode
+}
+
+def_sysfn! {
+    @SYS_CREATE_MUTEX
+    sys_create_mutex(
+        name: *const ApexMutexName,
+        priority: ApexPriority,
+        queuing_discipline: ApexQueueDiscipline,
+        id: *mut ApexMutexId,
+    ) -> ApexReturnCode
+
+    @SYS_ACQUIRE_MUTEX
+    sys_acquire_mutex(
+        id: ApexMutexId,
+        timeout: ApexSystemTime,
+    ) -> ApexReturnCode
+
+    @SYS_RELEASE_MUTEX
+    sys_release_mutex(
+        id: ApexMutexId,
+    ) -> ApexReturnCode
+
+    @SYS_RESET_MUTEX
+    sys_reset_mutex(
+        id: ApexMutexId,
+        process_id: ApexProcessId,
+    ) -> ApexReturnCode
+
+    @SYS_GET_MUTEX_ID
+    sys_get_mutex_id(
+        name: *const ApexMutexName,
+        id: *mut ApexMutexId,
+    ) -> ApexReturnCode
+
+    @SYS_GET_MUTEX_STATUS
+    sys_get_mutex_status(
+        id: ApexMutexId,
+        status: *mut ApexMutexStatus,
+    ) -> ApexReturnCode
+
+    @SYS_GET_PROCESS_MUTEX_STATE
+    sys_get_process_mutex_state(
+        process_id: ApexProcessId,
+        id: *mut ApexMutexId,
+    ) -> ApexReturnCode
+}
+
+def_sysfn! {
+    @SYS_REPORT_APPLICATION_MESSAGE
+    sys_report_application_message(
+        message: *const ApexByte,
+        length: ApexMessageSize,
+    ) -> ApexReturnCode
+
+    @SYS_CREATE_ERROR_HANDLER
+    sys_create_error_handler(
+        entry_point: usize,
+        stack_size: ApexStackSize,
+    ) -> ApexReturnCode
+
+    @SYS_GET_ERROR_STATUS
+    sys_get_error_status(
+        status: *mut ApexErrorStatus,
+    ) -> ApexReturnCode
+
+    @SYS_RAISE_APPLICATION_ERROR
+    sys_raise_application_error(
+        error_code: ApexErrorCode,
+        message: *const ApexByte,
+        length: ApexMessageSize,
+    ) -> ApexReturnCode
+}
+
+def_sysfn! {
+    @SYS_SET_MODULE_SCHEDULE
+    sys_set_module_schedule(
+        id: ApexScheduleId,
+    ) -> ApexReturnCode
+
+    @SYS_GET_MODULE_SCHEDULE_STATUS
+    sys_get_module_schedule_status(
+        status: *mut ApexScheduleStatus,
+    ) -> ApexReturnCode
+
+    @SYS_GET_MODULE_SCHEDULE_ID
+    sys_get_module_schedule_id(
+        name: *const ApexScheduleName,
+        id: *mut ApexScheduleId,
+    ) -> ApexReturnCode
+
+    @SYS_GET_WINDOW_STATUS
+    sys_get_window_status(
+        core_id: ApexProcessorCoreId,
+        index: ApexWindowIndex,
+        status: *mut ApexWindowStatus,
+    ) -> ApexReturnCode
+
+    @SYS_GET_TIMING_EVENT
+    sys_get_timing_event(
+        index: ApexTimingEventIndex,
+        event: *mut ApexTimingEvent,
+    ) -> ApexReturnCode
+
+    @SYS_GET_GAP_STATUS
+    sys_get_gap_status(
+        core_id: ApexProcessorCoreId,
+        status: *mut ApexGapStatus,
+    ) -> ApexReturnCode
+}
+
 def_sysfn! {
     @SYS_DEBUG_LOG
     sys_debug_log(
@@ -114,6 +114,22 @@ def_sysno! {
     SYS_GET_PROCESS_MUTEX_STATE,
 }
 
+def_sysno! {
+    SYS_REPORT_APPLICATION_MESSAGE = 0x6000,
+    SYS_CREATE_ERROR_HANDLER,
+    SYS_GET_ERROR_STATUS,
+    SYS_RAISE_APPLICATION_ERROR,
+}
+
+def_sysno! {
+    SYS_SET_MODULE_SCHEDULE = 0x7000,
+    SYS_GET_MODULE_SCHEDULE_STATUS,
+    SYS_GET_MODULE_SCHEDULE_ID,
+    SYS_GET_WINDOW_STATUS,
+    SYS_GET_TIMING_EVENT,
+    SYS_GET_GAP_STATUS,
+}
+
 def_sysno! {
     SYS_DEBUG_LOG = 0xdbdbdbdb,
     SYS_DEBUG_HALT,
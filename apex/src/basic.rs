@@ -75,6 +75,12 @@ impl From<ApexSystemAddress> for usize {
     }
 }
 
+impl From<usize> for ApexSystemAddress {
+    fn from(addr: usize) -> Self {
+        Self(addr)
+    }
+}
+
 impl ApexSystemAddress {
     pub fn of<R>(f: extern "C" fn() -> R) -> Self {
         Self(f as usize)
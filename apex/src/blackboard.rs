@@ -0,0 +1,65 @@
+use crate::bindings::*;
+
+pub type ApexBlackboardName = ApexName;
+pub type ApexBlackboardId = ApexLongInteger;
+
+#[repr(u32)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ApexEmptyIndicator {
+    #[default]
+    Empty = 0,
+    Occupied = 1,
+}
+
+impl TryFrom<ApexUnsigned> for ApexEmptyIndicator {
+    type Error = ApexUnsigned;
+
+    fn try_from(value: ApexUnsigned) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Empty),
+            1 => Ok(Self::Occupied),
+            _ => Err(value),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApexBlackboardStatus {
+    pub empty_indicator: ApexEmptyIndicator,
+    pub max_message_size: ApexMessageSize,
+    pub waiting_processes: ApexWaitingRange,
+}
+
+pub trait ApexBlackboardService {
+    fn create_blackboard(
+        &self,
+        blackboard_name: &ApexBlackboardName,
+        max_message_size: ApexMessageSize,
+    ) -> Result<ApexBlackboardId, ApexReturnCode>;
+
+    fn display_blackboard(
+        &self,
+        blackboard_id: ApexBlackboardId,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode>;
+
+    fn read_blackboard(
+        &self,
+        blackboard_id: ApexBlackboardId,
+        time_out: ApexSystemTime,
+        message: &mut [ApexByte],
+    ) -> Result<ApexMessageSize, ApexReturnCode>;
+
+    fn clear_blackboard(&self, blackboard_id: ApexBlackboardId) -> Result<(), ApexReturnCode>;
+
+    fn get_blackboard_id(
+        &self,
+        blackboard_name: &ApexBlackboardName,
+    ) -> Result<ApexBlackboardId, ApexReturnCode>;
+
+    fn get_blackboard_status(
+        &self,
+        blackboard_id: ApexBlackboardId,
+    ) -> Result<ApexBlackboardStatus, ApexReturnCode>;
+}
@@ -0,0 +1,44 @@
+use crate::bindings::*;
+
+pub type ApexBufferName = ApexName;
+pub type ApexBufferId = ApexLongInteger;
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApexBufferStatus {
+    pub nb_message: ApexMessageRange,
+    pub max_nb_message: ApexMessageRange,
+    pub max_message_size: ApexMessageSize,
+    pub waiting_processes: ApexWaitingRange,
+}
+
+pub trait ApexBufferService {
+    fn create_buffer(
+        &self,
+        buffer_name: &ApexBufferName,
+        max_message_size: ApexMessageSize,
+        max_nb_message: ApexMessageRange,
+        queuing_discipline: ApexQueueDiscipline,
+    ) -> Result<ApexBufferId, ApexReturnCode>;
+
+    fn send_buffer(
+        &self,
+        buffer_id: ApexBufferId,
+        message: &[ApexByte],
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode>;
+
+    fn receive_buffer(
+        &self,
+        buffer_id: ApexBufferId,
+        time_out: ApexSystemTime,
+        message: &mut [ApexByte],
+    ) -> Result<ApexMessageSize, ApexReturnCode>;
+
+    fn get_buffer_id(&self, buffer_name: &ApexBufferName) -> Result<ApexBufferId, ApexReturnCode>;
+
+    fn get_buffer_status(
+        &self,
+        buffer_id: ApexBufferId,
+    ) -> Result<ApexBufferStatus, ApexReturnCode>;
+}
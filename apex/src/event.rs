@@ -0,0 +1,49 @@
+use crate::bindings::*;
+
+pub type ApexEventName = ApexName;
+pub type ApexEventId = ApexLongInteger;
+
+#[repr(u32)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ApexEventState {
+    #[default]
+    Down = 0,
+    Up = 1,
+}
+
+impl TryFrom<ApexUnsigned> for ApexEventState {
+    type Error = ApexUnsigned;
+
+    fn try_from(value: ApexUnsigned) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Down),
+            1 => Ok(Self::Up),
+            _ => Err(value),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApexEventStatus {
+    pub event_state: ApexEventState,
+    pub waiting_processes: ApexWaitingRange,
+}
+
+pub trait ApexEventService {
+    fn create_event(&self, event_name: &ApexEventName) -> Result<ApexEventId, ApexReturnCode>;
+
+    fn set_event(&self, event_id: ApexEventId) -> Result<(), ApexReturnCode>;
+
+    fn reset_event(&self, event_id: ApexEventId) -> Result<(), ApexReturnCode>;
+
+    fn wait_event(
+        &self,
+        event_id: ApexEventId,
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode>;
+
+    fn get_event_id(&self, event_name: &ApexEventName) -> Result<ApexEventId, ApexReturnCode>;
+
+    fn get_event_status(&self, event_id: ApexEventId) -> Result<ApexEventStatus, ApexReturnCode>;
+}
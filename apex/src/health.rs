@@ -0,0 +1,84 @@
+use crate::bindings::*;
+
+pub const APEX_MAX_ERROR_MESSAGE_SIZE: usize = 128;
+
+pub type ApexErrorMessageSize = ApexMessageSize;
+pub type ApexErrorMessage = [ApexByte; APEX_MAX_ERROR_MESSAGE_SIZE];
+
+#[repr(u32)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ApexErrorCode {
+    #[default]
+    DeadlineMissed = 0,
+    ApplicationError = 1,
+    NumericError = 2,
+    IllegalRequest = 3,
+    StackOverflow = 4,
+    MemoryViolation = 5,
+    HardwareFault = 6,
+    PowerFail = 7,
+}
+
+impl TryFrom<ApexUnsigned> for ApexErrorCode {
+    type Error = ApexUnsigned;
+
+    fn try_from(value: ApexUnsigned) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::DeadlineMissed),
+            1 => Ok(Self::ApplicationError),
+            2 => Ok(Self::NumericError),
+            3 => Ok(Self::IllegalRequest),
+            4 => Ok(Self::StackOverflow),
+            5 => Ok(Self::MemoryViolation),
+            6 => Ok(Self::HardwareFault),
+            7 => Ok(Self::PowerFail),
+            _ => Err(value),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApexErrorStatus {
+    pub error_code: ApexErrorCode,
+    pub message: ApexErrorMessage,
+    pub length: ApexErrorMessageSize,
+    pub failed_process_id: ApexProcessId,
+    pub failed_address: ApexSystemAddress,
+}
+
+impl Default for ApexErrorStatus {
+    fn default() -> Self {
+        Self {
+            error_code: ApexErrorCode::default(),
+            message: [0; APEX_MAX_ERROR_MESSAGE_SIZE],
+            length: 0,
+            failed_process_id: APEX_NULL_PROCESS_ID,
+            failed_address: ApexSystemAddress::default(),
+        }
+    }
+}
+
+impl ApexErrorStatus {
+    pub fn message(&self) -> &[ApexByte] {
+        &self.message[..self.length as usize]
+    }
+}
+
+pub trait ApexHealthService {
+    fn report_application_message(&self, message: &[ApexByte]) -> Result<(), ApexReturnCode>;
+
+    fn create_error_handler(
+        &self,
+        entry_point: ApexSystemAddress,
+        stack_size: ApexStackSize,
+    ) -> Result<(), ApexReturnCode>;
+
+    fn get_error_status(&self) -> Result<ApexErrorStatus, ApexReturnCode>;
+
+    fn raise_application_error(
+        &self,
+        error_code: ApexErrorCode,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode>;
+}
@@ -3,8 +3,17 @@
 pub(crate) mod bindings;
 
 pub(crate) mod basic;
+pub(crate) mod blackboard;
+pub(crate) mod buffer;
+pub(crate) mod event;
+pub(crate) mod health;
+pub(crate) mod mutex;
 pub(crate) mod partition;
 pub(crate) mod process;
+pub(crate) mod queuing;
+pub(crate) mod sampling;
+pub(crate) mod schedule;
+pub(crate) mod semaphore;
 pub(crate) mod time;
 
 pub use bindings::*;
@@ -0,0 +1,69 @@
+use crate::bindings::*;
+
+pub type ApexMutexName = ApexName;
+pub type ApexMutexId = ApexLongInteger;
+pub type ApexLockCount = ApexInteger;
+
+pub const APEX_NO_MUTEX_OWNED: ApexMutexId = -2;
+
+#[repr(u32)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ApexMutexState {
+    #[default]
+    Available = 0,
+    Owned = 1,
+}
+
+impl TryFrom<ApexUnsigned> for ApexMutexState {
+    type Error = ApexUnsigned;
+
+    fn try_from(value: ApexUnsigned) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Available),
+            1 => Ok(Self::Owned),
+            _ => Err(value),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApexMutexStatus {
+    pub mutex_owner: ApexProcessId,
+    pub mutex_state: ApexMutexState,
+    pub mutex_priority: ApexPriority,
+    pub lock_count: ApexLockCount,
+    pub waiting_processes: ApexWaitingRange,
+}
+
+pub trait ApexMutexService {
+    fn create_mutex(
+        &self,
+        mutex_name: &ApexMutexName,
+        mutex_priority: ApexPriority,
+        queuing_discipline: ApexQueueDiscipline,
+    ) -> Result<ApexMutexId, ApexReturnCode>;
+
+    fn acquire_mutex(
+        &self,
+        mutex_id: ApexMutexId,
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode>;
+
+    fn release_mutex(&self, mutex_id: ApexMutexId) -> Result<(), ApexReturnCode>;
+
+    fn reset_mutex(
+        &self,
+        mutex_id: ApexMutexId,
+        process_id: ApexProcessId,
+    ) -> Result<(), ApexReturnCode>;
+
+    fn get_mutex_id(&self, mutex_name: &ApexMutexName) -> Result<ApexMutexId, ApexReturnCode>;
+
+    fn get_mutex_status(&self, mutex_id: ApexMutexId) -> Result<ApexMutexStatus, ApexReturnCode>;
+
+    fn get_process_mutex_state(
+        &self,
+        process_id: ApexProcessId,
+    ) -> Result<ApexMutexId, ApexReturnCode>;
+}
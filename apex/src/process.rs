@@ -4,6 +4,8 @@ pub type ApexProcessName = ApexName;
 pub type ApexProcessIndex = ApexInteger;
 pub type ApexProcessId = ApexLongInteger;
 
+pub const APEX_NULL_PROCESS_ID: ApexProcessId = 0;
+
 #[repr(u32)]
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum ApexProcessState {
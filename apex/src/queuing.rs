@@ -0,0 +1,51 @@
+use crate::bindings::*;
+
+pub type ApexQueuingPortName = ApexName;
+pub type ApexQueuingPortId = ApexLongInteger;
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApexQueuingPortStatus {
+    pub nb_message: ApexMessageRange,
+    pub max_nb_message: ApexMessageRange,
+    pub max_message_size: ApexMessageSize,
+    pub port_direction: ApexPortDirection,
+    pub waiting_processes: ApexWaitingRange,
+}
+
+pub trait ApexQueuingPortService {
+    fn create_queuing_port(
+        &self,
+        queuing_port_name: &ApexQueuingPortName,
+        max_message_size: ApexMessageSize,
+        max_nb_message: ApexMessageRange,
+        port_direction: ApexPortDirection,
+        queuing_discipline: ApexQueueDiscipline,
+    ) -> Result<ApexQueuingPortId, ApexReturnCode>;
+
+    fn send_queuing_message(
+        &self,
+        queuing_port_id: ApexQueuingPortId,
+        message: &[ApexByte],
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode>;
+
+    fn receive_queuing_message(
+        &self,
+        queuing_port_id: ApexQueuingPortId,
+        time_out: ApexSystemTime,
+        message: &mut [ApexByte],
+    ) -> Result<ApexMessageSize, ApexReturnCode>;
+
+    fn get_queuing_port_id(
+        &self,
+        queuing_port_name: &ApexQueuingPortName,
+    ) -> Result<ApexQueuingPortId, ApexReturnCode>;
+
+    fn get_queuing_port_status(
+        &self,
+        queuing_port_id: ApexQueuingPortId,
+    ) -> Result<ApexQueuingPortStatus, ApexReturnCode>;
+
+    fn clear_queuing_port(&self, queuing_port_id: ApexQueuingPortId) -> Result<(), ApexReturnCode>;
+}
@@ -0,0 +1,65 @@
+use crate::bindings::*;
+
+pub type ApexSamplingPortName = ApexName;
+pub type ApexSamplingPortId = ApexLongInteger;
+
+#[repr(u32)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ApexValidity {
+    #[default]
+    Invalid = 0,
+    Valid = 1,
+}
+
+impl TryFrom<ApexUnsigned> for ApexValidity {
+    type Error = ApexUnsigned;
+
+    fn try_from(value: ApexUnsigned) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Invalid),
+            1 => Ok(Self::Valid),
+            _ => Err(value),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApexSamplingPortStatus {
+    pub refresh_period: ApexSystemTime,
+    pub max_message_size: ApexMessageSize,
+    pub port_direction: ApexPortDirection,
+    pub last_msg_validity: ApexValidity,
+}
+
+pub trait ApexSamplingPortService {
+    fn create_sampling_port(
+        &self,
+        sampling_port_name: &ApexSamplingPortName,
+        max_message_size: ApexMessageSize,
+        port_direction: ApexPortDirection,
+        refresh_period: ApexSystemTime,
+    ) -> Result<ApexSamplingPortId, ApexReturnCode>;
+
+    fn write_sampling_message(
+        &self,
+        sampling_port_id: ApexSamplingPortId,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode>;
+
+    fn read_sampling_message(
+        &self,
+        sampling_port_id: ApexSamplingPortId,
+        message: &mut [ApexByte],
+    ) -> Result<(ApexMessageSize, ApexValidity), ApexReturnCode>;
+
+    fn get_sampling_port_id(
+        &self,
+        sampling_port_name: &ApexSamplingPortName,
+    ) -> Result<ApexSamplingPortId, ApexReturnCode>;
+
+    fn get_sampling_port_status(
+        &self,
+        sampling_port_id: ApexSamplingPortId,
+    ) -> Result<ApexSamplingPortStatus, ApexReturnCode>;
+}
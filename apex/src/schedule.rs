@@ -0,0 +1,90 @@
+use crate::bindings::*;
+
+pub type ApexScheduleName = ApexName;
+pub type ApexScheduleId = ApexLongInteger;
+pub type ApexWindowIndex = ApexInteger;
+pub type ApexTimingEventIndex = ApexInteger;
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApexScheduleStatus {
+    pub time_of_last_schedule_switch: ApexSystemTime,
+    pub current_schedule: ApexScheduleId,
+    pub next_schedule: ApexScheduleId,
+}
+
+/// The time accounting of a window in the current module schedule.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApexWindowStatus {
+    pub partition_id: ApexPartitionId,
+    pub window_offset: ApexSystemTime,
+    pub window_duration: ApexSystemTime,
+    pub activations: ApexLongInteger,
+    pub last_start: ApexSystemTime,
+    pub last_end: ApexSystemTime,
+    pub last_consumed: ApexSystemTime,
+    pub total_consumed: ApexSystemTime,
+    pub max_jitter: ApexSystemTime,
+    pub max_overrun: ApexSystemTime,
+    pub overruns: ApexLongInteger,
+}
+
+/// The time a core spent in the gaps between the windows of the module schedules.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApexGapStatus {
+    pub idle_time: ApexSystemTime,
+    pub background_time: ApexSystemTime,
+}
+
+#[repr(u32)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ApexTimingEventKind {
+    #[default]
+    WindowStartMissed = 0,
+    LateJoin = 1,
+    OutOfPhase = 2,
+}
+
+/// A timing event recorded in the module health monitor log.
+///
+/// A missed window start expected its window at `expected_time`, a late join expected the core
+/// at the module-wide datum, and a core out of phase expected its major frame to be aligned to
+/// the one of `other_core_id` starting at `expected_time`. `partition_id` is only set for a
+/// missed window start.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApexTimingEvent {
+    pub kind: ApexTimingEventKind,
+    pub core_id: ApexProcessorCoreId,
+    pub partition_id: ApexPartitionId,
+    pub other_core_id: ApexProcessorCoreId,
+    pub expected_time: ApexSystemTime,
+    pub actual_time: ApexSystemTime,
+}
+
+pub trait ApexScheduleService {
+    fn set_module_schedule(&self, schedule_id: ApexScheduleId) -> Result<(), ApexReturnCode>;
+
+    fn get_module_schedule_status(&self) -> Result<ApexScheduleStatus, ApexReturnCode>;
+
+    fn get_module_schedule_id(
+        &self,
+        schedule_name: &ApexScheduleName,
+    ) -> Result<ApexScheduleId, ApexReturnCode>;
+
+    fn get_window_status(
+        &self,
+        core_id: ApexProcessorCoreId,
+        window_index: ApexWindowIndex,
+    ) -> Result<ApexWindowStatus, ApexReturnCode>;
+
+    fn get_gap_status(&self, core_id: ApexProcessorCoreId)
+        -> Result<ApexGapStatus, ApexReturnCode>;
+
+    fn get_timing_event(
+        &self,
+        event_index: ApexTimingEventIndex,
+    ) -> Result<ApexTimingEvent, ApexReturnCode>;
+}
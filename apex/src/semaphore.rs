@@ -0,0 +1,43 @@
+use crate::bindings::*;
+
+pub const APEX_SEMAPHORE_VALUE_MAX: ApexSemaphoreValue = 32767;
+
+pub type ApexSemaphoreName = ApexName;
+pub type ApexSemaphoreId = ApexLongInteger;
+pub type ApexSemaphoreValue = ApexInteger;
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApexSemaphoreStatus {
+    pub current_value: ApexSemaphoreValue,
+    pub maximum_value: ApexSemaphoreValue,
+    pub waiting_processes: ApexWaitingRange,
+}
+
+pub trait ApexSemaphoreService {
+    fn create_semaphore(
+        &self,
+        semaphore_name: &ApexSemaphoreName,
+        current_value: ApexSemaphoreValue,
+        maximum_value: ApexSemaphoreValue,
+        queuing_discipline: ApexQueueDiscipline,
+    ) -> Result<ApexSemaphoreId, ApexReturnCode>;
+
+    fn wait_semaphore(
+        &self,
+        semaphore_id: ApexSemaphoreId,
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode>;
+
+    fn signal_semaphore(&self, semaphore_id: ApexSemaphoreId) -> Result<(), ApexReturnCode>;
+
+    fn get_semaphore_id(
+        &self,
+        semaphore_name: &ApexSemaphoreName,
+    ) -> Result<ApexSemaphoreId, ApexReturnCode>;
+
+    fn get_semaphore_status(
+        &self,
+        semaphore_id: ApexSemaphoreId,
+    ) -> Result<ApexSemaphoreStatus, ApexReturnCode>;
+}
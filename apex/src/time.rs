@@ -19,3 +19,13 @@ pub fn duration_as_time(duration: core::time::Duration) -> ApexSystemTime {
         duration.as_nanos() as ApexSystemTime
     }
 }
+
+pub trait ApexTimeService {
+    fn timed_wait(&self, delay_time: ApexSystemTime) -> Result<(), ApexReturnCode>;
+
+    fn periodic_wait(&self) -> Result<(), ApexReturnCode>;
+
+    fn get_time(&self) -> Result<ApexSystemTime, ApexReturnCode>;
+
+    fn replenish(&self, budget_time: ApexSystemTime) -> Result<(), ApexReturnCode>;
+}
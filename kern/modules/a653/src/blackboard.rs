@@ -0,0 +1,185 @@
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+
+use jrinx_apex::*;
+use jrinx_error::{InternalError, Result};
+use jrinx_serial_id_macro::SerialId;
+use spin::{Mutex, RwLock};
+
+use crate::{
+    partition::{Partition, PartitionId, PartitionMemoryAllocator},
+    process::Process,
+    wait::{WaitQueue, WaitResult},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, SerialId)]
+pub struct BlackboardId(ApexBlackboardId);
+
+pub struct Blackboard {
+    identifier: BlackboardId,
+    name: ApexBlackboardName,
+    partition_id: PartitionId,
+    max_message_size: ApexMessageSize,
+    state: Mutex<BlackboardState>,
+}
+
+pub struct BlackboardConfig {
+    pub name: ApexBlackboardName,
+    pub max_message_size: ApexMessageSize,
+}
+
+struct BlackboardState {
+    message: Vec<ApexByte, PartitionMemoryAllocator>,
+    empty_indicator: ApexEmptyIndicator,
+    readers: WaitQueue<(), Vec<ApexByte>>,
+}
+
+static BLACKBOARDS: RwLock<BTreeMap<BlackboardId, Arc<Blackboard>>> = RwLock::new(BTreeMap::new());
+
+impl From<BlackboardId> for ApexBlackboardId {
+    fn from(id: BlackboardId) -> Self {
+        id.0
+    }
+}
+
+impl From<ApexBlackboardId> for BlackboardId {
+    fn from(value: ApexBlackboardId) -> Self {
+        Self(value)
+    }
+}
+
+impl Blackboard {
+    pub fn new(partition_id: PartitionId, config: &BlackboardConfig) -> Result<Arc<Self>> {
+        let allocator = Partition::find_by_id(partition_id).unwrap().allocator();
+
+        let mut message = Vec::new_in(allocator);
+        message
+            .try_reserve_exact(config.max_message_size as _)
+            .map_err(|_| InternalError::NotEnoughMem)?;
+
+        let blackboard = Arc::new(Self {
+            identifier: BlackboardId::new(),
+            name: config.name,
+            partition_id,
+            max_message_size: config.max_message_size,
+            state: Mutex::new(BlackboardState {
+                message,
+                empty_indicator: ApexEmptyIndicator::Empty,
+                readers: WaitQueue::new(ApexQueueDiscipline::Fifo),
+            }),
+        });
+
+        BLACKBOARDS
+            .write()
+            .insert(blackboard.identifier, blackboard.clone());
+
+        Ok(blackboard)
+    }
+
+    pub fn find_by_id(partition_id: PartitionId, identifier: BlackboardId) -> Option<Arc<Self>> {
+        BLACKBOARDS
+            .read()
+            .get(&identifier)
+            .filter(|blackboard| blackboard.partition_id == partition_id)
+            .cloned()
+    }
+
+    pub fn find_by_name(partition_id: PartitionId, name: &ApexBlackboardName) -> Option<Arc<Self>> {
+        BLACKBOARDS
+            .read()
+            .values()
+            .find(|blackboard| blackboard.partition_id == partition_id && blackboard.name == *name)
+            .cloned()
+    }
+
+    /// Removes all the blackboards created by the partition.
+    pub(crate) fn remove_by_partition(partition_id: PartitionId) {
+        BLACKBOARDS
+            .write()
+            .retain(|_, blackboard| blackboard.partition_id != partition_id);
+    }
+
+    pub fn identifier(&self) -> BlackboardId {
+        self.identifier
+    }
+
+    pub fn name(&self) -> ApexBlackboardName {
+        self.name
+    }
+
+    pub fn partition_id(&self) -> PartitionId {
+        self.partition_id
+    }
+
+    pub fn max_message_size(&self) -> ApexMessageSize {
+        self.max_message_size
+    }
+
+    /// Displays `message`, and wakes every process waiting to read it.
+    pub fn display(&self, message: &[ApexByte]) {
+        let mut state = self.state.lock();
+
+        state.message.clear();
+        state.message.extend_from_slice(message);
+        state.empty_indicator = ApexEmptyIndicator::Occupied;
+
+        for reader in state.readers.drain() {
+            reader.notify(message.to_vec());
+        }
+    }
+
+    /// Reads the displayed message into `message`, waiting up to `timeout` for one.
+    pub fn read(
+        self: &Arc<Self>,
+        message: &mut [ApexByte],
+        timeout: ApexSystemTime,
+    ) -> core::result::Result<ApexMessageSize, ApexReturnCode> {
+        let mut state = self.state.lock();
+
+        if state.empty_indicator == ApexEmptyIndicator::Occupied {
+            message[..state.message.len()].copy_from_slice(&state.message);
+            return Ok(state.message.len() as _);
+        }
+        if timeout == 0 {
+            return Err(ApexReturnCode::NotAvailable);
+        }
+        if !Process::current().unwrap().can_wait() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let token = state.readers.push(());
+        drop(state);
+
+        let blackboard = self.clone();
+        let result = token.wait(timeout, move |identifier| {
+            let waiter = blackboard.state.lock().readers.remove(identifier);
+            if let Some(waiter) = waiter {
+                waiter.time_out();
+            }
+        });
+
+        match result {
+            WaitResult::Notified(displayed) => {
+                message[..displayed.len()].copy_from_slice(&displayed);
+                Ok(displayed.len() as _)
+            }
+            WaitResult::TimedOut => Err(ApexReturnCode::TimedOut),
+        }
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+
+        state.message.clear();
+        state.empty_indicator = ApexEmptyIndicator::Empty;
+    }
+
+    pub fn status(&self) -> ApexBlackboardStatus {
+        let state = self.state.lock();
+
+        ApexBlackboardStatus {
+            empty_indicator: state.empty_indicator,
+            max_message_size: self.max_message_size,
+            waiting_processes: state.readers.len() as _,
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+
+use jrinx_apex::*;
+use jrinx_error::{InternalError, Result};
+use jrinx_serial_id_macro::SerialId;
+use spin::{Mutex, RwLock};
+
+use crate::{
+    partition::{Partition, PartitionId, PartitionMemoryAllocator},
+    process::Process,
+    wait::{WaitQueue, WaitResult},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, SerialId)]
+pub struct BufferId(ApexBufferId);
+
+pub struct Buffer {
+    identifier: BufferId,
+    name: ApexBufferName,
+    partition_id: PartitionId,
+    max_message_size: ApexMessageSize,
+    max_nb_message: ApexMessageRange,
+    discipline: ApexQueueDiscipline,
+    state: Mutex<BufferState>,
+}
+
+pub struct BufferConfig {
+    pub name: ApexBufferName,
+    pub max_message_size: ApexMessageSize,
+    pub max_nb_message: ApexMessageRange,
+    pub discipline: ApexQueueDiscipline,
+}
+
+/// A ring of `max_nb_message` slots of `max_message_size` bytes, allocated from the memory of
+/// the owning partition when the buffer is created.
+struct BufferState {
+    storage: Vec<ApexByte, PartitionMemoryAllocator>,
+    lengths: Vec<ApexMessageSize, PartitionMemoryAllocator>,
+    head: usize,
+    nb_message: usize,
+    senders: WaitQueue<Vec<ApexByte>>,
+    receivers: WaitQueue<(), Vec<ApexByte>>,
+}
+
+static BUFFERS: RwLock<BTreeMap<BufferId, Arc<Buffer>>> = RwLock::new(BTreeMap::new());
+
+impl From<BufferId> for ApexBufferId {
+    fn from(id: BufferId) -> Self {
+        id.0
+    }
+}
+
+impl From<ApexBufferId> for BufferId {
+    fn from(value: ApexBufferId) -> Self {
+        Self(value)
+    }
+}
+
+impl Buffer {
+    pub fn new(partition_id: PartitionId, config: &BufferConfig) -> Result<Arc<Self>> {
+        let allocator = Partition::find_by_id(partition_id).unwrap().allocator();
+        let max_message_size = config.max_message_size as usize;
+        let max_nb_message = config.max_nb_message as usize;
+
+        let storage_size = max_message_size
+            .checked_mul(max_nb_message)
+            .ok_or(InternalError::NotEnoughMem)?;
+        let mut storage = Vec::new_in(allocator);
+        storage
+            .try_reserve_exact(storage_size)
+            .map_err(|_| InternalError::NotEnoughMem)?;
+        storage.resize(storage_size, 0);
+
+        let mut lengths = Vec::new_in(allocator);
+        lengths
+            .try_reserve_exact(max_nb_message)
+            .map_err(|_| InternalError::NotEnoughMem)?;
+        lengths.resize(max_nb_message, 0);
+
+        let buffer = Arc::new(Self {
+            identifier: BufferId::new(),
+            name: config.name,
+            partition_id,
+            max_message_size: config.max_message_size,
+            max_nb_message: config.max_nb_message,
+            discipline: config.discipline,
+            state: Mutex::new(BufferState {
+                storage,
+                lengths,
+                head: 0,
+                nb_message: 0,
+                senders: WaitQueue::new(config.discipline),
+                receivers: WaitQueue::new(config.discipline),
+            }),
+        });
+
+        BUFFERS.write().insert(buffer.identifier, buffer.clone());
+
+        Ok(buffer)
+    }
+
+    pub fn find_by_id(partition_id: PartitionId, identifier: BufferId) -> Option<Arc<Self>> {
+        BUFFERS
+            .read()
+            .get(&identifier)
+            .filter(|buffer| buffer.partition_id == partition_id)
+            .cloned()
+    }
+
+    pub fn find_by_name(partition_id: PartitionId, name: &ApexBufferName) -> Option<Arc<Self>> {
+        BUFFERS
+            .read()
+            .values()
+            .find(|buffer| buffer.partition_id == partition_id && buffer.name == *name)
+            .cloned()
+    }
+
+    /// Removes all the buffers created by the partition.
+    pub(crate) fn remove_by_partition(partition_id: PartitionId) {
+        BUFFERS
+            .write()
+            .retain(|_, buffer| buffer.partition_id != partition_id);
+    }
+
+    pub fn identifier(&self) -> BufferId {
+        self.identifier
+    }
+
+    pub fn name(&self) -> ApexBufferName {
+        self.name
+    }
+
+    pub fn partition_id(&self) -> PartitionId {
+        self.partition_id
+    }
+
+    pub fn max_message_size(&self) -> ApexMessageSize {
+        self.max_message_size
+    }
+
+    pub fn max_nb_message(&self) -> ApexMessageRange {
+        self.max_nb_message
+    }
+
+    pub fn discipline(&self) -> ApexQueueDiscipline {
+        self.discipline
+    }
+
+    /// Sends `message`, waiting up to `timeout` for a free slot.
+    pub fn send(
+        self: &Arc<Self>,
+        message: &[ApexByte],
+        timeout: ApexSystemTime,
+    ) -> core::result::Result<(), ApexReturnCode> {
+        let mut state = self.state.lock();
+
+        if let Some(receiver) = state.receivers.pop() {
+            receiver.notify(message.to_vec());
+            return Ok(());
+        }
+        if state.nb_message < self.max_nb_message as usize {
+            state.push(self.max_message_size as _, message);
+            return Ok(());
+        }
+        if timeout == 0 {
+            return Err(ApexReturnCode::NotAvailable);
+        }
+        if !Process::current().unwrap().can_wait() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let token = state.senders.push(message.to_vec());
+        drop(state);
+
+        let buffer = self.clone();
+        let result = token.wait(timeout, move |identifier| {
+            let waiter = buffer.state.lock().senders.remove(identifier);
+            if let Some(waiter) = waiter {
+                waiter.time_out();
+            }
+        });
+
+        match result {
+            WaitResult::Notified(()) => Ok(()),
+            WaitResult::TimedOut => Err(ApexReturnCode::TimedOut),
+        }
+    }
+
+    /// Receives a message into `message`, waiting up to `timeout` for one to arrive.
+    pub fn receive(
+        self: &Arc<Self>,
+        message: &mut [ApexByte],
+        timeout: ApexSystemTime,
+    ) -> core::result::Result<ApexMessageSize, ApexReturnCode> {
+        let mut state = self.state.lock();
+
+        if state.nb_message != 0 {
+            let length = state.pop(self.max_message_size as _, message);
+            if let Some(sender) = state.senders.pop() {
+                let pending = sender.notify(());
+                state.push(self.max_message_size as _, &pending);
+            }
+            return Ok(length);
+        }
+        if timeout == 0 {
+            return Err(ApexReturnCode::NotAvailable);
+        }
+        if !Process::current().unwrap().can_wait() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let token = state.receivers.push(());
+        drop(state);
+
+        let buffer = self.clone();
+        let result = token.wait(timeout, move |identifier| {
+            let waiter = buffer.state.lock().receivers.remove(identifier);
+            if let Some(waiter) = waiter {
+                waiter.time_out();
+            }
+        });
+
+        match result {
+            WaitResult::Notified(pending) => {
+                message[..pending.len()].copy_from_slice(&pending);
+                Ok(pending.len() as _)
+            }
+            WaitResult::TimedOut => Err(ApexReturnCode::TimedOut),
+        }
+    }
+
+    pub fn status(&self) -> ApexBufferStatus {
+        let state = self.state.lock();
+
+        ApexBufferStatus {
+            nb_message: state.nb_message as _,
+            max_nb_message: self.max_nb_message,
+            max_message_size: self.max_message_size,
+            waiting_processes: (state.senders.len() + state.receivers.len()) as _,
+        }
+    }
+}
+
+impl BufferState {
+    fn push(&mut self, slot_size: usize, message: &[ApexByte]) {
+        let slot = (self.head + self.nb_message) % self.lengths.len();
+        self.storage[slot * slot_size..][..message.len()].copy_from_slice(message);
+        self.lengths[slot] = message.len() as _;
+        self.nb_message += 1;
+    }
+
+    fn pop(&mut self, slot_size: usize, message: &mut [ApexByte]) -> ApexMessageSize {
+        let slot = self.head;
+        let length = self.lengths[slot];
+        message[..length as usize]
+            .copy_from_slice(&self.storage[slot * slot_size..][..length as usize]);
+        self.head = (self.head + 1) % self.lengths.len();
+        self.nb_message -= 1;
+        length
+    }
+}
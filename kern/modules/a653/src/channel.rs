@@ -0,0 +1,151 @@
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+
+use jrinx_apex::*;
+use jrinx_error::{InternalError, Result};
+use jrinx_serial_id_macro::SerialId;
+use spin::RwLock;
+
+use crate::{queuing::QueuingChannel, sampling::SamplingChannel};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, SerialId)]
+pub struct ChannelId(u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelPort {
+    pub partition: ApexName,
+    pub port: ApexName,
+}
+
+pub struct Channel {
+    identifier: ChannelId,
+    name: ApexName,
+    max_message_size: ApexMessageSize,
+    source: ChannelPort,
+    destinations: Vec<ChannelPort>,
+    inner: ChannelInner,
+}
+
+pub(crate) enum ChannelInner {
+    Sampling(SamplingChannel),
+    Queuing(QueuingChannel),
+}
+
+pub struct ChannelConfig {
+    pub name: ApexName,
+    pub max_message_size: ApexMessageSize,
+    pub source: ChannelPort,
+    pub destinations: Vec<ChannelPort>,
+    pub channel_type: ChannelTypeConfig,
+}
+
+pub enum ChannelTypeConfig {
+    Sampling,
+    Queuing { max_nb_message: ApexMessageRange },
+}
+
+static CHANNELS: RwLock<BTreeMap<ChannelId, Arc<Channel>>> = RwLock::new(BTreeMap::new());
+
+impl Channel {
+    pub fn new(config: &ChannelConfig) -> Result<Arc<Self>> {
+        if config.max_message_size == 0 || config.destinations.is_empty() {
+            return Err(InternalError::InvalidChannelConfig);
+        }
+        if let ChannelTypeConfig::Queuing { max_nb_message } = config.channel_type {
+            if max_nb_message == 0 || config.destinations.len() != 1 {
+                return Err(InternalError::InvalidChannelConfig);
+            }
+        }
+
+        let mut channels = CHANNELS.write();
+
+        if channels.values().any(|channel| {
+            channel.name == config.name
+                || channel
+                    .ports()
+                    .any(|port| *port == config.source || config.destinations.contains(port))
+        }) {
+            return Err(InternalError::InvalidChannelConfig);
+        }
+
+        let channel = Arc::new(Self {
+            identifier: ChannelId::new(),
+            name: config.name,
+            max_message_size: config.max_message_size,
+            source: config.source,
+            destinations: config.destinations.clone(),
+            inner: match config.channel_type {
+                ChannelTypeConfig::Sampling => ChannelInner::Sampling(SamplingChannel::new()),
+                ChannelTypeConfig::Queuing { max_nb_message } => {
+                    ChannelInner::Queuing(QueuingChannel::new(max_nb_message))
+                }
+            },
+        });
+
+        channels.insert(channel.identifier, channel.clone());
+
+        Ok(channel)
+    }
+
+    pub fn find_by_name(name: &ApexName) -> Option<Arc<Self>> {
+        CHANNELS
+            .read()
+            .values()
+            .find(|channel| channel.name == *name)
+            .cloned()
+    }
+
+    pub fn find_by_port(port: &ChannelPort) -> Option<(Arc<Self>, ApexPortDirection)> {
+        CHANNELS.read().values().find_map(|channel| {
+            if channel.source == *port {
+                Some((channel.clone(), ApexPortDirection::Source))
+            } else if channel.destinations.contains(port) {
+                Some((channel.clone(), ApexPortDirection::Destination))
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn identifier(&self) -> ChannelId {
+        self.identifier
+    }
+
+    pub fn name(&self) -> ApexName {
+        self.name
+    }
+
+    pub fn max_message_size(&self) -> ApexMessageSize {
+        self.max_message_size
+    }
+
+    pub fn source(&self) -> ChannelPort {
+        self.source
+    }
+
+    pub fn destinations(&self) -> &[ChannelPort] {
+        &self.destinations
+    }
+
+    pub fn max_nb_message(&self) -> Option<ApexMessageRange> {
+        match &self.inner {
+            ChannelInner::Queuing(queuing) => Some(queuing.max_nb_message()),
+            _ => None,
+        }
+    }
+
+    pub fn is_sampling(&self) -> bool {
+        matches!(self.inner, ChannelInner::Sampling(_))
+    }
+
+    pub fn is_queuing(&self) -> bool {
+        matches!(self.inner, ChannelInner::Queuing(_))
+    }
+
+    pub(crate) fn inner(&self) -> &ChannelInner {
+        &self.inner
+    }
+
+    fn ports(&self) -> impl Iterator<Item = &ChannelPort> {
+        core::iter::once(&self.source).chain(self.destinations.iter())
+    }
+}
@@ -0,0 +1,157 @@
+use alloc::{collections::BTreeMap, sync::Arc};
+
+use jrinx_apex::*;
+use jrinx_error::Result;
+use jrinx_serial_id_macro::SerialId;
+use spin::{Mutex, RwLock};
+
+use crate::{
+    partition::PartitionId,
+    process::Process,
+    wait::{WaitQueue, WaitResult},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, SerialId)]
+pub struct EventId(ApexEventId);
+
+pub struct Event {
+    identifier: EventId,
+    name: ApexEventName,
+    partition_id: PartitionId,
+    state: Mutex<EventState>,
+}
+
+pub struct EventConfig {
+    pub name: ApexEventName,
+}
+
+struct EventState {
+    event_state: ApexEventState,
+    waiters: WaitQueue,
+}
+
+static EVENTS: RwLock<BTreeMap<EventId, Arc<Event>>> = RwLock::new(BTreeMap::new());
+
+impl From<EventId> for ApexEventId {
+    fn from(id: EventId) -> Self {
+        id.0
+    }
+}
+
+impl From<ApexEventId> for EventId {
+    fn from(value: ApexEventId) -> Self {
+        Self(value)
+    }
+}
+
+impl Event {
+    pub fn new(partition_id: PartitionId, config: &EventConfig) -> Result<Arc<Self>> {
+        let event = Arc::new(Self {
+            identifier: EventId::new(),
+            name: config.name,
+            partition_id,
+            state: Mutex::new(EventState {
+                event_state: ApexEventState::Down,
+                waiters: WaitQueue::new(ApexQueueDiscipline::Fifo),
+            }),
+        });
+
+        EVENTS.write().insert(event.identifier, event.clone());
+
+        Ok(event)
+    }
+
+    pub fn find_by_id(partition_id: PartitionId, identifier: EventId) -> Option<Arc<Self>> {
+        EVENTS
+            .read()
+            .get(&identifier)
+            .filter(|event| event.partition_id == partition_id)
+            .cloned()
+    }
+
+    pub fn find_by_name(partition_id: PartitionId, name: &ApexEventName) -> Option<Arc<Self>> {
+        EVENTS
+            .read()
+            .values()
+            .find(|event| event.partition_id == partition_id && event.name == *name)
+            .cloned()
+    }
+
+    /// Removes all the events created by the partition.
+    pub(crate) fn remove_by_partition(partition_id: PartitionId) {
+        EVENTS
+            .write()
+            .retain(|_, event| event.partition_id != partition_id);
+    }
+
+    pub fn identifier(&self) -> EventId {
+        self.identifier
+    }
+
+    pub fn name(&self) -> ApexEventName {
+        self.name
+    }
+
+    pub fn partition_id(&self) -> PartitionId {
+        self.partition_id
+    }
+
+    /// Sets the event up, and moves every waiting process to ready.
+    ///
+    /// A woken process preempts the caller only once the partition's preemption lock is released.
+    pub fn set(&self) {
+        let mut state = self.state.lock();
+
+        state.event_state = ApexEventState::Up;
+        for waiter in state.waiters.drain() {
+            waiter.notify(());
+        }
+    }
+
+    pub fn reset(&self) {
+        self.state.lock().event_state = ApexEventState::Down;
+    }
+
+    /// Waits up to `timeout` for the event to be set up.
+    pub fn wait(
+        self: &Arc<Self>,
+        timeout: ApexSystemTime,
+    ) -> core::result::Result<(), ApexReturnCode> {
+        let mut state = self.state.lock();
+
+        if state.event_state == ApexEventState::Up {
+            return Ok(());
+        }
+        if timeout == 0 {
+            return Err(ApexReturnCode::NotAvailable);
+        }
+        if !Process::current().unwrap().can_wait() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let token = state.waiters.push(());
+        drop(state);
+
+        let event = self.clone();
+        let result = token.wait(timeout, move |identifier| {
+            let waiter = event.state.lock().waiters.remove(identifier);
+            if let Some(waiter) = waiter {
+                waiter.time_out();
+            }
+        });
+
+        match result {
+            WaitResult::Notified(()) => Ok(()),
+            WaitResult::TimedOut => Err(ApexReturnCode::TimedOut),
+        }
+    }
+
+    pub fn status(&self) -> ApexEventStatus {
+        let state = self.state.lock();
+
+        ApexEventStatus {
+            event_state: state.event_state,
+            waiting_processes: state.waiters.len() as _,
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use alloc::{collections::VecDeque, sync::Arc, vec::Vec};
+use jrinx_apex::*;
+use jrinx_hal::{Cpu, Hal, HaltReason, Interrupt};
+use jrinx_multitask::runtime::{Runtime, RuntimeTimingEvent};
+use spin::{Mutex, RwLock};
+
+use crate::{
+    partition::{Partition, PartitionId},
+    process::Process,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryAction {
+    Ignore,
+    StopProcess,
+    ColdStart,
+    WarmStart,
+    Idle,
+    Reset,
+}
+
+/// Maps each class of error, identified by its [`ApexErrorCode`], to a [`RecoveryAction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryTable([RecoveryAction; RecoveryTable::NUM]);
+
+static MODULE_RECOVERY_TABLE: RwLock<RecoveryTable> = RwLock::new(RecoveryTable::MODULE);
+
+/// An event recorded by the health monitor of the module, which is detected outside any process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleHmEvent {
+    /// A window of `partition_id` on `cpu_id` started at `actual` instead of `expected`.
+    WindowStartMissed {
+        cpu_id: usize,
+        partition_id: Option<PartitionId>,
+        expected: ApexSystemTime,
+        actual: ApexSystemTime,
+    },
+    /// `cpu_id` joined the major frames synchronized to `datum` late at `joined`.
+    LateJoin {
+        cpu_id: usize,
+        datum: ApexSystemTime,
+        joined: ApexSystemTime,
+    },
+    /// The major frame of `cpu_id` starting at `datum` is out of phase with the one of
+    /// `other_cpu_id` starting at `other_datum`.
+    OutOfPhase {
+        cpu_id: usize,
+        other_cpu_id: usize,
+        datum: ApexSystemTime,
+        other_datum: ApexSystemTime,
+    },
+}
+
+impl ModuleHmEvent {
+    /// Returns the class of error the event is recovered from by the module recovery table,
+    /// which is [`ApexErrorCode::DeadlineMissed`] for all the timing events.
+    pub fn error_code(&self) -> ApexErrorCode {
+        match self {
+            Self::WindowStartMissed { .. } | Self::LateJoin { .. } | Self::OutOfPhase { .. } => {
+                ApexErrorCode::DeadlineMissed
+            }
+        }
+    }
+}
+
+/// The module health monitor log, which keeps the latest events.
+static MODULE_HM_LOG: Mutex<VecDeque<ModuleHmEvent>> = Mutex::new(VecDeque::new());
+
+const MODULE_HM_LOG_SIZE: usize = 64;
+
+impl RecoveryTable {
+    const NUM: usize = ApexErrorCode::PowerFail as usize + 1;
+
+    /// The default recovery table of the module, which resets the module except on the timing
+    /// events in the module health monitor log.
+    const MODULE: Self = {
+        let mut actions = [RecoveryAction::Reset; Self::NUM];
+        actions[ApexErrorCode::DeadlineMissed as usize] = RecoveryAction::Ignore;
+        Self(actions)
+    };
+
+    pub const fn new(action: RecoveryAction) -> Self {
+        Self([action; Self::NUM])
+    }
+
+    pub fn action(&self, error_code: ApexErrorCode) -> RecoveryAction {
+        self.0[error_code as usize]
+    }
+
+    pub fn set_action(&mut self, error_code: ApexErrorCode, action: RecoveryAction) {
+        self.0[error_code as usize] = action;
+    }
+}
+
+pub fn module_recovery_table() -> RecoveryTable {
+    *MODULE_RECOVERY_TABLE.read()
+}
+
+pub fn set_module_recovery_table(table: RecoveryTable) {
+    *MODULE_RECOVERY_TABLE.write() = table;
+}
+
+/// Records the events detected by the runtime in the module health monitor log.
+pub fn init() {
+    Runtime::set_timing_handler(timing);
+}
+
+/// Returns the module health monitor log, from the oldest event to the latest one.
+///
+/// The log is recorded by the runtime after preempting a window, so it is only locked with
+/// interrupts disabled.
+pub fn module_hm_log() -> Vec<ModuleHmEvent> {
+    hal!()
+        .interrupt()
+        .with_saved_off(|| MODULE_HM_LOG.lock().iter().copied().collect())
+}
+
+/// Records `event` in the module health monitor log, and recovers from it by the module
+/// recovery table, since it is detected by the runtime outside any partition.
+fn record(event: ModuleHmEvent) {
+    warn!("module health monitor recorded {:?}", event);
+
+    hal!().interrupt().with_saved_off(|| {
+        let mut log = MODULE_HM_LOG.lock();
+        if log.len() == MODULE_HM_LOG_SIZE {
+            log.pop_front();
+        }
+        log.push_back(event);
+    });
+
+    recover(None, event.error_code());
+}
+
+fn timing(event: RuntimeTimingEvent) {
+    let cpu_id = hal!().cpu().id();
+
+    record(match event {
+        RuntimeTimingEvent::WindowStartMissed {
+            entry,
+            expected,
+            actual,
+        } => ModuleHmEvent::WindowStartMissed {
+            cpu_id,
+            partition_id: Partition::find_by_inspector(cpu_id, entry.inspector_id)
+                .map(|partition| partition.identifier()),
+            expected: duration_as_time(expected),
+            actual: duration_as_time(actual),
+        },
+        RuntimeTimingEvent::LateJoin { datum, joined } => ModuleHmEvent::LateJoin {
+            cpu_id,
+            datum: duration_as_time(datum),
+            joined: duration_as_time(joined),
+        },
+        RuntimeTimingEvent::OutOfPhase {
+            datum,
+            other_cpu_id,
+            other_datum,
+        } => ModuleHmEvent::OutOfPhase {
+            cpu_id,
+            other_cpu_id,
+            datum: duration_as_time(datum),
+            other_datum: duration_as_time(other_datum),
+        },
+    });
+}
+
+/// Reports an error detected in `process` to the health monitor of its partition.
+///
+/// The error is queued for the error handler of the partition, unless there is none to handle
+/// it (i.e., the partition has not created one, is not in normal mode, or the error is detected
+/// in the error handler itself), in which case it is recovered from by the recovery tables.
+/// Returns the error handler if it is dormant and must be started.
+pub fn report(
+    process: &Process,
+    error_code: ApexErrorCode,
+    message: &[ApexByte],
+    failed_address: usize,
+) -> Option<Arc<Process>> {
+    dispatch(process, error_code, message, failed_address, true)
+}
+
+/// Reports an error that `process` survives, which only reaches the error handler (if any).
+///
+/// Returns the error handler if it needs to be started.
+pub fn notify(
+    process: &Process,
+    error_code: ApexErrorCode,
+    message: &[ApexByte],
+    failed_address: usize,
+) -> Option<Arc<Process>> {
+    dispatch(process, error_code, message, failed_address, false)
+}
+
+fn dispatch(
+    process: &Process,
+    error_code: ApexErrorCode,
+    message: &[ApexByte],
+    failed_address: usize,
+    recoverable: bool,
+) -> Option<Arc<Process>> {
+    let partition = Partition::find_by_id(process.partition_id()).unwrap();
+
+    let length = message.len().min(APEX_MAX_ERROR_MESSAGE_SIZE);
+    let mut error = ApexErrorStatus {
+        error_code,
+        length: length as _,
+        failed_process_id: process.identifier().into(),
+        failed_address: failed_address.into(),
+        ..Default::default()
+    };
+    error.message[..length].copy_from_slice(&message[..length]);
+
+    warn!(
+        "process {:?} of partition {:?} raised {:?} at {:#x}",
+        process.name(),
+        partition.name(),
+        error_code,
+        failed_address
+    );
+
+    match partition.error_handler() {
+        Some(handler)
+            if partition.operating_mode() == ApexOperatingMode::Normal
+                && handler.identifier() != process.identifier() =>
+        {
+            partition.push_error(error);
+            (handler.process_state() == ApexProcessState::Dormant).then_some(handler)
+        }
+        _ => {
+            if recoverable {
+                recover(Some(process), error_code);
+            }
+            None
+        }
+    }
+}
+
+/// Recovers from an error by the recovery table of the partition of `process`, or by the
+/// recovery table of the module if the error is detected outside any partition.
+pub fn recover(process: Option<&Process>, error_code: ApexErrorCode) {
+    let partition = match process {
+        Some(process) => Partition::find_by_id(process.partition_id()),
+        None => Partition::current(),
+    };
+
+    let Some(partition) = partition else {
+        let action = module_recovery_table().action(error_code);
+        error!("module recovers from {:?} by {:?}", error_code, action);
+        match action {
+            RecoveryAction::Ignore => {}
+            _ => hal!().halt(HaltReason::SysFailure),
+        }
+        return;
+    };
+
+    let action = partition.recovery_table().action(error_code);
+    error!(
+        "partition {:?} recovers from {:?} by {:?}",
+        partition.name(),
+        error_code,
+        action
+    );
+    match action {
+        RecoveryAction::Ignore => {}
+        RecoveryAction::StopProcess => {
+            if let Some(process) = process {
+                if let Err(err) = process.stop() {
+                    warn!("failed to stop process {:?}: {:?}", process.name(), err);
+                }
+            }
+        }
+        // a partition never resets the module, which its recovery table is validated against,
+        // so it is at worst shut down alone
+        RecoveryAction::Idle | RecoveryAction::Reset => partition.shutdown(),
+        RecoveryAction::ColdStart | RecoveryAction::WarmStart => {
+            let mode = match action {
+                RecoveryAction::ColdStart => ApexOperatingMode::ColdStart,
+                _ => ApexOperatingMode::WarmStart,
+            };
+            if let Err(err) = partition.restart(mode, ApexStartCondition::HmPartitionRestart) {
+                error!(
+                    "failed to restart partition {:?}: {:?}",
+                    partition.name(),
+                    err
+                );
+                partition.shutdown();
+            }
+        }
+    }
+}
@@ -9,8 +9,19 @@ extern crate log;
 #[macro_use]
 extern crate jrinx_hal;
 
+pub mod blackboard;
+pub mod buffer;
+pub mod channel;
+pub mod event;
+pub mod health;
+pub mod mutex;
 pub mod partition;
 pub mod process;
+pub mod queuing;
+pub mod sampling;
+pub mod schedule;
+pub mod semaphore;
+pub mod wait;
 
 #[derive(Debug, Clone, Copy)]
 pub enum A653Entry {
@@ -0,0 +1,275 @@
+use alloc::{collections::BTreeMap, sync::Arc};
+
+use jrinx_apex::*;
+use jrinx_error::Result;
+use jrinx_serial_id_macro::SerialId;
+use spin::{Mutex as SpinMutex, RwLock};
+
+use crate::{
+    partition::PartitionId,
+    process::Process,
+    wait::{WaitQueue, WaitResult},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, SerialId)]
+pub struct MutexId(ApexMutexId);
+
+pub struct Mutex {
+    identifier: MutexId,
+    name: ApexMutexName,
+    partition_id: PartitionId,
+    priority: ApexPriority,
+    discipline: ApexQueueDiscipline,
+    state: SpinMutex<MutexState>,
+}
+
+pub struct MutexConfig {
+    pub name: ApexMutexName,
+    pub priority: ApexPriority,
+    pub discipline: ApexQueueDiscipline,
+}
+
+struct MutexState {
+    owner: Option<Arc<Process>>,
+    lock_count: ApexLockCount,
+    retained_priority: ApexPriority,
+    waiters: WaitQueue,
+}
+
+static MUTEXES: RwLock<BTreeMap<MutexId, Arc<Mutex>>> = RwLock::new(BTreeMap::new());
+
+impl From<MutexId> for ApexMutexId {
+    fn from(id: MutexId) -> Self {
+        id.0
+    }
+}
+
+impl From<ApexMutexId> for MutexId {
+    fn from(value: ApexMutexId) -> Self {
+        Self(value)
+    }
+}
+
+impl Mutex {
+    pub fn new(partition_id: PartitionId, config: &MutexConfig) -> Result<Arc<Self>> {
+        let mutex = Arc::new(Self {
+            identifier: MutexId::new(),
+            name: config.name,
+            partition_id,
+            priority: config.priority,
+            discipline: config.discipline,
+            state: SpinMutex::new(MutexState {
+                owner: None,
+                lock_count: 0,
+                retained_priority: 0,
+                waiters: WaitQueue::new(config.discipline),
+            }),
+        });
+
+        MUTEXES.write().insert(mutex.identifier, mutex.clone());
+
+        Ok(mutex)
+    }
+
+    pub fn find_by_id(partition_id: PartitionId, identifier: MutexId) -> Option<Arc<Self>> {
+        MUTEXES
+            .read()
+            .get(&identifier)
+            .filter(|mutex| mutex.partition_id == partition_id)
+            .cloned()
+    }
+
+    pub fn find_by_name(partition_id: PartitionId, name: &ApexMutexName) -> Option<Arc<Self>> {
+        MUTEXES
+            .read()
+            .values()
+            .find(|mutex| mutex.partition_id == partition_id && mutex.name == *name)
+            .cloned()
+    }
+
+    /// Removes all the mutexes created by the partition.
+    pub(crate) fn remove_by_partition(partition_id: PartitionId) {
+        MUTEXES
+            .write()
+            .retain(|_, mutex| mutex.partition_id != partition_id);
+    }
+
+    pub fn identifier(&self) -> MutexId {
+        self.identifier
+    }
+
+    pub fn name(&self) -> ApexMutexName {
+        self.name
+    }
+
+    pub fn partition_id(&self) -> PartitionId {
+        self.partition_id
+    }
+
+    pub fn priority(&self) -> ApexPriority {
+        self.priority
+    }
+
+    pub fn discipline(&self) -> ApexQueueDiscipline {
+        self.discipline
+    }
+
+    /// Acquires the mutex for the current process, waiting up to `timeout` for its owner to
+    /// release it.
+    ///
+    /// The owner runs at the priority of the mutex until the mutex is released.
+    pub fn acquire(
+        self: &Arc<Self>,
+        timeout: ApexSystemTime,
+    ) -> core::result::Result<(), ApexReturnCode> {
+        let process = Process::current().unwrap();
+        let mut state = self.state.lock();
+
+        match &state.owner {
+            None => {
+                self.grant(&mut state, process);
+                return Ok(());
+            }
+            Some(owner) if owner.identifier() == process.identifier() => {
+                if state.lock_count >= APEX_LOCK_LEVEL_MAX {
+                    return Err(ApexReturnCode::InvalidConfig);
+                }
+                state.lock_count += 1;
+                return Ok(());
+            }
+            Some(_) => {}
+        }
+        if timeout == 0 {
+            return Err(ApexReturnCode::NotAvailable);
+        }
+        if !process.can_wait() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let token = state.waiters.push(());
+        drop(state);
+
+        let mutex = self.clone();
+        let result = token.wait(timeout, move |identifier| {
+            let waiter = mutex.state.lock().waiters.remove(identifier);
+            if let Some(waiter) = waiter {
+                waiter.time_out();
+            }
+        });
+
+        match result {
+            WaitResult::Notified(()) => Ok(()),
+            WaitResult::TimedOut => Err(ApexReturnCode::TimedOut),
+        }
+    }
+
+    /// Releases one lock of the current process on the mutex, handing the mutex over to the
+    /// first waiting process once the lock count drops to zero.
+    pub fn release(&self) -> core::result::Result<(), ApexReturnCode> {
+        let process = Process::current().unwrap();
+        let mut state = self.state.lock();
+
+        if !state
+            .owner
+            .as_ref()
+            .is_some_and(|owner| owner.identifier() == process.identifier())
+        {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        state.lock_count -= 1;
+        if state.lock_count == 0 {
+            self.hand_over(&mut state);
+        }
+
+        Ok(())
+    }
+
+    /// Releases every lock of `process` on the mutex at once.
+    pub fn reset(&self, process: &Process) -> core::result::Result<(), ApexReturnCode> {
+        let mut state = self.state.lock();
+
+        if !state
+            .owner
+            .as_ref()
+            .is_some_and(|owner| owner.identifier() == process.identifier())
+        {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        state.lock_count = 0;
+        self.hand_over(&mut state);
+
+        Ok(())
+    }
+
+    /// Sets the priority `process` returns to when it releases the mutex.
+    pub fn set_retained_priority(
+        &self,
+        process: &Process,
+        priority: ApexPriority,
+    ) -> core::result::Result<(), ApexReturnCode> {
+        let mut state = self.state.lock();
+
+        if !state
+            .owner
+            .as_ref()
+            .is_some_and(|owner| owner.identifier() == process.identifier())
+        {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        state.retained_priority = priority;
+
+        Ok(())
+    }
+
+    pub fn status(&self) -> ApexMutexStatus {
+        let state = self.state.lock();
+
+        ApexMutexStatus {
+            mutex_owner: state
+                .owner
+                .as_ref()
+                .map_or(APEX_NULL_PROCESS_ID, |owner| owner.identifier().into()),
+            mutex_state: match state.owner {
+                Some(_) => ApexMutexState::Owned,
+                None => ApexMutexState::Available,
+            },
+            mutex_priority: self.priority,
+            lock_count: state.lock_count,
+            waiting_processes: state.waiters.len() as _,
+        }
+    }
+
+    fn grant(&self, state: &mut MutexState, process: Arc<Process>) {
+        state.lock_count = 1;
+        state.retained_priority = process.curr_priority();
+
+        process.set_mutex(Some(self.identifier));
+        set_priority(&process, self.priority);
+
+        state.owner = Some(process);
+    }
+
+    fn hand_over(&self, state: &mut MutexState) {
+        let owner = state.owner.take().unwrap();
+        owner.set_mutex(None);
+        set_priority(&owner, state.retained_priority);
+
+        if let Some(waiter) = state.waiters.pop() {
+            self.grant(state, waiter.process().clone());
+            waiter.notify(());
+        }
+    }
+}
+
+fn set_priority(process: &Process, priority: ApexPriority) {
+    if let Err(err) = process.set_curr_priority(priority) {
+        warn!(
+            "failed to set priority of process {:?}: {:?}",
+            process.name(),
+            err
+        );
+    }
+}
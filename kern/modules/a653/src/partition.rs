@@ -8,26 +8,33 @@ use alloc::{
 use core::{alloc::Allocator, ops::Deref, sync::atomic::AtomicUsize};
 
 use elf::{
-    abi::{PF_R, PF_W, PF_X},
+    abi::{PF_R, PF_W, PF_X, PT_LOAD},
     endian::AnyEndian,
     ElfBytes,
 };
 use jrinx_addr::VirtAddr;
 use jrinx_apex::*;
 use jrinx_error::{InternalError, Result};
-use jrinx_hal::{hal, Cache, Hal, Vm};
+use jrinx_hal::{hal, Cache, Cpu, Hal, Interrupt, Vm};
 use jrinx_loader::ElfLoader;
-use jrinx_multitask::inspector::Inspector;
+use jrinx_multitask::{
+    executor::{Executor, ExecutorId},
+    inspector::{Inspector, InspectorId},
+    runtime::Runtime,
+};
 use jrinx_paging::{common::PageTable, GenericPagePerm, GenericPageTable, PagePerm};
 use jrinx_phys_frame::PhysFrame;
 use jrinx_serial_id_macro::SerialId;
 use jrinx_stack_alloc::StackAllocator;
 use jrinx_vmm::KERN_PAGE_TABLE;
-use spin::{Mutex, RwLock, RwLockReadGuard};
+use spin::{Mutex, Once, RwLock, RwLockReadGuard};
 
 use crate::{
+    blackboard, buffer, event,
+    health::{RecoveryAction, RecoveryTable},
+    mutex,
     process::{Process, ProcessId},
-    A653Entry,
+    queuing, sampling, semaphore, A653Entry,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, SerialId)]
@@ -40,17 +47,22 @@ pub struct Partition {
     memory: PartitionMemory,
     page_table: RwLock<PageTable>,
     pre_start_hooks: RwLock<VecDeque<Box<dyn FnOnce() + Send + Sync>>>,
+    spawner: Once<Box<ProcessSpawner>>,
     process_registry: RwLock<PartitionProcessRegistry>,
     stack_allocator: StackAllocator,
     next_index: AtomicUsize,
     entry: A653Entry,
+    program: Option<ElfBytes<'static, AnyEndian>>,
     period: ApexSystemTime,
     duration: ApexSystemTime,
-    lock_level: RwLock<ApexLockLevel>,
+    lock: RwLock<PartitionLock>,
     operating_mode: RwLock<ApexOperatingMode>,
-    start_condition: ApexStartCondition,
+    start_condition: RwLock<ApexStartCondition>,
     num_assigned_cores: ApexNumCores,
     assigned_cores: RwLock<Vec<ApexProcessorCoreId>>,
+    error_handler: RwLock<Option<Arc<Process>>>,
+    errors: Mutex<VecDeque<ApexErrorStatus>>,
+    recovery_table: RwLock<RecoveryTable>,
 }
 
 struct PartitionMemory {
@@ -58,6 +70,13 @@ struct PartitionMemory {
     free: Mutex<usize>,
 }
 
+type ProcessSpawner = dyn Fn(&Arc<Process>) -> Result<()> + Send + Sync;
+
+struct PartitionLock {
+    level: ApexLockLevel,
+    owner: Option<ExecutorId>,
+}
+
 struct PartitionProcessRegistry {
     registry: BTreeMap<ProcessId, Arc<Process>>,
     names: BTreeMap<ApexName, ProcessId>,
@@ -68,24 +87,30 @@ pub struct PartitionMemoryAllocator {
     partition_id: PartitionId,
 }
 
-pub struct PartitionConfig<'a> {
+pub struct PartitionConfig {
     pub name: ApexName,
     pub memory: usize,
     pub period: ApexSystemTime,
     pub duration: ApexSystemTime,
     pub num_cores: ApexNumCores,
-    pub partition_type: PartitionTypeConfig<'a>,
+    pub partition_type: PartitionTypeConfig,
 }
 
-pub enum PartitionTypeConfig<'a> {
-    Kern, // TODO
-    User(ElfBytes<'a, AnyEndian>),
+pub enum PartitionTypeConfig {
+    Kern(ApexSystemAddress),
+    User(ElfBytes<'static, AnyEndian>),
 }
 
 static PARTITIONS: RwLock<BTreeMap<PartitionId, Weak<Partition>>> = RwLock::new(BTreeMap::new());
 
+impl From<PartitionId> for ApexPartitionId {
+    fn from(id: PartitionId) -> Self {
+        id.0
+    }
+}
+
 impl Partition {
-    pub fn new(config: &PartitionConfig) -> Result<Arc<Self>> {
+    pub fn new(config: PartitionConfig) -> Result<Arc<Self>> {
         let page_table = PageTable::new_from(&KERN_PAGE_TABLE.read())?;
         let partition_id = PartitionId::new();
 
@@ -113,33 +138,44 @@ impl Partition {
         );
 
         let partition = Arc::new(Self {
-            kernel: matches!(config.partition_type, PartitionTypeConfig::Kern),
+            kernel: matches!(config.partition_type, PartitionTypeConfig::Kern(_)),
             identifier: partition_id,
             name: config.name,
             memory: PartitionMemory::new(config.memory),
             page_table: RwLock::new(page_table),
             pre_start_hooks: RwLock::new(VecDeque::new()),
+            spawner: Once::new(),
             process_registry: RwLock::new(PartitionProcessRegistry::new()),
             stack_allocator,
             next_index: AtomicUsize::new(0),
             period: config.period,
             duration: config.duration,
-            lock_level: RwLock::new(APEX_LOCK_LEVEL_MIN),
-            operating_mode: RwLock::new(ApexOperatingMode::Idle),
-            start_condition: ApexStartCondition::NormalStart,
+            lock: RwLock::new(PartitionLock {
+                level: APEX_LOCK_LEVEL_MIN,
+                owner: None,
+            }),
+            operating_mode: RwLock::new(ApexOperatingMode::ColdStart),
+            start_condition: RwLock::new(ApexStartCondition::NormalStart),
             num_assigned_cores: config.num_cores,
             assigned_cores: RwLock::new(Vec::new()),
+            error_handler: RwLock::new(None),
+            errors: Mutex::new(VecDeque::new()),
+            recovery_table: RwLock::new(RecoveryTable::new(RecoveryAction::StopProcess)),
             entry: match &config.partition_type {
-                PartitionTypeConfig::Kern => todo!(),
+                PartitionTypeConfig::Kern(entry) => A653Entry::Kern(*entry),
                 PartitionTypeConfig::User(program) => A653Entry::User(program.ehdr.e_entry as _),
             },
+            program: match config.partition_type {
+                PartitionTypeConfig::Kern(_) => None,
+                PartitionTypeConfig::User(program) => Some(program),
+            },
         });
 
         PARTITIONS
             .write()
             .insert(partition.identifier, Arc::downgrade(&partition));
 
-        if let PartitionTypeConfig::User(program) = &config.partition_type {
+        if let Some(program) = &partition.program {
             partition.load_program(program)?;
         }
 
@@ -161,6 +197,19 @@ impl Partition {
             .find_map(|p| p.upgrade().filter(|p| p.name == *name))
     }
 
+    /// Finds the partition of the inspector registered in the runtime of `cpu_id`.
+    pub fn find_by_inspector(cpu_id: usize, id: InspectorId) -> Option<Arc<Self>> {
+        Runtime::with_spec_cpu(cpu_id, |rt| {
+            rt.with_registry(|registry| {
+                registry
+                    .get(&id)
+                    .and_then(|inspector| inspector.ext().downcast_ref::<Arc<Partition>>().cloned())
+            })
+        })
+        .ok()
+        .flatten()
+    }
+
     pub fn kernel(&self) -> bool {
         self.kernel
     }
@@ -201,12 +250,81 @@ impl Partition {
         *self.operating_mode.write() = mode;
     }
 
+    pub fn start_condition(&self) -> ApexStartCondition {
+        *self.start_condition.read()
+    }
+
     pub fn lock_level(&self) -> ApexLockLevel {
-        *self.lock_level.read()
+        self.lock.read().level
+    }
+
+    /// Returns the executor that locks preemption in the partition.
+    pub fn lock_owner(&self) -> Option<ExecutorId> {
+        self.lock.read().owner
+    }
+
+    /// Increments the lock level, and locks preemption of all the cores assigned to the
+    /// partition for the current executor.
+    ///
+    /// Fails if preemption is locked by another executor, e.g., one running on another core.
+    pub fn lock_preemption(&self) -> Result<ApexLockLevel> {
+        let current = Executor::with_current(|ex| ex.id())?;
+        let mut lock = self.lock.write();
+        if lock.level >= APEX_LOCK_LEVEL_MAX {
+            return Err(InternalError::InvalidApexLockLevel);
+        }
+        if lock.owner.is_some_and(|owner| owner != current) {
+            return Err(InternalError::InvalidExecutorId);
+        }
+        if lock.level == APEX_LOCK_LEVEL_MIN {
+            self.lock_cores(Some(current))?;
+            lock.owner = Some(current);
+        }
+        lock.level += 1;
+        Ok(lock.level)
+    }
+
+    /// Decrements the lock level, and unlocks preemption of all the cores assigned to the
+    /// partition once it reaches zero.
+    ///
+    /// Fails if preemption is locked by another executor.
+    pub fn unlock_preemption(&self) -> Result<ApexLockLevel> {
+        let current = Executor::with_current(|ex| ex.id())?;
+        let mut lock = self.lock.write();
+        if lock.level <= APEX_LOCK_LEVEL_MIN {
+            return Err(InternalError::InvalidApexLockLevel);
+        }
+        if lock.owner.is_some_and(|owner| owner != current) {
+            return Err(InternalError::InvalidExecutorId);
+        }
+        lock.level -= 1;
+        if lock.level == APEX_LOCK_LEVEL_MIN {
+            lock.owner = None;
+            self.lock_cores(None)?;
+        }
+        Ok(lock.level)
+    }
+
+    /// Resets the lock level to zero, and unlocks preemption of all the cores assigned to the
+    /// partition.
+    pub fn reset_preemption(&self) -> Result<()> {
+        let mut lock = self.lock.write();
+        lock.level = APEX_LOCK_LEVEL_MIN;
+        if lock.owner.take().is_some() {
+            self.lock_cores(None)?;
+        }
+        Ok(())
     }
 
-    pub fn set_lock_level(&self, level: ApexLockLevel) {
-        *self.lock_level.write() = level;
+    fn lock_cores(&self, owner: Option<ExecutorId>) -> Result<()> {
+        for cpu_id in self.assigned_cores() {
+            let cpu_id = cpu_id as usize;
+            self.with_inspector(cpu_id, |is| is.lock(owner))?;
+            if cpu_id != hal!().cpu().id() {
+                hal!().interrupt().send_ipi(&[cpu_id]);
+            }
+        }
+        Ok(())
     }
 
     pub fn assigned_cores(&self) -> Vec<ApexProcessorCoreId> {
@@ -226,12 +344,123 @@ impl Partition {
         Ok(())
     }
 
+    pub fn error_handler(&self) -> Option<Arc<Process>> {
+        self.error_handler.read().clone()
+    }
+
+    pub fn set_error_handler(&self, process: Arc<Process>) {
+        *self.error_handler.write() = Some(process);
+    }
+
+    pub fn push_error(&self, error: ApexErrorStatus) {
+        self.errors.lock().push_back(error);
+    }
+
+    pub fn pop_error(&self) -> Option<ApexErrorStatus> {
+        self.errors.lock().pop_front()
+    }
+
+    pub fn recovery_table(&self) -> RecoveryTable {
+        *self.recovery_table.read()
+    }
+
+    pub fn set_recovery_table(&self, table: RecoveryTable) {
+        *self.recovery_table.write() = table;
+    }
+
+    /// Stops every process of the partition.
+    pub fn stop_processes(&self) {
+        let processes = self
+            .process_registry
+            .read()
+            .registry
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        for process in processes {
+            if let Err(err) = process.stop() {
+                warn!("failed to stop process {:?}: {:?}", process.name(), err);
+            }
+        }
+    }
+
+    /// Stops every process of the partition and switches it to [`ApexOperatingMode::Idle`], in
+    /// which its windows run nothing until the module is restarted.
+    pub fn shutdown(&self) {
+        self.set_operating_mode(ApexOperatingMode::Idle);
+        self.pre_start_hooks.write().clear();
+        self.stop_processes();
+        if let Err(err) = self.reset_preemption() {
+            warn!(
+                "failed to unlock preemption of partition {:?}: {:?}",
+                self.name, err
+            );
+        }
+    }
+
+    /// Restarts the partition in `mode`, which is either [`ApexOperatingMode::ColdStart`] or
+    /// [`ApexOperatingMode::WarmStart`].
+    ///
+    /// All the processes and objects created by the partition are discarded, and a new initial
+    /// process is started. A cold start also reloads the program of the partition, so that its
+    /// data is reinitialized.
+    pub fn restart(&self, mode: ApexOperatingMode, condition: ApexStartCondition) -> Result<()> {
+        self.set_operating_mode(mode);
+        *self.start_condition.write() = condition;
+        self.pre_start_hooks.write().clear();
+        self.stop_processes();
+        self.reset_preemption()?;
+
+        *self.error_handler.write() = None;
+        self.errors.lock().clear();
+        let processes = core::mem::replace(
+            &mut *self.process_registry.write(),
+            PartitionProcessRegistry::new(),
+        );
+        drop(processes);
+        self.next_index
+            .store(0, core::sync::atomic::Ordering::SeqCst);
+
+        blackboard::Blackboard::remove_by_partition(self.identifier);
+        buffer::Buffer::remove_by_partition(self.identifier);
+        event::Event::remove_by_partition(self.identifier);
+        mutex::Mutex::remove_by_partition(self.identifier);
+        queuing::QueuingPort::remove_by_partition(self.identifier);
+        sampling::SamplingPort::remove_by_partition(self.identifier);
+        semaphore::Semaphore::remove_by_partition(self.identifier);
+
+        if mode == ApexOperatingMode::ColdStart {
+            if let Some(program) = &self.program {
+                self.clear_program_data(program)?;
+                self.load_program(program)?;
+            }
+        }
+
+        let process = Process::new_init(self.identifier)?;
+        process.set_core_affinity(self.assigned_cores().first().map(|&cpu_id| cpu_id as _));
+        let spawner = self.spawner().ok_or(InternalError::InvalidExecutorStatus)?;
+        spawner(&process)
+    }
+
     pub fn allocator(&self) -> PartitionMemoryAllocator {
         PartitionMemoryAllocator {
             partition_id: self.identifier,
         }
     }
 
+    /// Registers how to spawn a process of the partition, which is the first one registered by
+    /// [`Process::gen_executor`](crate::process::Process::gen_executor).
+    pub(crate) fn set_spawner(
+        &self,
+        spawner: impl Fn(&Arc<Process>) -> Result<()> + Send + Sync + 'static,
+    ) {
+        self.spawner.call_once(|| Box::new(spawner));
+    }
+
+    pub(crate) fn spawner(&self) -> Option<&ProcessSpawner> {
+        self.spawner.get().map(Box::as_ref)
+    }
+
     pub fn add_pre_start_hook(&self, hook: impl FnOnce() + Send + Sync + 'static) {
         self.pre_start_hooks.write().push_back(Box::new(hook));
     }
@@ -266,15 +495,48 @@ impl Partition {
             identifier: self.identifier.0,
             lock_level: self.lock_level(),
             operating_mode: self.operating_mode(),
-            start_condition: self.start_condition,
+            start_condition: self.start_condition(),
             num_assigned_cores: self.num_assigned_cores,
         }
     }
 
+    /// Returns the partition's next periodic processing start on the current core, i.e., the
+    /// start of the first window of its next period.
+    ///
+    /// Returns the current time if the partition is not scheduled periodically.
+    pub fn next_periodic_start(&self) -> ApexSystemTime {
+        let now = hal!().cpu().get_time();
+        let start = self
+            .with_inspector(hal!().cpu().id(), |is| is.id())
+            .ok()
+            .and_then(|id| Runtime::with_current(|rt| rt.next_period_start(id, now)));
+        duration_as_time(start.unwrap_or(now))
+    }
+
     pub fn gen_inspector(self: &Arc<Self>) -> Result<Inspector> {
         Ok(Inspector::new_with_ext(self.clone()))
     }
 
+    pub fn with_inspector<F, R>(&self, cpu_id: usize, f: F) -> Result<R>
+    where
+        F: FnOnce(&Inspector) -> R,
+    {
+        Runtime::with_spec_cpu(cpu_id, |rt| {
+            rt.with_registry(|registry| {
+                registry
+                    .values()
+                    .find(|inspector| {
+                        inspector
+                            .ext()
+                            .downcast_ref::<Arc<Partition>>()
+                            .is_some_and(|partition| partition.identifier == self.identifier)
+                    })
+                    .map(f)
+                    .ok_or(InternalError::InvalidInspectorId)
+            })
+        })?
+    }
+
     pub(crate) fn allocate_stack(&self, stack_size: usize) -> Result<VirtAddr> {
         self.stack_allocator.allocate(stack_size)
     }
@@ -308,6 +570,37 @@ impl Partition {
             .and_then(|id| self.find_process_by_id(*id))
     }
 
+    /// Zeroes the writable segments of `program`, which are then reinitialized by
+    /// [`Partition::load_program`].
+    fn clear_program_data(&self, program: &ElfBytes<'_, AnyEndian>) -> Result<()> {
+        let page_table = self.page_table.read();
+
+        for phdr in program
+            .segments()
+            .ok_or(InternalError::ElfParseError)?
+            .iter()
+            .filter(|phdr| phdr.p_type == PT_LOAD && phdr.p_flags & PF_W != 0)
+        {
+            let mut vaddr = VirtAddr::new(phdr.p_vaddr as usize);
+            let end = vaddr + phdr.p_memsz as usize;
+            while vaddr < end {
+                let offset = vaddr - vaddr.align_page_down();
+                let len = core::cmp::min(jrinx_config::PAGE_SIZE - offset, end - vaddr);
+                let (phys_frame, _) = page_table.lookup(vaddr)?;
+                unsafe {
+                    core::ptr::write_bytes(
+                        (phys_frame.addr().to_virt().as_usize() + offset) as *mut u8,
+                        0,
+                        len,
+                    );
+                }
+                vaddr = vaddr + len;
+            }
+        }
+
+        Ok(())
+    }
+
     fn load_program(&self, program: &ElfBytes<'_, AnyEndian>) -> Result<()> {
         let mut page_table = self.page_table.write();
 
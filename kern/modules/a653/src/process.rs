@@ -1,23 +1,33 @@
 use alloc::{boxed::Box, format, sync::Arc};
-use core::{future::Future, ops::Deref, pin::Pin};
+use core::{
+    future::Future,
+    ops::{ControlFlow, Deref},
+    pin::Pin,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
 use jrinx_apex::*;
 use jrinx_paging::GenericPageTable;
-use jrinx_trap::{arch::Context, GenericContext};
+use jrinx_trap::{arch::Context, GenericContext, TrapReason};
 
 use jrinx_addr::VirtAddr;
 use jrinx_config::PAGE_SIZE;
 use jrinx_error::{InternalError, Result};
-use jrinx_hal::{Hal, Vm};
+use jrinx_hal::{Cpu, Hal, Interrupt, Vm};
 use jrinx_multitask::{
-    executor::{Executor, ExecutorPriority},
+    executor::{Executor, ExecutorId, ExecutorPriority, ExecutorStatus},
+    inspector::{Inspector, InspectorStatus},
+    runtime::{Runtime, RuntimeStatus},
     Task, TaskPriority,
 };
 use jrinx_serial_id_macro::SerialId;
-use spin::RwLock;
+use jrinx_timed_event::{TimedEvent, TimedEventHandler, TimedEventTracker};
+use spin::{Mutex, RwLock};
 
 use crate::{
+    health,
+    mutex::{self, MutexId},
     partition::{Partition, PartitionId},
-    A653Entry,
+    wait, A653Entry,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, SerialId)]
@@ -28,7 +38,7 @@ pub struct Process {
     name: ApexProcessName,
     index: Option<ApexProcessIndex>,
     partition_id: PartitionId,
-    stack_top: VirtAddr,
+    stack_top: RwLock<VirtAddr>,
     base_priority: ApexPriority,
     deadline: ApexDeadline,
     entry: A653Entry,
@@ -37,8 +47,24 @@ pub struct Process {
     time_capacity: ApexSystemTime,
     curr_priority: RwLock<ApexPriority>,
     deadline_time: RwLock<ApexSystemTime>,
+    deadline_event: Mutex<Option<TimedEventTracker>>,
+    deadline_misses: AtomicUsize,
+    deadline_missed: AtomicBool,
+    preemptible: AtomicBool,
+    release_point: RwLock<ApexSystemTime>,
     process_state: RwLock<ApexProcessState>,
     core_affinity: RwLock<Option<usize>>,
+    executor: RwLock<Option<(usize, ExecutorId)>>,
+    mutex: RwLock<Option<MutexId>>,
+    wait_cancel: Mutex<Option<Box<dyn FnOnce() + Send>>>,
+    suspension: Mutex<ProcessSuspension>,
+}
+
+#[derive(Default)]
+struct ProcessSuspension {
+    suspended: bool,
+    timed_out: bool,
+    timeout: Option<TimedEventTracker>,
 }
 
 pub struct ProcessConfig {
@@ -64,7 +90,8 @@ impl From<ApexProcessId> for ProcessId {
 }
 
 impl Process {
-    pub const MAX_PRIORITY: ApexPriority = ExecutorPriority::MAX as _;
+    pub const MAX_PRIORITY: ApexPriority = ExecutorPriority::MAX as ApexPriority - 1;
+    pub const ERROR_HANDLER_PRIORITY: ApexPriority = ExecutorPriority::MAX as _;
 
     pub fn new(partition_id: PartitionId, config: &ProcessConfig) -> Result<Arc<Self>> {
         let partition = Partition::find_by_id(partition_id).unwrap();
@@ -76,7 +103,7 @@ impl Process {
             name: config.name,
             index: if index == 0 { None } else { Some(index as _) },
             partition_id,
-            stack_top,
+            stack_top: RwLock::new(stack_top),
             base_priority: config.priority,
             deadline: config.deadline,
             entry: config.entry,
@@ -85,8 +112,17 @@ impl Process {
             time_capacity: config.time_capacity,
             curr_priority: RwLock::new(config.priority),
             deadline_time: RwLock::new(APEX_TIME_INFINITY),
+            deadline_event: Mutex::new(None),
+            deadline_misses: AtomicUsize::new(0),
+            deadline_missed: AtomicBool::new(false),
+            preemptible: AtomicBool::new(false),
+            release_point: RwLock::new(0),
             process_state: RwLock::new(ApexProcessState::Dormant),
             core_affinity: RwLock::new(None),
+            executor: RwLock::new(None),
+            mutex: RwLock::new(None),
+            wait_cancel: Mutex::new(None),
+            suspension: Mutex::new(ProcessSuspension::default()),
         });
 
         partition.register_process(process.clone());
@@ -114,6 +150,34 @@ impl Process {
         )
     }
 
+    pub fn new_error_handler(
+        partition_id: PartitionId,
+        entry: A653Entry,
+        stack_size: ApexStackSize,
+    ) -> Result<Arc<Self>> {
+        let partition = Partition::find_by_id(partition_id).unwrap();
+
+        let process = Self::new(
+            partition_id,
+            &ProcessConfig {
+                name: format!("{:?}.e", partition.name())
+                    .as_str()
+                    .try_into()
+                    .map_err(|_| InternalError::InvalidApexName)?,
+                priority: Self::ERROR_HANDLER_PRIORITY,
+                deadline: ApexDeadline::Soft,
+                entry,
+                period: APEX_TIME_INFINITY,
+                stack_size,
+                time_capacity: APEX_TIME_INFINITY,
+            },
+        )?;
+
+        partition.set_error_handler(process.clone());
+
+        Ok(process)
+    }
+
     pub fn current() -> Option<Arc<Self>> {
         Executor::with_current(|ex| ex.ext().deref().downcast_ref().cloned()).ok()?
     }
@@ -145,7 +209,7 @@ impl Process {
     }
 
     pub fn stack_top(&self) -> VirtAddr {
-        self.stack_top
+        *self.stack_top.read()
     }
 
     pub fn stack_size(&self) -> ApexStackSize {
@@ -172,20 +236,154 @@ impl Process {
         *self.curr_priority.read()
     }
 
-    pub fn set_curr_priority(&self, priority: ApexPriority) {
+    /// Sets the current priority, and propagates it to the executor running this process.
+    pub fn set_curr_priority(&self, priority: ApexPriority) -> Result<()> {
         *self.curr_priority.write() = priority;
+
+        if let Some((cpu_id, executor_id)) = self.executor() {
+            let priority = ExecutorPriority::new(
+                priority
+                    .try_into()
+                    .map_err(|_| InternalError::InvalidApexPriority)?,
+            );
+            Partition::find_by_id(self.partition_id)
+                .unwrap()
+                .with_inspector(cpu_id, |is| is.set_priority(executor_id, priority))??;
+
+            if cpu_id != hal!().cpu().id() {
+                hal!().interrupt().send_ipi(&[cpu_id]);
+            }
+        }
+
+        Ok(())
     }
 
     pub fn deadline_time(&self) -> ApexSystemTime {
         *self.deadline_time.read()
     }
 
+    /// Sets the deadline time, and re-arms the deadline monitoring accordingly.
     pub fn set_deadline_time(&self, time: ApexSystemTime) {
         *self.deadline_time.write() = time;
+
+        hal!().interrupt().with_saved_off(|| {
+            let mut deadline_event = self.deadline_event.lock();
+            if let Some(event) = deadline_event.take() {
+                let _ = event.cancel();
+            }
+            if time != APEX_TIME_INFINITY {
+                let (partition_id, identifier) = (self.partition_id, self.identifier);
+                *deadline_event = Some(TimedEvent::create(
+                    time_as_duration(time),
+                    TimedEventHandler::new(
+                        move || {
+                            if let Some(process) = Process::find_by_id(partition_id, identifier) {
+                                process.miss_deadline();
+                            }
+                        },
+                        || {},
+                    ),
+                ));
+            }
+        });
+    }
+
+    pub fn deadline_misses(&self) -> usize {
+        self.deadline_misses.load(Ordering::SeqCst)
+    }
+
+    /// Records a deadline miss in the timer interrupt, and wakes the process so that it reports
+    /// the miss itself by [`Process::handle_deadline_miss`].
+    fn miss_deadline(&self) {
+        self.deadline_event.lock().take();
+        self.deadline_misses.fetch_add(1, Ordering::SeqCst);
+        self.deadline_missed.store(true, Ordering::SeqCst);
+
+        if let Some((cpu_id, executor_id)) = self.executor() {
+            if let Some(partition) = Partition::find_by_id(self.partition_id) {
+                let _ = partition.with_inspector(cpu_id, |is| is.wake(executor_id));
+            }
+            if cpu_id != hal!().cpu().id() {
+                hal!().interrupt().send_ipi(&[cpu_id]);
+            }
+        }
+    }
+
+    /// Reports the deadline miss recorded since the last call (if any) to the health monitor,
+    /// which is called by the process itself at its next trap, service return or wakeup.
+    ///
+    /// A process with a soft deadline keeps running, and the miss only reaches the error
+    /// handler (if any); whereas a miss of a hard deadline is recovered from by the recovery
+    /// table of the partition if there is no error handler. A suspended process reports the miss
+    /// once it is resumed.
+    pub fn handle_deadline_miss(&self) {
+        if !self.deadline_missed.swap(false, Ordering::SeqCst) {
+            return;
+        }
+
+        if matches!(
+            self.process_state(),
+            ApexProcessState::Dormant | ApexProcessState::Faulted
+        ) {
+            return;
+        }
+
+        let message = format!(
+            "deadline {:?} missed at {} ({} misses)",
+            self.deadline,
+            self.deadline_time(),
+            self.deadline_misses()
+        );
+        let handler = match self.deadline {
+            ApexDeadline::Soft => {
+                health::notify(self, ApexErrorCode::DeadlineMissed, message.as_bytes(), 0)
+            }
+            ApexDeadline::Hard => {
+                health::report(self, ApexErrorCode::DeadlineMissed, message.as_bytes(), 0)
+            }
+        };
+        if let Some(handler) = handler {
+            if let Err(err) = handler.start_error_handler() {
+                warn!(
+                    "failed to start error handler {:?}: {:?}",
+                    handler.name(),
+                    err
+                );
+            }
+        }
     }
 
+    /// Sets whether the interrupts may preempt this kernel process, i.e., whether it runs the
+    /// code of its entry rather than an APEX service, which may hold the locks of the partition.
+    pub fn set_preemptible(&self, preemptible: bool) {
+        self.preemptible.store(preemptible, Ordering::SeqCst);
+    }
+
+    pub fn release_point(&self) -> ApexSystemTime {
+        *self.release_point.read()
+    }
+
+    /// Sets the release point, and recomputes the deadline time from it.
+    pub fn set_release_point(&self, time: ApexSystemTime) -> Result<()> {
+        let deadline_time = match self.time_capacity {
+            APEX_TIME_INFINITY => APEX_TIME_INFINITY,
+            time_capacity => time
+                .checked_add(time_capacity)
+                .ok_or(InternalError::InvalidApexTime)?,
+        };
+        *self.release_point.write() = time;
+        self.set_deadline_time(deadline_time);
+        Ok(())
+    }
+
+    /// Returns the state of the process, where a suspended process is waiting even if it is
+    /// otherwise ready.
     pub fn process_state(&self) -> ApexProcessState {
-        *self.process_state.read()
+        let state = *self.process_state.read();
+        match state {
+            ApexProcessState::Ready if self.is_suspended() => ApexProcessState::Waiting,
+            state => state,
+        }
     }
 
     pub fn set_process_state(&self, state: ApexProcessState) {
@@ -200,6 +398,242 @@ impl Process {
         *self.core_affinity.write() = cpu_id;
     }
 
+    pub fn executor(&self) -> Option<(usize, ExecutorId)> {
+        *self.executor.read()
+    }
+
+    /// Sets the executor running this process.
+    ///
+    /// The executor is also read by the suspension timeout in the timer interrupt, so it is
+    /// written with interrupts disabled.
+    pub fn set_executor(&self, executor: Option<(usize, ExecutorId)>) {
+        hal!()
+            .interrupt()
+            .with_saved_off(|| *self.executor.write() = executor);
+    }
+
+    pub fn mutex(&self) -> Option<MutexId> {
+        *self.mutex.read()
+    }
+
+    pub fn set_mutex(&self, mutex: Option<MutexId>) {
+        *self.mutex.write() = mutex;
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        hal!()
+            .interrupt()
+            .with_saved_off(|| self.suspension.lock().suspended)
+    }
+
+    /// Suspends the process, and resumes it after `timeout` unless it is infinite.
+    ///
+    /// The suspension is orthogonal to waiting on resources: a suspended process is not
+    /// scheduled until it is resumed, even if the resource it waits on becomes available.
+    pub fn suspend(self: &Arc<Self>, timeout: ApexSystemTime) -> Result<()> {
+        hal!().interrupt().with_saved_off(|| {
+            let mut suspension = self.suspension.lock();
+            if let Some((cpu_id, executor_id)) = self.executor() {
+                Partition::find_by_id(self.partition_id)
+                    .unwrap()
+                    .with_inspector(cpu_id, |is| is.suspend(executor_id))??;
+            }
+
+            suspension.suspended = true;
+            suspension.timed_out = false;
+            suspension.timeout = (timeout != APEX_TIME_INFINITY).then(|| {
+                let process = self.clone();
+                TimedEvent::create(
+                    hal!()
+                        .cpu()
+                        .get_time()
+                        .saturating_add(time_as_duration(timeout)),
+                    TimedEventHandler::new(
+                        move || {
+                            if let Err(err) = process.do_resume(true) {
+                                warn!("failed to resume process {:?}: {:?}", process.name(), err);
+                            }
+                        },
+                        || {},
+                    ),
+                )
+            });
+
+            Ok(())
+        })
+    }
+
+    /// Resumes the suspended process.
+    pub fn resume(&self) -> Result<()> {
+        hal!().interrupt().with_saved_off(|| self.do_resume(false))
+    }
+
+    /// Returns whether the last suspension of the process ended by its timeout.
+    pub fn suspension_timed_out(&self) -> bool {
+        hal!()
+            .interrupt()
+            .with_saved_off(|| self.suspension.lock().timed_out)
+    }
+
+    /// Ends the suspension, which must be called with interrupts disabled since the suspension
+    /// timeout calls it from the timer interrupt.
+    fn do_resume(&self, timed_out: bool) -> Result<()> {
+        let mut suspension = self.suspension.lock();
+        if !suspension.suspended {
+            return Err(InternalError::InvalidExecutorStatus);
+        }
+
+        suspension.suspended = false;
+        suspension.timed_out = timed_out;
+        if let Some(timeout) = suspension.timeout.take() {
+            if !timed_out {
+                timeout.cancel()?;
+            }
+        }
+
+        if let Some((cpu_id, executor_id)) = self.executor() {
+            Partition::find_by_id(self.partition_id)
+                .unwrap()
+                .with_inspector(cpu_id, |is| is.resume(executor_id))??;
+
+            if cpu_id != hal!().cpu().id() {
+                hal!().interrupt().send_ipi(&[cpu_id]);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stops the process, bringing it back to dormant.
+    ///
+    /// Its pending wait and suspension are cancelled, its mutex is released, and its executor is
+    /// woken up to return the next time it runs in the kernel, so that its coroutine unwinds
+    /// instead of being dropped in the middle. A later start begins again at the entry point
+    /// with a fresh stack, while the old one is released once the old executor is dropped.
+    pub fn stop(&self) -> Result<()> {
+        if self.process_state() == ApexProcessState::Dormant {
+            return Ok(());
+        }
+
+        let wait_cancel = self.wait_cancel.lock().take();
+        if let Some(wait_cancel) = wait_cancel {
+            hal!().interrupt().with_saved_off(wait_cancel);
+        }
+
+        hal!().interrupt().with_saved_off(|| {
+            let mut suspension = self.suspension.lock();
+            suspension.suspended = false;
+            if let Some(timeout) = suspension.timeout.take() {
+                let _ = timeout.cancel();
+            }
+        });
+
+        self.release_mutex();
+
+        self.set_process_state(ApexProcessState::Dormant);
+        self.set_deadline_time(APEX_TIME_INFINITY);
+
+        self.release_preemption()?;
+        let executor = hal!()
+            .interrupt()
+            .with_saved_off(|| self.executor.write().take());
+        if let Some((cpu_id, executor_id)) = executor {
+            let partition = Partition::find_by_id(self.partition_id).unwrap();
+            partition.with_inspector(cpu_id, |is| {
+                let _ = is.resume(executor_id);
+                is.wake(executor_id)
+            })??;
+
+            if cpu_id != hal!().cpu().id() {
+                hal!().interrupt().send_ipi(&[cpu_id]);
+            }
+
+            *self.stack_top.write() = partition.allocate_stack(self.stack_size as _)?;
+            hal!().vm().sync_all();
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether preemption of the partition is unlocked, or locked by this process.
+    pub fn owns_preemption_lock(&self) -> bool {
+        let owner = Partition::find_by_id(self.partition_id)
+            .unwrap()
+            .lock_owner();
+        owner.is_none() || owner == self.executor().map(|(_, executor_id)| executor_id)
+    }
+
+    /// Releases the mutex owned by this process (if any), handing it over to the next waiter.
+    fn release_mutex(&self) {
+        if let Some(mutex) = self
+            .mutex()
+            .and_then(|mutex_id| mutex::Mutex::find_by_id(self.partition_id, mutex_id))
+        {
+            if let Err(err) = mutex.reset(self) {
+                warn!(
+                    "failed to release mutex {:?} of process {:?}: {:?}",
+                    mutex.name(),
+                    self.name,
+                    err
+                );
+            }
+        }
+    }
+
+    /// Unlocks preemption of the partition if it is locked by this process.
+    fn release_preemption(&self) -> Result<()> {
+        let partition = Partition::find_by_id(self.partition_id).unwrap();
+        match (self.executor(), partition.lock_owner()) {
+            (Some((_, executor_id)), Some(owner)) if executor_id == owner => {
+                partition.reset_preemption()
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn set_wait_cancel(&self, wait_cancel: Option<Box<dyn FnOnce() + Send>>) {
+        *self.wait_cancel.lock() = wait_cancel;
+    }
+
+    /// Returns whether the current executor no longer runs this process, e.g., after it is
+    /// stopped.
+    pub fn is_stale(&self) -> bool {
+        Executor::with_current(|ex| ex.id()).ok() != self.executor().map(|(_, id)| id)
+    }
+
+    pub fn is_error_handler(&self) -> bool {
+        Partition::find_by_id(self.partition_id)
+            .and_then(|partition| partition.error_handler())
+            .is_some_and(|handler| handler.identifier == self.identifier)
+    }
+
+    /// Returns whether `addr` lies in the guard page right below the stack of this process.
+    pub fn is_stack_guard(&self, addr: VirtAddr) -> bool {
+        let stack_bottom =
+            self.stack_top() - (self.stack_size as usize).next_multiple_of(PAGE_SIZE);
+        (stack_bottom - PAGE_SIZE..stack_bottom).contains(&addr)
+    }
+
+    pub fn can_wait(&self) -> bool {
+        let partition = Partition::find_by_id(self.partition_id).unwrap();
+        partition.operating_mode() == ApexOperatingMode::Normal
+            && partition.lock_level() == APEX_LOCK_LEVEL_MIN
+    }
+
+    pub fn wake(&self) -> Result<()> {
+        let (cpu_id, executor_id) = self.executor().ok_or(InternalError::InvalidExecutorId)?;
+
+        Partition::find_by_id(self.partition_id)
+            .unwrap()
+            .with_inspector(cpu_id, |is| is.wake(executor_id))??;
+
+        if cpu_id != hal!().cpu().id() {
+            hal!().interrupt().send_ipi(&[cpu_id]);
+        }
+
+        Ok(())
+    }
+
     pub fn status(&self) -> ApexProcessStatus {
         ApexProcessStatus {
             attributes: ApexProcessAttribute {
@@ -229,30 +663,143 @@ impl Process {
         proc_runner: ProcessRunner<H, F>,
     ) -> Result<Pin<Box<Executor>>>
     where
-        H: Fn(usize, [usize; 7]) -> F + Send + Sync + 'static,
+        H: Fn(usize, [usize; 7]) -> F + Clone + Send + Sync + 'static,
         F: Future<Output = Result<usize>> + Send + 'static,
     {
+        Partition::find_by_id(self.partition_id)
+            .unwrap()
+            .set_spawner({
+                let proc_runner = proc_runner.clone();
+                move |process| process.spawn(proc_runner.clone(), ExecutorStatus::Runnable)
+            });
+
         Ok(Executor::new_with_ext(
             ExecutorPriority::new(
-                self.base_priority()
+                self.curr_priority()
                     .try_into()
                     .map_err(|_| InternalError::InvalidApexPriority)?,
             ),
-            Task::new(proc_runner.run(self.clone()), TaskPriority::default()),
+            Task::new(
+                proc_runner.run(self.clone(), ProcessStack::new(self.clone())),
+                TaskPriority::default(),
+            ),
             self.clone(),
         ))
     }
+
+    /// Creates an executor running this process, and registers it in the inspector of its
+    /// partition on the core it is affine to (or on the current core).
+    pub fn spawn<H, F>(
+        self: &Arc<Self>,
+        proc_runner: ProcessRunner<H, F>,
+        status: ExecutorStatus,
+    ) -> Result<()>
+    where
+        H: Fn(usize, [usize; 7]) -> F + Clone + Send + Sync + 'static,
+        F: Future<Output = Result<usize>> + Send + 'static,
+    {
+        let mut executor = self.gen_executor(proc_runner)?;
+        executor.set_status(status);
+
+        let cpu_id = self.core_affinity().unwrap_or(hal!().cpu().id());
+        let executor_id = executor.id();
+        self.set_executor(Some((cpu_id, executor_id)));
+        let suspended = self.is_suspended();
+        Partition::find_by_id(self.partition_id)
+            .unwrap()
+            .with_inspector(cpu_id, move |is| {
+                is.register(executor)?;
+                if suspended {
+                    is.suspend(executor_id)?;
+                }
+                Ok(())
+            })??;
+        if Runtime::with_spec_cpu(cpu_id, |rt| rt.status() == RuntimeStatus::Endpoint)? {
+            hal!().interrupt().send_ipi(&[cpu_id]);
+        }
+
+        Ok(())
+    }
+
+    /// Starts the error handler of the partition to process the errors reported to it.
+    pub fn start_error_handler(self: &Arc<Self>) -> Result<()> {
+        let partition = Partition::find_by_id(self.partition_id).unwrap();
+        let spawner = partition
+            .spawner()
+            .ok_or(InternalError::InvalidExecutorStatus)?;
+
+        self.set_curr_priority(self.base_priority)?;
+        self.set_deadline_time(APEX_TIME_INFINITY);
+        self.set_process_state(ApexProcessState::Ready);
+        spawner(self)
+    }
 }
 
 impl Drop for Process {
     fn drop(&mut self) {
         if let Some(partition) = Partition::find_by_id(self.partition_id) {
-            partition.deallocate_stack(self.stack_top).unwrap();
+            partition
+                .deallocate_stack(*self.stack_top.get_mut())
+                .unwrap();
+            hal!().vm().sync_all();
+        }
+    }
+}
+
+/// The stack of a process used by one of its executors.
+///
+/// It is dropped along with the executor, i.e., after the executor is switched out for the last
+/// time, and releases the stack if the process has moved on to a fresh one since it is stopped.
+struct ProcessStack {
+    process: Arc<Process>,
+    stack_top: VirtAddr,
+}
+
+impl ProcessStack {
+    fn new(process: Arc<Process>) -> Self {
+        let stack_top = process.stack_top();
+        Self { process, stack_top }
+    }
+}
+
+impl Drop for ProcessStack {
+    fn drop(&mut self) {
+        if self.process.stack_top() == self.stack_top {
+            return;
+        }
+        if let Some(partition) = Partition::find_by_id(self.process.partition_id) {
+            if let Err(err) = partition.deallocate_stack(self.stack_top) {
+                warn!(
+                    "failed to release stack of process {:?}: {:?}",
+                    self.process.name, err
+                );
+            }
             hal!().vm().sync_all();
         }
     }
 }
 
+pub fn init() {
+    jrinx_trap::set_kern_preempt_handler(preempt_kern);
+}
+
+/// Yields the core at an interrupt trapped in a kernel process outside the APEX services, if
+/// the process should be rescheduled, e.g., for a process of higher priority released by a
+/// timer. Processes of user partitions yield likewise after their traps.
+fn preempt_kern() {
+    if !Process::current().is_some_and(|process| process.preemptible.load(Ordering::SeqCst)) {
+        return;
+    }
+    let reschedule = Inspector::with_current(|is| {
+        matches!(is.status(), InspectorStatus::Running(_)) && is.take_reschedule()
+    });
+    if reschedule.unwrap_or(false) {
+        hal!().interrupt().with_saved_on(|| {
+            Runtime::switch_yield();
+        });
+    }
+}
+
 pub struct ProcessRunner<H, F>
 where
     H: Fn(usize, [usize; 7]) -> F,
@@ -261,21 +808,67 @@ where
     pub syscall: H,
 }
 
-impl<H, F> ProcessRunner<H, F>
+impl<H, F> Clone for ProcessRunner<H, F>
 where
-    H: Fn(usize, [usize; 7]) -> F,
+    H: Fn(usize, [usize; 7]) -> F + Clone,
     F: Future<Output = Result<usize>>,
 {
-    pub async fn run(self, process: Arc<Process>) {
+    fn clone(&self) -> Self {
+        Self {
+            syscall: self.syscall.clone(),
+        }
+    }
+}
+
+impl<H, F> ProcessRunner<H, F>
+where
+    H: Fn(usize, [usize; 7]) -> F + Clone + Send + Sync + 'static,
+    F: Future<Output = Result<usize>> + Send + 'static,
+{
+    async fn run(self, process: Arc<Process>, stack: ProcessStack) {
         debug!("run process: {:?}", process.name());
 
+        wait::wait_until(process.release_point());
+        if process.is_stale() {
+            return;
+        }
+        process.set_process_state(ApexProcessState::Ready);
+
         match process.entry() {
-            A653Entry::Kern(_) => todo!(),
-            A653Entry::User(entry) => self.user_run(process, entry).await,
+            A653Entry::Kern(entry) => {
+                let (partition_id, identifier) = (process.partition_id(), process.identifier());
+                process.set_preemptible(true);
+                drop(process);
+                self.kern_run(partition_id, identifier, entry)
+            }
+            A653Entry::User(entry) => self.user_run(process, entry, stack.stack_top).await,
         }
     }
 
-    async fn user_run(self, process: Arc<Process>, entry: usize) {
+    /// Runs a process of a kernel partition, whose entry calls APEX services directly, until it
+    /// returns or is stopped.
+    ///
+    /// A stopped kernel process cannot return through its entry, so its executor is dropped in
+    /// the middle instead (see `KernApex`), and this frame holds no reference to the process.
+    ///
+    /// The process is preemptible by interrupts while it runs the code of its entry, and
+    /// `KernApex` makes it unpreemptible in the services.
+    fn kern_run(&self, partition_id: PartitionId, identifier: ProcessId, entry: ApexSystemAddress) {
+        let entry: extern "C" fn() = unsafe { core::mem::transmute(usize::from(entry)) };
+        entry();
+
+        let Some(process) = Process::find_by_id(partition_id, identifier) else {
+            return;
+        };
+        process.set_preemptible(false);
+        if !process.is_stale() {
+            if let Err(err) = process.stop() {
+                warn!("failed to stop process {:?}: {:?}", process.name(), err);
+            }
+        }
+    }
+
+    async fn user_run(self, process: Arc<Process>, entry: usize, stack_top: VirtAddr) {
         Partition::find_by_id(process.partition_id())
             .unwrap()
             .pt_sync();
@@ -289,7 +882,7 @@ where
         hal!().vm().sync_all();
 
         let mut ctx = Context::default();
-        ctx.user_setup(entry, process.stack_top().as_usize());
+        ctx.user_setup(entry, stack_top.as_usize());
 
         loop {
             Partition::find_by_id(process.partition_id())
@@ -303,21 +896,88 @@ where
                 .unwrap()
                 .pt_sync();
 
-            self.user_handle_trap(&mut ctx).await;
+            if self.user_handle_trap(&process, &mut ctx).await.is_break() {
+                break;
+            }
         }
     }
-    async fn user_handle_trap(&self, ctx: &mut Context) {
+
+    async fn user_handle_trap(&self, process: &Arc<Process>, ctx: &mut Context) -> ControlFlow<()> {
+        if process.is_stale() {
+            return ControlFlow::Break(());
+        }
+
         let reason = ctx.trap_reason();
         match reason {
-            jrinx_trap::TrapReason::SystemCall => {
-                ctx.syscall_ret(
-                    (self.syscall)(ctx.syscall_num(), ctx.syscall_args())
-                        .await
-                        .unwrap(), // TODO: handle error
-                );
+            TrapReason::SystemCall => {
+                let ret = (self.syscall)(ctx.syscall_num(), ctx.syscall_args())
+                    .await
+                    .unwrap_or_else(|err| {
+                        warn!("process {:?} failed in syscall: {:?}", process.name(), err);
+                        health::recover(Some(process), ApexErrorCode::IllegalRequest);
+                        ApexReturnCode::InvalidParam as _
+                    });
+                ctx.syscall_ret(ret);
                 ctx.pc_advance();
             }
-            _ => unimplemented!("{:#x?}", ctx),
+            TrapReason::SoftwareInterrupt => jrinx_trap::soft_int::handle(ctx),
+            TrapReason::TimerInterrupt => jrinx_trap::timer_int::handle(ctx),
+            _ => {
+                self.user_handle_fault(process, reason);
+                return ControlFlow::Break(());
+            }
+        }
+
+        process.handle_deadline_miss();
+        if process.is_stale() {
+            return ControlFlow::Break(());
+        }
+
+        if Inspector::with_current(|is| is.take_reschedule()).unwrap_or(false) {
+            Runtime::switch_yield();
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Faults `process` on a trap it cannot recover from, releasing its mutex and preemption lock
+    /// as a stop would, and reports the error to the health monitor.
+    fn user_handle_fault(&self, process: &Arc<Process>, reason: TrapReason) {
+        let (error_code, failed_address) = match reason {
+            TrapReason::PageFault { addr, .. } if process.is_stack_guard(addr) => {
+                (ApexErrorCode::StackOverflow, addr.as_usize())
+            }
+            TrapReason::PageFault { addr, .. } | TrapReason::AccessFault { addr, .. } => {
+                (ApexErrorCode::MemoryViolation, addr.as_usize())
+            }
+            TrapReason::IllegalInstruction { addr } | TrapReason::Breakpoint { addr } => {
+                (ApexErrorCode::IllegalRequest, addr.as_usize())
+            }
+            _ => (ApexErrorCode::HardwareFault, 0),
+        };
+
+        process.set_process_state(ApexProcessState::Faulted);
+        process.release_mutex();
+        if let Err(err) = process.release_preemption() {
+            warn!(
+                "failed to release preemption lock of process {:?}: {:?}",
+                process.name(),
+                err
+            );
+        }
+        process.set_executor(None);
+
+        let message = format!("{:x?}", reason);
+        if let Some(handler) =
+            health::report(process, error_code, message.as_bytes(), failed_address)
+        {
+            if let Err(err) = handler.start_error_handler() {
+                warn!(
+                    "failed to start error handler {:?}: {:?}",
+                    handler.name(),
+                    err
+                );
+            }
         }
     }
 }
@@ -0,0 +1,299 @@
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+    vec::Vec,
+};
+
+use jrinx_apex::*;
+use jrinx_error::{InternalError, Result};
+use jrinx_serial_id_macro::SerialId;
+use spin::{Mutex, RwLock};
+
+use crate::{
+    channel::{Channel, ChannelInner},
+    partition::PartitionId,
+    process::Process,
+    wait::{WaitQueue, WaitResult},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, SerialId)]
+pub struct QueuingPortId(ApexQueuingPortId);
+
+pub struct QueuingPort {
+    identifier: QueuingPortId,
+    name: ApexQueuingPortName,
+    partition_id: PartitionId,
+    channel: Arc<Channel>,
+    direction: ApexPortDirection,
+    discipline: ApexQueueDiscipline,
+}
+
+pub struct QueuingPortConfig {
+    pub name: ApexQueuingPortName,
+    pub direction: ApexPortDirection,
+    pub discipline: ApexQueueDiscipline,
+}
+
+pub(crate) struct QueuingChannel {
+    max_nb_message: ApexMessageRange,
+    state: Mutex<QueuingChannelState>,
+}
+
+struct QueuingChannelState {
+    messages: VecDeque<Vec<ApexByte>>,
+    senders: WaitQueue<Vec<ApexByte>>,
+    receivers: WaitQueue<(), Vec<ApexByte>>,
+}
+
+static QUEUING_PORTS: RwLock<BTreeMap<QueuingPortId, Arc<QueuingPort>>> =
+    RwLock::new(BTreeMap::new());
+
+impl From<QueuingPortId> for ApexQueuingPortId {
+    fn from(id: QueuingPortId) -> Self {
+        id.0
+    }
+}
+
+impl From<ApexQueuingPortId> for QueuingPortId {
+    fn from(value: ApexQueuingPortId) -> Self {
+        Self(value)
+    }
+}
+
+impl QueuingPort {
+    pub fn new(
+        partition_id: PartitionId,
+        channel: Arc<Channel>,
+        config: &QueuingPortConfig,
+    ) -> Result<Arc<Self>> {
+        let ChannelInner::Queuing(queuing) = channel.inner() else {
+            return Err(InternalError::InvalidChannelConfig);
+        };
+
+        let mut state = queuing.state.lock();
+        match config.direction {
+            ApexPortDirection::Source => state.senders.set_discipline(config.discipline),
+            ApexPortDirection::Destination => state.receivers.set_discipline(config.discipline),
+        }
+        drop(state);
+
+        let port = Arc::new(Self {
+            identifier: QueuingPortId::new(),
+            name: config.name,
+            partition_id,
+            channel,
+            direction: config.direction,
+            discipline: config.discipline,
+        });
+
+        QUEUING_PORTS.write().insert(port.identifier, port.clone());
+
+        Ok(port)
+    }
+
+    pub fn find_by_id(partition_id: PartitionId, identifier: QueuingPortId) -> Option<Arc<Self>> {
+        QUEUING_PORTS
+            .read()
+            .get(&identifier)
+            .filter(|port| port.partition_id == partition_id)
+            .cloned()
+    }
+
+    pub fn find_by_name(
+        partition_id: PartitionId,
+        name: &ApexQueuingPortName,
+    ) -> Option<Arc<Self>> {
+        QUEUING_PORTS
+            .read()
+            .values()
+            .find(|port| port.partition_id == partition_id && port.name == *name)
+            .cloned()
+    }
+
+    /// Removes all the queuing ports created by the partition.
+    pub(crate) fn remove_by_partition(partition_id: PartitionId) {
+        QUEUING_PORTS
+            .write()
+            .retain(|_, port| port.partition_id != partition_id);
+    }
+
+    pub fn identifier(&self) -> QueuingPortId {
+        self.identifier
+    }
+
+    pub fn name(&self) -> ApexQueuingPortName {
+        self.name
+    }
+
+    pub fn partition_id(&self) -> PartitionId {
+        self.partition_id
+    }
+
+    pub fn direction(&self) -> ApexPortDirection {
+        self.direction
+    }
+
+    pub fn discipline(&self) -> ApexQueueDiscipline {
+        self.discipline
+    }
+
+    pub fn max_message_size(&self) -> ApexMessageSize {
+        self.channel.max_message_size()
+    }
+
+    pub fn max_nb_message(&self) -> ApexMessageRange {
+        QueuingChannel::of(&self.channel).max_nb_message()
+    }
+
+    /// Sends `message`, waiting up to `timeout` for room in the queue.
+    ///
+    /// A message that cannot be queued without waiting (i.e., `timeout` is zero) is only
+    /// reported back to the sender as [`ApexReturnCode::NotAvailable`].
+    pub fn send(
+        &self,
+        message: &[ApexByte],
+        timeout: ApexSystemTime,
+    ) -> core::result::Result<(), ApexReturnCode> {
+        let queuing = QueuingChannel::of(&self.channel);
+        let mut state = queuing.state.lock();
+
+        if let Some(receiver) = state.receivers.pop() {
+            receiver.notify(message.to_vec());
+            return Ok(());
+        }
+        if state.messages.len() < queuing.max_nb_message as usize {
+            state.messages.push_back(message.to_vec());
+            return Ok(());
+        }
+        if timeout == 0 {
+            return Err(ApexReturnCode::NotAvailable);
+        }
+        if !Process::current().unwrap().can_wait() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let token = state.senders.push(message.to_vec());
+        drop(state);
+
+        let channel = self.channel.clone();
+        let result = token.wait(timeout, move |identifier| {
+            let waiter = QueuingChannel::of(&channel)
+                .state
+                .lock()
+                .senders
+                .remove(identifier);
+            if let Some(waiter) = waiter {
+                waiter.time_out();
+            }
+        });
+
+        match result {
+            WaitResult::Notified(()) => Ok(()),
+            WaitResult::TimedOut => Err(ApexReturnCode::TimedOut),
+        }
+    }
+
+    /// Receives a message into `buffer`, waiting up to `timeout` for one to arrive.
+    ///
+    /// Returns the length of the message.
+    pub fn receive(
+        &self,
+        buffer: &mut [ApexByte],
+        timeout: ApexSystemTime,
+    ) -> core::result::Result<ApexMessageSize, ApexReturnCode> {
+        let queuing = QueuingChannel::of(&self.channel);
+        let mut state = queuing.state.lock();
+
+        if let Some(message) = state.messages.pop_front() {
+            if let Some(sender) = state.senders.pop() {
+                let pending = sender.notify(());
+                state.messages.push_back(pending);
+            }
+            buffer[..message.len()].copy_from_slice(&message);
+            return Ok(message.len() as _);
+        }
+        if timeout == 0 {
+            return Err(ApexReturnCode::NotAvailable);
+        }
+        if !Process::current().unwrap().can_wait() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let token = state.receivers.push(());
+        drop(state);
+
+        let channel = self.channel.clone();
+        let result = token.wait(timeout, move |identifier| {
+            let waiter = QueuingChannel::of(&channel)
+                .state
+                .lock()
+                .receivers
+                .remove(identifier);
+            if let Some(waiter) = waiter {
+                waiter.time_out();
+            }
+        });
+
+        match result {
+            WaitResult::Notified(message) => {
+                buffer[..message.len()].copy_from_slice(&message);
+                Ok(message.len() as _)
+            }
+            WaitResult::TimedOut => Err(ApexReturnCode::TimedOut),
+        }
+    }
+
+    pub fn clear(&self) {
+        let queuing = QueuingChannel::of(&self.channel);
+        let mut state = queuing.state.lock();
+
+        state.messages.clear();
+        while state.messages.len() < queuing.max_nb_message as usize {
+            let Some(sender) = state.senders.pop() else {
+                break;
+            };
+            let pending = sender.notify(());
+            state.messages.push_back(pending);
+        }
+    }
+
+    pub fn status(&self) -> ApexQueuingPortStatus {
+        let queuing = QueuingChannel::of(&self.channel);
+        let state = queuing.state.lock();
+
+        ApexQueuingPortStatus {
+            nb_message: state.messages.len() as _,
+            max_nb_message: queuing.max_nb_message,
+            max_message_size: self.max_message_size(),
+            port_direction: self.direction,
+            waiting_processes: match self.direction {
+                ApexPortDirection::Source => state.senders.len(),
+                ApexPortDirection::Destination => state.receivers.len(),
+            } as _,
+        }
+    }
+}
+
+impl QueuingChannel {
+    pub(crate) const fn new(max_nb_message: ApexMessageRange) -> Self {
+        Self {
+            max_nb_message,
+            state: Mutex::new(QueuingChannelState {
+                messages: VecDeque::new(),
+                senders: WaitQueue::new(ApexQueueDiscipline::Fifo),
+                receivers: WaitQueue::new(ApexQueueDiscipline::Fifo),
+            }),
+        }
+    }
+
+    pub(crate) fn max_nb_message(&self) -> ApexMessageRange {
+        self.max_nb_message
+    }
+
+    fn of(channel: &Channel) -> &Self {
+        match channel.inner() {
+            ChannelInner::Queuing(queuing) => queuing,
+            _ => unreachable!(),
+        }
+    }
+}
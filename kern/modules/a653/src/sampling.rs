@@ -0,0 +1,180 @@
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use core::time::Duration;
+
+use jrinx_apex::*;
+use jrinx_error::{InternalError, Result};
+use jrinx_hal::{Cpu, Hal};
+use jrinx_serial_id_macro::SerialId;
+use spin::RwLock;
+
+use crate::{
+    channel::{Channel, ChannelInner},
+    partition::PartitionId,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, SerialId)]
+pub struct SamplingPortId(ApexSamplingPortId);
+
+pub struct SamplingPort {
+    identifier: SamplingPortId,
+    name: ApexSamplingPortName,
+    partition_id: PartitionId,
+    channel: Arc<Channel>,
+    direction: ApexPortDirection,
+    refresh_period: ApexSystemTime,
+    last_validity: RwLock<ApexValidity>,
+}
+
+pub struct SamplingPortConfig {
+    pub name: ApexSamplingPortName,
+    pub direction: ApexPortDirection,
+    pub refresh_period: ApexSystemTime,
+}
+
+pub(crate) struct SamplingChannel {
+    message: RwLock<Option<SamplingMessage>>,
+}
+
+struct SamplingMessage {
+    data: Vec<ApexByte>,
+    time: Duration,
+}
+
+static SAMPLING_PORTS: RwLock<BTreeMap<SamplingPortId, Arc<SamplingPort>>> =
+    RwLock::new(BTreeMap::new());
+
+impl From<SamplingPortId> for ApexSamplingPortId {
+    fn from(id: SamplingPortId) -> Self {
+        id.0
+    }
+}
+
+impl From<ApexSamplingPortId> for SamplingPortId {
+    fn from(value: ApexSamplingPortId) -> Self {
+        Self(value)
+    }
+}
+
+impl SamplingPort {
+    pub fn new(
+        partition_id: PartitionId,
+        channel: Arc<Channel>,
+        config: &SamplingPortConfig,
+    ) -> Result<Arc<Self>> {
+        if !channel.is_sampling() {
+            return Err(InternalError::InvalidChannelConfig);
+        }
+
+        let port = Arc::new(Self {
+            identifier: SamplingPortId::new(),
+            name: config.name,
+            partition_id,
+            channel,
+            direction: config.direction,
+            refresh_period: config.refresh_period,
+            last_validity: RwLock::new(ApexValidity::Invalid),
+        });
+
+        SAMPLING_PORTS.write().insert(port.identifier, port.clone());
+
+        Ok(port)
+    }
+
+    pub fn find_by_id(partition_id: PartitionId, identifier: SamplingPortId) -> Option<Arc<Self>> {
+        SAMPLING_PORTS
+            .read()
+            .get(&identifier)
+            .filter(|port| port.partition_id == partition_id)
+            .cloned()
+    }
+
+    pub fn find_by_name(
+        partition_id: PartitionId,
+        name: &ApexSamplingPortName,
+    ) -> Option<Arc<Self>> {
+        SAMPLING_PORTS
+            .read()
+            .values()
+            .find(|port| port.partition_id == partition_id && port.name == *name)
+            .cloned()
+    }
+
+    /// Removes all the sampling ports created by the partition.
+    pub(crate) fn remove_by_partition(partition_id: PartitionId) {
+        SAMPLING_PORTS
+            .write()
+            .retain(|_, port| port.partition_id != partition_id);
+    }
+
+    pub fn identifier(&self) -> SamplingPortId {
+        self.identifier
+    }
+
+    pub fn name(&self) -> ApexSamplingPortName {
+        self.name
+    }
+
+    pub fn partition_id(&self) -> PartitionId {
+        self.partition_id
+    }
+
+    pub fn direction(&self) -> ApexPortDirection {
+        self.direction
+    }
+
+    pub fn max_message_size(&self) -> ApexMessageSize {
+        self.channel.max_message_size()
+    }
+
+    pub fn refresh_period(&self) -> ApexSystemTime {
+        self.refresh_period
+    }
+
+    pub fn write(&self, message: &[ApexByte]) {
+        *self.sampling().message.write() = Some(SamplingMessage {
+            data: message.to_vec(),
+            time: hal!().cpu().get_time(),
+        });
+    }
+
+    pub fn read(&self, buffer: &mut [ApexByte]) -> Option<(ApexMessageSize, ApexValidity)> {
+        let message = self.sampling().message.read();
+        let message = message.as_ref()?;
+
+        buffer[..message.data.len()].copy_from_slice(&message.data);
+
+        let age = hal!().cpu().get_time().saturating_sub(message.time);
+        let validity = if age <= time_as_duration(self.refresh_period) {
+            ApexValidity::Valid
+        } else {
+            ApexValidity::Invalid
+        };
+        *self.last_validity.write() = validity;
+
+        Some((message.data.len() as _, validity))
+    }
+
+    pub fn status(&self) -> ApexSamplingPortStatus {
+        ApexSamplingPortStatus {
+            refresh_period: self.refresh_period,
+            max_message_size: self.max_message_size(),
+            port_direction: self.direction,
+            last_msg_validity: *self.last_validity.read(),
+        }
+    }
+
+    fn sampling(&self) -> &SamplingChannel {
+        match self.channel.inner() {
+            ChannelInner::Sampling(sampling) => sampling,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl SamplingChannel {
+    pub(crate) const fn new() -> Self {
+        Self {
+            message: RwLock::new(None),
+        }
+    }
+}
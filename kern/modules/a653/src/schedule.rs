@@ -0,0 +1,153 @@
+use alloc::{collections::BTreeMap, sync::Arc};
+
+use jrinx_apex::*;
+use jrinx_error::{InternalError, Result};
+use jrinx_hal::{Cpu, Hal};
+use jrinx_multitask::runtime::{Runtime, RuntimeSchedTable};
+use jrinx_serial_id_macro::SerialId;
+use spin::{Mutex, RwLock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, SerialId)]
+pub struct ScheduleId(ApexScheduleId);
+
+/// A module schedule, which consists of the sched tables of all the cores it runs on.
+pub struct Schedule {
+    identifier: ScheduleId,
+    name: ApexScheduleName,
+    sched_tables: BTreeMap<usize, RuntimeSchedTable>,
+}
+
+pub struct ScheduleConfig {
+    pub name: ApexScheduleName,
+    pub sched_tables: BTreeMap<usize, RuntimeSchedTable>,
+}
+
+struct ScheduleState {
+    current: Option<ScheduleId>,
+    next: Option<(ScheduleId, ApexSystemTime)>,
+    last_switch: ApexSystemTime,
+}
+
+static SCHEDULES: RwLock<BTreeMap<ScheduleId, Arc<Schedule>>> = RwLock::new(BTreeMap::new());
+
+static STATE: Mutex<ScheduleState> = Mutex::new(ScheduleState {
+    current: None,
+    next: None,
+    last_switch: 0,
+});
+
+impl From<ScheduleId> for ApexScheduleId {
+    fn from(id: ScheduleId) -> Self {
+        id.0
+    }
+}
+
+impl From<ApexScheduleId> for ScheduleId {
+    fn from(value: ApexScheduleId) -> Self {
+        Self(value)
+    }
+}
+
+impl Schedule {
+    pub fn new(config: ScheduleConfig) -> Result<Arc<Self>> {
+        let schedule = Arc::new(Self {
+            identifier: ScheduleId::new(),
+            name: config.name,
+            sched_tables: config.sched_tables,
+        });
+
+        SCHEDULES
+            .write()
+            .insert(schedule.identifier, schedule.clone());
+
+        Ok(schedule)
+    }
+
+    pub fn find_by_id(identifier: ScheduleId) -> Option<Arc<Self>> {
+        SCHEDULES.read().get(&identifier).cloned()
+    }
+
+    pub fn find_by_name(name: &ApexScheduleName) -> Option<Arc<Self>> {
+        SCHEDULES
+            .read()
+            .values()
+            .find(|schedule| schedule.name == *name)
+            .cloned()
+    }
+
+    pub fn identifier(&self) -> ScheduleId {
+        self.identifier
+    }
+
+    pub fn name(&self) -> ApexScheduleName {
+        self.name
+    }
+
+    /// Enacts the schedule on all its cores at once, which is meant for the initial schedule.
+    pub fn enact(&self) -> Result<()> {
+        for (&cpu_id, sched_table) in self.sched_tables.iter() {
+            Runtime::with_spec_cpu(cpu_id, |rt| rt.enact_sched_table(sched_table.renew()))??;
+        }
+
+        STATE.lock().current = Some(self.identifier);
+        Ok(())
+    }
+
+    /// Requests to switch to the schedule at the end of the current major frame, i.e., the
+    /// latest start of the next major frame among all the cores.
+    ///
+    /// A switch requested before but not taken effect yet is overridden.
+    pub fn request(&self) -> Result<()> {
+        let mut state = STATE.lock();
+        state.resolve();
+
+        if state.current == Some(self.identifier) && state.next.is_none() {
+            return Ok(());
+        }
+
+        let now = hal!().cpu().get_time();
+        let mut switch_at = now;
+        for &cpu_id in self.sched_tables.keys() {
+            let start = Runtime::with_spec_cpu(cpu_id, |rt| rt.next_frame_start(now))?
+                .ok_or(InternalError::InvalidRuntimeSchedTable)?;
+            switch_at = switch_at.max(start);
+        }
+
+        for (&cpu_id, sched_table) in self.sched_tables.iter() {
+            Runtime::with_spec_cpu(cpu_id, |rt| {
+                rt.defer_sched_table(sched_table.renew(), switch_at)
+            })??;
+        }
+
+        state.next = if state.current == Some(self.identifier) {
+            None
+        } else {
+            Some((self.identifier, duration_as_time(switch_at)))
+        };
+        Ok(())
+    }
+
+    pub fn status() -> ApexScheduleStatus {
+        let mut state = STATE.lock();
+        state.resolve();
+
+        let current = state.current.map_or(0, ApexScheduleId::from);
+        ApexScheduleStatus {
+            time_of_last_schedule_switch: state.last_switch,
+            current_schedule: current,
+            next_schedule: state.next.map_or(current, |(next, _)| next.into()),
+        }
+    }
+}
+
+impl ScheduleState {
+    fn resolve(&mut self) {
+        if let Some((next, switch_at)) = self.next {
+            if duration_as_time(hal!().cpu().get_time()) >= switch_at {
+                self.current = Some(next);
+                self.next = None;
+                self.last_switch = switch_at;
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use alloc::{collections::BTreeMap, sync::Arc};
+
+use jrinx_apex::*;
+use jrinx_error::Result;
+use jrinx_serial_id_macro::SerialId;
+use spin::{Mutex, RwLock};
+
+use crate::{
+    partition::PartitionId,
+    process::Process,
+    wait::{WaitQueue, WaitResult},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, SerialId)]
+pub struct SemaphoreId(ApexSemaphoreId);
+
+pub struct Semaphore {
+    identifier: SemaphoreId,
+    name: ApexSemaphoreName,
+    partition_id: PartitionId,
+    maximum_value: ApexSemaphoreValue,
+    discipline: ApexQueueDiscipline,
+    state: Mutex<SemaphoreState>,
+}
+
+pub struct SemaphoreConfig {
+    pub name: ApexSemaphoreName,
+    pub current_value: ApexSemaphoreValue,
+    pub maximum_value: ApexSemaphoreValue,
+    pub discipline: ApexQueueDiscipline,
+}
+
+struct SemaphoreState {
+    current_value: ApexSemaphoreValue,
+    waiters: WaitQueue,
+}
+
+static SEMAPHORES: RwLock<BTreeMap<SemaphoreId, Arc<Semaphore>>> = RwLock::new(BTreeMap::new());
+
+impl From<SemaphoreId> for ApexSemaphoreId {
+    fn from(id: SemaphoreId) -> Self {
+        id.0
+    }
+}
+
+impl From<ApexSemaphoreId> for SemaphoreId {
+    fn from(value: ApexSemaphoreId) -> Self {
+        Self(value)
+    }
+}
+
+impl Semaphore {
+    pub fn new(partition_id: PartitionId, config: &SemaphoreConfig) -> Result<Arc<Self>> {
+        let semaphore = Arc::new(Self {
+            identifier: SemaphoreId::new(),
+            name: config.name,
+            partition_id,
+            maximum_value: config.maximum_value,
+            discipline: config.discipline,
+            state: Mutex::new(SemaphoreState {
+                current_value: config.current_value,
+                waiters: WaitQueue::new(config.discipline),
+            }),
+        });
+
+        SEMAPHORES
+            .write()
+            .insert(semaphore.identifier, semaphore.clone());
+
+        Ok(semaphore)
+    }
+
+    pub fn find_by_id(partition_id: PartitionId, identifier: SemaphoreId) -> Option<Arc<Self>> {
+        SEMAPHORES
+            .read()
+            .get(&identifier)
+            .filter(|semaphore| semaphore.partition_id == partition_id)
+            .cloned()
+    }
+
+    pub fn find_by_name(partition_id: PartitionId, name: &ApexSemaphoreName) -> Option<Arc<Self>> {
+        SEMAPHORES
+            .read()
+            .values()
+            .find(|semaphore| semaphore.partition_id == partition_id && semaphore.name == *name)
+            .cloned()
+    }
+
+    /// Removes all the semaphores created by the partition.
+    pub(crate) fn remove_by_partition(partition_id: PartitionId) {
+        SEMAPHORES
+            .write()
+            .retain(|_, semaphore| semaphore.partition_id != partition_id);
+    }
+
+    pub fn identifier(&self) -> SemaphoreId {
+        self.identifier
+    }
+
+    pub fn name(&self) -> ApexSemaphoreName {
+        self.name
+    }
+
+    pub fn partition_id(&self) -> PartitionId {
+        self.partition_id
+    }
+
+    pub fn maximum_value(&self) -> ApexSemaphoreValue {
+        self.maximum_value
+    }
+
+    pub fn discipline(&self) -> ApexQueueDiscipline {
+        self.discipline
+    }
+
+    /// Decrements the semaphore, waiting up to `timeout` for it to become positive.
+    pub fn wait(
+        self: &Arc<Self>,
+        timeout: ApexSystemTime,
+    ) -> core::result::Result<(), ApexReturnCode> {
+        let mut state = self.state.lock();
+
+        if state.current_value > 0 {
+            state.current_value -= 1;
+            return Ok(());
+        }
+        if timeout == 0 {
+            return Err(ApexReturnCode::NotAvailable);
+        }
+        if !Process::current().unwrap().can_wait() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let token = state.waiters.push(());
+        drop(state);
+
+        let semaphore = self.clone();
+        let result = token.wait(timeout, move |identifier| {
+            let waiter = semaphore.state.lock().waiters.remove(identifier);
+            if let Some(waiter) = waiter {
+                waiter.time_out();
+            }
+        });
+
+        match result {
+            WaitResult::Notified(()) => Ok(()),
+            WaitResult::TimedOut => Err(ApexReturnCode::TimedOut),
+        }
+    }
+
+    /// Signals the semaphore, handing it directly to the first waiting process if any.
+    pub fn signal(&self) -> core::result::Result<(), ApexReturnCode> {
+        let mut state = self.state.lock();
+
+        if let Some(waiter) = state.waiters.pop() {
+            waiter.notify(());
+            return Ok(());
+        }
+        if state.current_value == self.maximum_value {
+            return Err(ApexReturnCode::NoAction);
+        }
+        state.current_value += 1;
+
+        Ok(())
+    }
+
+    pub fn status(&self) -> ApexSemaphoreStatus {
+        let state = self.state.lock();
+
+        ApexSemaphoreStatus {
+            current_value: state.current_value,
+            maximum_value: self.maximum_value,
+            waiting_processes: state.waiters.len() as _,
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
+
+use jrinx_apex::*;
+use jrinx_hal::{Cpu, Hal, Interrupt};
+use jrinx_multitask::{
+    executor::{Executor, ExecutorStatus},
+    runtime::Runtime,
+};
+use jrinx_timed_event::{TimedEvent, TimedEventHandler};
+use spin::Mutex;
+
+use crate::{
+    partition::Partition,
+    process::{Process, ProcessId},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitResult<R> {
+    Notified(R),
+    TimedOut,
+}
+
+/// A queue of processes waiting on a resource, ordered by its [`ApexQueueDiscipline`].
+///
+/// Each waiter carries `D` from the waiting process to its notifier, and receives `R` back.
+pub struct WaitQueue<D = (), R = ()> {
+    discipline: ApexQueueDiscipline,
+    waiters: VecDeque<Waiter<D, R>>,
+}
+
+pub struct Waiter<D, R> {
+    process: Arc<Process>,
+    data: D,
+    state: Arc<Mutex<WaitState<R>>>,
+}
+
+#[must_use]
+pub struct WaitToken<R> {
+    process: Arc<Process>,
+    state: Arc<Mutex<WaitState<R>>>,
+}
+
+/// The outcome of a wait, which is shared by the waiting process, its notifier and its timer.
+///
+/// It is only locked with interrupts disabled, since the timer marks it in interrupt context.
+struct WaitState<R> {
+    result: Option<WaitResult<R>>,
+    expired: bool,
+}
+
+impl<D, R> WaitQueue<D, R> {
+    pub const fn new(discipline: ApexQueueDiscipline) -> Self {
+        Self {
+            discipline,
+            waiters: VecDeque::new(),
+        }
+    }
+
+    pub fn discipline(&self) -> ApexQueueDiscipline {
+        self.discipline
+    }
+
+    pub fn set_discipline(&mut self, discipline: ApexQueueDiscipline) {
+        self.discipline = discipline;
+    }
+
+    pub fn len(&self) -> usize {
+        self.waiters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.waiters.is_empty()
+    }
+
+    /// Enqueues the current process and marks it as waiting.
+    ///
+    /// The caller must release any lock protecting this queue before [`WaitToken::wait`].
+    pub fn push(&mut self, data: D) -> WaitToken<R> {
+        let process = Process::current().unwrap();
+        let state = Arc::new(Mutex::new(WaitState {
+            result: None,
+            expired: false,
+        }));
+
+        process.set_process_state(ApexProcessState::Waiting);
+        Executor::with_current(|ex| ex.set_status(ExecutorStatus::Blocked)).unwrap();
+
+        let index = match self.discipline {
+            ApexQueueDiscipline::Fifo => self.waiters.len(),
+            ApexQueueDiscipline::Priority => self
+                .waiters
+                .iter()
+                .position(|waiter| waiter.process.curr_priority() < process.curr_priority())
+                .unwrap_or(self.waiters.len()),
+        };
+        self.waiters.insert(
+            index,
+            Waiter {
+                process: process.clone(),
+                data,
+                state: state.clone(),
+            },
+        );
+
+        WaitToken { process, state }
+    }
+
+    pub fn pop(&mut self) -> Option<Waiter<D, R>> {
+        self.waiters.pop_front()
+    }
+
+    pub fn remove(&mut self, identifier: ProcessId) -> Option<Waiter<D, R>> {
+        let index = self
+            .waiters
+            .iter()
+            .position(|waiter| waiter.process.identifier() == identifier)?;
+        self.waiters.remove(index)
+    }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = Waiter<D, R>> + '_ {
+        self.waiters.drain(..)
+    }
+}
+
+impl<D, R> Waiter<D, R> {
+    pub fn process(&self) -> &Arc<Process> {
+        &self.process
+    }
+
+    pub fn notify(self, result: R) -> D {
+        self.resolve(WaitResult::Notified(result))
+    }
+
+    pub fn time_out(self) -> D {
+        self.resolve(WaitResult::TimedOut)
+    }
+
+    fn resolve(self, result: WaitResult<R>) -> D {
+        hal!()
+            .interrupt()
+            .with_saved_off(|| self.state.lock().result = Some(result));
+
+        self.process.set_process_state(ApexProcessState::Ready);
+        if let Err(err) = self.process.wake() {
+            warn!(
+                "failed to wake process {:?}: {:?}",
+                self.process.name(),
+                err
+            );
+        }
+
+        self.data
+    }
+}
+
+impl<R: Send + 'static> WaitToken<R> {
+    /// Blocks the current process until it is notified, or until `timeout` expires.
+    ///
+    /// On expiry, the timer interrupt only wakes the waiting process, which then calls
+    /// `on_timeout` itself. `on_timeout` is expected to remove the process from its queue and
+    /// [`Waiter::time_out`] it, so it never takes the lock of the resource in interrupt context.
+    ///
+    /// The expiry is marked under the same lock as the result, which the process checks and
+    /// blocks under at once, so neither of them is lost if it arrives in between.
+    pub fn wait(
+        self,
+        timeout: ApexSystemTime,
+        on_timeout: impl FnOnce(ProcessId) + Send + 'static,
+    ) -> WaitResult<R> {
+        let identifier = self.process.identifier();
+        let on_timeout = Arc::new(Mutex::new(Some(on_timeout)));
+
+        // the wait also expires when the process is stopped
+        self.process.set_wait_cancel(Some(Box::new({
+            let on_timeout = on_timeout.clone();
+            move || expire(&on_timeout, identifier)
+        })));
+
+        let tracker = (timeout != APEX_TIME_INFINITY)
+            .then(|| self.process.executor())
+            .flatten()
+            .map(|(cpu_id, executor_id)| {
+                let partition = Partition::find_by_id(self.process.partition_id()).unwrap();
+                let state = self.state.clone();
+                hal!().interrupt().with_saved_off(|| {
+                    TimedEvent::create(
+                        hal!()
+                            .cpu()
+                            .get_time()
+                            .saturating_add(time_as_duration(timeout)),
+                        TimedEventHandler::new(
+                            move || {
+                                state.lock().expired = true;
+                                let _ = partition.with_inspector(cpu_id, |is| is.wake(executor_id));
+                                if cpu_id != hal!().cpu().id() {
+                                    hal!().interrupt().send_ipi(&[cpu_id]);
+                                }
+                            },
+                            || {},
+                        ),
+                    )
+                })
+            });
+
+        let result = loop {
+            Runtime::switch_yield();
+
+            // a stopped process gives up the wait to unwind
+            self.process.handle_deadline_miss();
+            let expired = hal!()
+                .interrupt()
+                .with_saved_off(|| self.state.lock().expired);
+            if expired || self.process.is_stale() {
+                expire(&on_timeout, identifier);
+            }
+
+            let result = hal!().interrupt().with_saved_off(|| {
+                let mut state = self.state.lock();
+                let result = state.result.take();
+                if result.is_none() && !state.expired {
+                    Executor::with_current(|ex| ex.set_status(ExecutorStatus::Blocked)).unwrap();
+                }
+                result
+            });
+            if let Some(result) = result {
+                break result;
+            }
+        };
+
+        self.process.set_wait_cancel(None);
+        if let Some(tracker) = tracker {
+            let _ = hal!().interrupt().with_saved_off(|| tracker.cancel());
+        }
+
+        result
+    }
+}
+
+fn expire(on_timeout: &Mutex<Option<impl FnOnce(ProcessId)>>, identifier: ProcessId) {
+    let on_timeout = on_timeout.lock().take();
+    if let Some(on_timeout) = on_timeout {
+        on_timeout(identifier);
+    }
+}
+
+/// Blocks the current process until `time`, or returns at once if `time` has passed.
+pub fn wait_until(time: ApexSystemTime) {
+    let timeout = time - duration_as_time(hal!().cpu().get_time());
+    if timeout <= 0 {
+        return;
+    }
+
+    let queue = Arc::new(Mutex::new(WaitQueue::<(), ()>::new(
+        ApexQueueDiscipline::Fifo,
+    )));
+    let token = queue.lock().push(());
+    token.wait(timeout, move |id| {
+        let waiter = queue.lock().remove(id);
+        if let Some(waiter) = waiter {
+            waiter.time_out();
+        }
+    });
+}
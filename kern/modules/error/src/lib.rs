@@ -10,6 +10,7 @@ pub enum InternalError {
     InvalidVirtAddr,
     DuplicateTaskId,
     InvalidExecutorId,
+    InvalidExecutorStatus,
     DuplicateExecutorId,
     InvalidInspectorId,
     DuplicateInspectorId,
@@ -21,6 +22,10 @@ pub enum InternalError {
     InvalidApexName,
     InvalidApexPriority,
     InvalidApexNumCores,
+    InvalidApexTime,
+    InvalidApexLockLevel,
+    InvalidChannelConfig,
+    InvalidPartitionConfig,
     InvalidSyscallNumber,
 }
 
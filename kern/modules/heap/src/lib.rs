@@ -26,3 +26,9 @@ pub fn enlarge(region: (VirtAddr, usize)) {
             .add_to_heap(region.0.as_usize(), region.0.as_usize() + region.1);
     }
 }
+
+/// Returns the number of bytes not yet allocated from the heap.
+pub fn free_size() -> usize {
+    let heap = unsafe { HEAP_ALLOCATOR.lock() };
+    heap.stats_total_bytes() - heap.stats_alloc_actual()
+}
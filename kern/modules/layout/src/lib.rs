@@ -33,3 +33,6 @@ def_ld_sym!(_edev);
 
 def_ld_sym!(_stest);
 def_ld_sym!(_etest);
+
+def_ld_sym!(_spart);
+def_ld_sym!(_epart);
@@ -141,6 +141,10 @@ impl Executor {
         self.priority
     }
 
+    pub(crate) fn set_priority(&mut self, priority: ExecutorPriority) {
+        self.priority = priority;
+    }
+
     pub fn status(&self) -> ExecutorStatus {
         self.status
     }
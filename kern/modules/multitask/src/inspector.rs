@@ -1,5 +1,15 @@
-use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
-use core::{any::Any, fmt::Display, pin::Pin};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{
+    any::Any,
+    fmt::Display,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use jrinx_addr::VirtAddr;
 use jrinx_error::{InternalError, Result};
@@ -35,6 +45,7 @@ pub struct Inspector {
     id: InspectorId,
     status: Mutex<InspectorStatus>,
     scheduler: RwLock<Scheduler>,
+    reschedule: AtomicBool,
     ext: Arc<dyn Any + Send + Sync>,
 }
 
@@ -42,6 +53,8 @@ struct Scheduler {
     registry: BTreeMap<ExecutorId, Pin<Box<Executor>>>,
     queue: ExecutorQueue,
     wait_list: Vec<ExecutorId>,
+    suspended: BTreeSet<ExecutorId>,
+    lock: Option<ExecutorId>,
 }
 
 impl Default for Inspector {
@@ -50,6 +63,23 @@ impl Default for Inspector {
     }
 }
 
+impl Scheduler {
+    /// Returns a runnable executor of the highest priority other than the lock owner, which is
+    /// not kept out by the lock.
+    fn highest_ready(&self) -> Option<ExecutorId> {
+        self.registry
+            .iter()
+            .find(|&(id, executor)| {
+                Some(*id) != self.lock
+                    && executor.priority() == ExecutorPriority::new(ExecutorPriority::MAX)
+                    && executor.status() == ExecutorStatus::Runnable
+                    && !self.suspended.contains(id)
+                    && !self.wait_list.contains(id)
+            })
+            .map(|(&id, _)| id)
+    }
+}
+
 impl Inspector {
     pub fn new() -> Self {
         Self::new_with_ext(())
@@ -63,7 +93,10 @@ impl Inspector {
                 registry: BTreeMap::new(),
                 queue: ExecutorQueue::new(),
                 wait_list: Vec::new(),
+                suspended: BTreeSet::new(),
+                lock: None,
             }),
+            reschedule: AtomicBool::new(false),
             ext: Arc::new(ext),
         }
     }
@@ -112,11 +145,36 @@ impl Inspector {
     }
 
     pub fn unregister(&self, executor_id: ExecutorId) -> Result<()> {
-        self.scheduler
-            .write()
+        let mut scheduler = self.scheduler.write();
+        scheduler
             .registry
             .remove(&executor_id)
             .ok_or(InternalError::InvalidExecutorId)?;
+        scheduler.suspended.remove(&executor_id);
+        Ok(())
+    }
+
+    /// Finishes an executor before it runs to completion.
+    ///
+    /// The executor is unregistered once it is dequeued, or switched out if it is running. A
+    /// blocked executor is dropped without resuming its coroutine, so the values on its stack
+    /// are leaked rather than dropped.
+    pub fn finish(&self, id: ExecutorId) -> Result<()> {
+        let mut scheduler = self.scheduler.write();
+
+        let executor = scheduler
+            .registry
+            .get_mut(&id)
+            .ok_or(InternalError::InvalidExecutorId)?;
+        executor.set_status(ExecutorStatus::Finished);
+        let priority = executor.priority();
+
+        scheduler.suspended.remove(&id);
+        if let Some(index) = scheduler.wait_list.iter().position(|&x| x == id) {
+            scheduler.wait_list.swap_remove(index);
+            scheduler.queue.enqueue(priority, id);
+        }
+
         Ok(())
     }
 
@@ -134,24 +192,186 @@ impl Inspector {
     }
 
     pub fn wake(&self, id: ExecutorId) -> Result<()> {
+        let current = match self.status() {
+            InspectorStatus::Running(current) => Some(current),
+            _ => None,
+        };
+
         let mut scheduler = self.scheduler.write();
 
+        let executor = scheduler
+            .registry
+            .get_mut(&id)
+            .ok_or(InternalError::InvalidExecutorId)?;
+        if executor.status() == ExecutorStatus::Finished {
+            return Err(InternalError::InvalidExecutorStatus);
+        }
+        executor.set_status(ExecutorStatus::Runnable);
+        let priority = executor.priority();
+
+        if scheduler.suspended.contains(&id) {
+            return Ok(());
+        }
+
         if let Some(index) = scheduler.wait_list.iter().position(|&x| x == id) {
             scheduler.wait_list.swap_remove(index);
-            scheduler.queue.enqueue(
-                scheduler
+            scheduler.queue.enqueue(priority, id);
+        }
+
+        if let Some(current) = current.filter(|&current| current != id) {
+            if scheduler
+                .registry
+                .get(&current)
+                .is_some_and(|ex| ex.priority() < priority)
+            {
+                self.reschedule.store(true, Ordering::SeqCst);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Suspends an executor, which is not scheduled until [`Inspector::resume`] regardless of
+    /// its status.
+    pub fn suspend(&self, id: ExecutorId) -> Result<()> {
+        let mut scheduler = self.scheduler.write();
+
+        let executor = scheduler
+            .registry
+            .get(&id)
+            .ok_or(InternalError::InvalidExecutorId)?;
+        if executor.status() == ExecutorStatus::Finished {
+            return Err(InternalError::InvalidExecutorStatus);
+        }
+
+        scheduler.suspended.insert(id);
+        if scheduler.queue.remove(&id).is_some() {
+            scheduler.wait_list.push(id);
+        }
+
+        Ok(())
+    }
+
+    /// Resumes a suspended executor, re-queuing it if it is runnable.
+    pub fn resume(&self, id: ExecutorId) -> Result<()> {
+        let current = match self.status() {
+            InspectorStatus::Running(current) => Some(current),
+            _ => None,
+        };
+
+        let mut scheduler = self.scheduler.write();
+
+        let executor = scheduler
+            .registry
+            .get(&id)
+            .ok_or(InternalError::InvalidExecutorId)?;
+        if executor.status() == ExecutorStatus::Finished {
+            return Err(InternalError::InvalidExecutorStatus);
+        }
+        let runnable = executor.status() == ExecutorStatus::Runnable;
+        let priority = executor.priority();
+
+        if !scheduler.suspended.remove(&id) {
+            return Err(InternalError::InvalidExecutorStatus);
+        }
+
+        if !runnable {
+            return Ok(());
+        }
+
+        if let Some(index) = scheduler.wait_list.iter().position(|&x| x == id) {
+            scheduler.wait_list.swap_remove(index);
+            scheduler.queue.enqueue(priority, id);
+        }
+
+        if let Some(current) = current.filter(|&current| current != id) {
+            if scheduler
+                .registry
+                .get(&current)
+                .is_some_and(|ex| ex.priority() < priority)
+            {
+                self.reschedule.store(true, Ordering::SeqCst);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Changes the priority of an executor, re-queuing it if it is runnable.
+    ///
+    /// Lowering the running executor, or raising a queued one above it, requests a reschedule.
+    pub fn set_priority(&self, id: ExecutorId, priority: ExecutorPriority) -> Result<()> {
+        let current = match self.status() {
+            InspectorStatus::Running(current) => Some(current),
+            _ => None,
+        };
+
+        let mut scheduler = self.scheduler.write();
+
+        let executor = scheduler
+            .registry
+            .get_mut(&id)
+            .ok_or(InternalError::InvalidExecutorId)?;
+        if executor.status() == ExecutorStatus::Finished {
+            return Err(InternalError::InvalidExecutorStatus);
+        }
+        let old_priority = executor.priority();
+        let runnable = executor.status() == ExecutorStatus::Runnable;
+        executor.set_priority(priority);
+
+        if scheduler.queue.remove(&id).is_some() {
+            scheduler.queue.enqueue(priority, id);
+        }
+
+        if current == Some(id) {
+            if priority < old_priority {
+                self.reschedule.store(true, Ordering::SeqCst);
+            }
+        } else if let Some(current) = current {
+            if runnable
+                && scheduler
                     .registry
-                    .get(&id)
-                    .ok_or(InternalError::InvalidExecutorId)?
-                    .priority(),
-                id,
-            );
-            Ok(())
-        } else {
-            Err(InternalError::InvalidExecutorId)
+                    .get(&current)
+                    .is_some_and(|ex| ex.priority() < priority)
+            {
+                self.reschedule.store(true, Ordering::SeqCst);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Locks the inspector to `owner`, which is then the only executor to be dequeued besides
+    /// the executors of the highest priority [`ExecutorPriority::MAX`], until it is unlocked
+    /// with `None`.
+    ///
+    /// If `owner` is not registered in this inspector (e.g., it runs on another core), only the
+    /// executors of the highest priority are dequeued while the inspector is locked.
+    pub fn lock(&self, owner: Option<ExecutorId>) {
+        let current = match self.status() {
+            InspectorStatus::Running(current) => Some(current),
+            _ => None,
+        };
+
+        self.scheduler.write().lock = owner;
+
+        if owner.is_none() || current.is_some_and(|current| Some(current) != owner) {
+            self.reschedule.store(true, Ordering::SeqCst);
         }
     }
 
+    /// Takes the reschedule request, which is kept pending while the running executor holds
+    /// the lock of the inspector, unless an executor of the highest priority is ready.
+    pub fn take_reschedule(&self) -> bool {
+        if let InspectorStatus::Running(current) = self.status() {
+            let scheduler = self.scheduler.read();
+            if scheduler.lock == Some(current) && scheduler.highest_ready().is_none() {
+                return false;
+            }
+        }
+        self.reschedule.swap(false, Ordering::SeqCst)
+    }
+
     pub(crate) fn with_executor<F, R>(&self, id: ExecutorId, f: F) -> Result<R>
     where
         F: FnOnce(&mut Pin<Box<Executor>>) -> R,
@@ -166,17 +386,32 @@ impl Inspector {
 
     pub(crate) fn dequeue(&self) -> Option<ExecutorId> {
         let mut scheduler = self.scheduler.write();
+
+        if let Some(owner) = scheduler.lock {
+            if let Some(id) = scheduler.highest_ready() {
+                if scheduler.queue.remove(&id).is_some() {
+                    return Some(id);
+                }
+            }
+            let runnable = scheduler
+                .registry
+                .get(&owner)
+                .is_some_and(|executor| executor.status() == ExecutorStatus::Runnable);
+            return (runnable && scheduler.queue.remove(&owner).is_some()).then_some(owner);
+        }
+
         while let Some((_, id)) = scheduler.queue.dequeue() {
-            if let Some(executor) = scheduler.registry.get(&id) {
-                match executor.status() {
-                    ExecutorStatus::Runnable => return Some(id),
-                    ExecutorStatus::Blocked => {
-                        scheduler.wait_list.push(id);
-                    }
-                    ExecutorStatus::Finished => panic!("executor {:?} is finished", id),
+            let Some(executor) = scheduler.registry.get(&id) else {
+                continue;
+            };
+            match executor.status() {
+                ExecutorStatus::Runnable if !scheduler.suspended.contains(&id) => return Some(id),
+                ExecutorStatus::Runnable | ExecutorStatus::Blocked => {
+                    scheduler.wait_list.push(id);
+                }
+                ExecutorStatus::Finished => {
+                    scheduler.registry.remove(&id);
                 }
-            } else {
-                panic!("executor {:?} is not found", id);
             }
         }
         None
@@ -217,6 +452,7 @@ impl Inspector {
             trace!("switch into executor {:?}", executor_id);
 
             Inspector::with_current(|is| {
+                is.reschedule.store(false, Ordering::SeqCst);
                 is.set_current(Some(executor_id));
             })
             .unwrap();
@@ -1,4 +1,6 @@
-use core::{cell::SyncUnsafeCell, future::Future, sync::atomic::AtomicUsize, time::Duration};
+use core::{
+    cell::SyncUnsafeCell, future::Future, ops::Range, sync::atomic::AtomicUsize, time::Duration,
+};
 
 use alloc::{
     collections::{BTreeMap, VecDeque},
@@ -10,7 +12,7 @@ use jrinx_hal::{Cpu, Hal, HaltReason, Interrupt};
 use jrinx_percpu::percpu;
 use jrinx_timed_event::{TimedEvent, TimedEventHandler, TimedEventTracker};
 use mtxgroup::MutexGroup;
-use spin::{Mutex, RwLock};
+use spin::{Mutex, Once, RwLock};
 
 use crate::{
     arch::{self, SwitchContext},
@@ -19,6 +21,39 @@ use crate::{
     Task, TaskPriority,
 };
 
+/// The delay from synchronizing the major frames to the module-wide datum, which gives all cpus
+/// time to start their sched tables before it.
+pub const SYNC_DATUM_DELAY: Duration = Duration::from_millis(10);
+
+/// The maximum lateness of a window start not regarded as missed.
+pub const WINDOW_START_TOLERANCE: Duration = Duration::from_micros(500);
+
+/// The default lateness of a window start or end beyond which the window is logged.
+pub const WINDOW_LOG_THRESHOLD: Duration = Duration::from_millis(1);
+
+/// An anomaly in the timing of the sched table, detected by the runtime of the current cpu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeTimingEvent {
+    /// The window of `entry` started at `actual` instead of `expected`.
+    WindowStartMissed {
+        entry: RuntimeSchedTableEntry,
+        expected: Duration,
+        actual: Duration,
+    },
+    /// The cpu started its sched table at `joined`, after the module-wide datum `datum`.
+    LateJoin { datum: Duration, joined: Duration },
+    /// The major frame starting at `datum` is out of phase with the one of `other_cpu_id`
+    /// starting at `other_datum`.
+    OutOfPhase {
+        datum: Duration,
+        other_cpu_id: usize,
+        other_datum: Duration,
+    },
+}
+
+/// Handles a [`RuntimeTimingEvent`] on the current cpu.
+pub type RuntimeTimingHandler = fn(RuntimeTimingEvent);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RuntimeStatus {
     Unused,
@@ -28,9 +63,34 @@ pub enum RuntimeStatus {
     Endpoint,
 }
 
+/// The time spent in the gaps between the windows of the sched tables.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeGapStats {
+    pub idle: Duration,
+    pub background: Duration,
+}
+
+/// The time accounting of a window of the sched tables, identified by its inspector and offset.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeWindowStats {
+    pub activations: u64,
+    pub last_start: Duration,
+    pub last_end: Duration,
+    /// The time consumed by the inspector in the last activation.
+    pub last_consumed: Duration,
+    pub total_consumed: Duration,
+    /// The maximum lateness of the window start.
+    pub max_jitter: Duration,
+    /// The maximum lateness of the window end.
+    pub max_overrun: Duration,
+    pub overruns: u64,
+}
+
 pub struct Runtime {
     scheduler: RwLock<RuntimeInspectorScheduler>,
     status: Mutex<RuntimeStatus>,
+    gap_stats: Mutex<RuntimeGapStats>,
+    window_stats: Mutex<BTreeMap<(InspectorId, Duration), RuntimeWindowStats>>,
     switch_context: SyncUnsafeCell<SwitchContext>,
 }
 
@@ -38,6 +98,7 @@ struct RuntimeInspectorScheduler {
     registry: BTreeMap<InspectorId, Inspector>,
     queue: VecDeque<InspectorId>,
     sched_table: Option<RuntimeSchedTable>,
+    pending_sched_table: Option<(RuntimeSchedTable, Duration)>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -51,11 +112,18 @@ pub struct RuntimeSchedTableEntry {
 pub struct RuntimeSchedTable {
     frame_size: Duration,
     table: Vec<RuntimeSchedTableEntry>,
+    background: Option<InspectorId>,
     next: AtomicUsize,
     datum: Mutex<Duration>,
     events: Mutex<VecDeque<TimedEventTracker>>,
 }
 
+static SYNC_DATUM: Once<Duration> = Once::new();
+
+static TIMING_HANDLER: Once<RuntimeTimingHandler> = Once::new();
+
+static WINDOW_THRESHOLD: Mutex<Duration> = Mutex::new(WINDOW_LOG_THRESHOLD);
+
 impl Default for Runtime {
     fn default() -> Self {
         Self::new()
@@ -69,8 +137,14 @@ impl Runtime {
                 registry: BTreeMap::new(),
                 queue: VecDeque::new(),
                 sched_table: None,
+                pending_sched_table: None,
             }),
             status: Mutex::new(RuntimeStatus::Unused),
+            gap_stats: Mutex::new(RuntimeGapStats {
+                idle: Duration::ZERO,
+                background: Duration::ZERO,
+            }),
+            window_stats: Mutex::new(BTreeMap::new()),
             switch_context: SyncUnsafeCell::new(SwitchContext::new_runtime()),
         }
     }
@@ -84,24 +158,57 @@ impl Runtime {
         if scheduler.sched_table.is_some() {
             return Err(InternalError::DuplicateRuntimeSchedTable);
         }
-        scheduler.queue.retain(|&id| {
-            sched_table
-                .table
-                .iter()
-                .all(|entry| entry.inspector_id != id)
-        });
+        scheduler.queue.retain(|&id| !sched_table.contains(id));
         scheduler.sched_table = Some(sched_table);
         Ok(())
     }
 
     pub fn revoke_sched_table(&self) -> Result<RuntimeSchedTable> {
-        self.scheduler
-            .write()
+        let mut scheduler = self.scheduler.write();
+        scheduler.pending_sched_table = None;
+        scheduler
             .sched_table
             .take()
             .ok_or(InternalError::InvalidRuntimeSchedTable)
     }
 
+    /// Replaces the enacted sched table with `sched_table` at the start of the first major frame
+    /// no earlier than `switch_at`.
+    ///
+    /// A sched table deferred before but not switched to yet is discarded.
+    pub fn defer_sched_table(
+        &self,
+        sched_table: RuntimeSchedTable,
+        switch_at: Duration,
+    ) -> Result<()> {
+        let mut scheduler = self.scheduler.write();
+        if scheduler.sched_table.is_none() {
+            return Err(InternalError::InvalidRuntimeSchedTable);
+        }
+        scheduler.pending_sched_table = Some((sched_table, switch_at));
+        Ok(())
+    }
+
+    /// Sets the module-wide datum, to which the major frames of the sched tables on all cpus
+    /// align since then.
+    ///
+    /// Only the first datum set takes effect.
+    pub fn synchronize(datum: Duration) {
+        SYNC_DATUM.call_once(|| datum);
+    }
+
+    pub fn sync_datum() -> Option<Duration> {
+        SYNC_DATUM.get().copied()
+    }
+
+    /// Sets the handler of the anomalies in the timing of the sched tables, e.g., windows
+    /// starting later than [`WINDOW_START_TOLERANCE`]. They are only logged if there is none.
+    ///
+    /// Only the first handler set takes effect.
+    pub fn set_timing_handler(handler: RuntimeTimingHandler) {
+        TIMING_HANDLER.call_once(|| handler);
+    }
+
     pub fn register(&self, inspector: Inspector) -> Result<()> {
         let id = inspector.id();
         let mut inspectors = self.scheduler.write();
@@ -196,6 +303,66 @@ impl Runtime {
         *self.status.lock()
     }
 
+    pub fn gap_stats(&self) -> RuntimeGapStats {
+        hal!().interrupt().with_saved_off(|| *self.gap_stats.lock())
+    }
+
+    /// Sets the lateness of a window start or end beyond which the window is logged, which
+    /// defaults to [`WINDOW_LOG_THRESHOLD`].
+    pub fn set_window_log_threshold(threshold: Duration) {
+        *WINDOW_THRESHOLD.lock() = threshold;
+    }
+
+    /// Returns the `index`-th entry (ordered by offset) of the sched table and the time
+    /// accounting of its window.
+    ///
+    /// Both are copied out with interrupts disabled, since the runtime takes the same locks
+    /// right after a window is preempted.
+    pub fn window_stats(
+        &self,
+        index: usize,
+    ) -> Option<(RuntimeSchedTableEntry, RuntimeWindowStats)> {
+        hal!().interrupt().with_saved_off(|| {
+            let entry = *self
+                .scheduler
+                .read()
+                .sched_table
+                .as_ref()?
+                .table
+                .get(index)?;
+            let stats = self
+                .window_stats
+                .lock()
+                .get(&(entry.inspector_id, entry.offset))
+                .copied()
+                .unwrap_or_default();
+            Some((entry, stats))
+        })
+    }
+
+    /// Returns the start of the first window of the inspector in each of its periods, which is
+    /// the earliest one no earlier than `time`.
+    ///
+    /// Returns `None` if there is no sched table, or the inspector is not scheduled in it.
+    pub fn next_period_start(&self, id: InspectorId, time: Duration) -> Option<Duration> {
+        self.scheduler
+            .read()
+            .sched_table
+            .as_ref()
+            .and_then(|table| table.next_period_start(id, time))
+    }
+
+    /// Returns the start of the earliest major frame of the sched table no earlier than `time`.
+    ///
+    /// Returns `None` if there is no sched table, or its major frame is infinite.
+    pub fn next_frame_start(&self, time: Duration) -> Option<Duration> {
+        self.scheduler
+            .read()
+            .sched_table
+            .as_ref()
+            .and_then(|table| table.next_frame_start(time))
+    }
+
     pub(crate) fn with_inspector<F, R>(&self, id: InspectorId, f: F) -> Result<R>
     where
         F: FnOnce(&Inspector) -> R,
@@ -243,7 +410,7 @@ impl Runtime {
         VirtAddr::new(self.switch_context.get() as *const _ as usize)
     }
 
-    fn sched_table_start(&self) -> Result<()> {
+    fn sched_table_start(&self) -> Result<Option<RuntimeTimingEvent>> {
         self.scheduler
             .read()
             .sched_table
@@ -252,9 +419,57 @@ impl Runtime {
             .ok_or(InternalError::InvalidRuntimeSchedTable)
     }
 
-    fn sched_table_next(&self) -> Option<RuntimeSchedTableEntry> {
-        self.scheduler
-            .read()
+    /// Compares the datum of the major frame about to start with the ones of the other cpus,
+    /// which are all aligned to the module-wide datum if the cores stay in phase.
+    fn sched_table_check_phase(&self) -> Option<RuntimeTimingEvent> {
+        SYNC_DATUM.get()?;
+
+        let scheduler = self.scheduler.read();
+        let table = scheduler.sched_table.as_ref()?;
+        if table.frame_size == Duration::MAX || !table.at_frame_start() {
+            return None;
+        }
+
+        let datum = table.get_datum();
+        let cpu_id = hal!().cpu().id();
+        RUNTIME
+            .iter()
+            .zip(0..)
+            .filter(|&(_, other_cpu_id)| other_cpu_id != cpu_id)
+            .find_map(|(rt, other_cpu_id)| {
+                let other = rt.scheduler.read();
+                let other_table = other.sched_table.as_ref()?;
+                if other_table.frame_size != table.frame_size {
+                    return None;
+                }
+
+                let other_datum = other_table.get_datum();
+                let distance = if datum >= other_datum {
+                    datum - other_datum
+                } else {
+                    other_datum - datum
+                };
+                (distance.as_nanos() % table.frame_size.as_nanos() != 0).then_some(
+                    RuntimeTimingEvent::OutOfPhase {
+                        datum,
+                        other_cpu_id,
+                        other_datum,
+                    },
+                )
+            })
+    }
+
+    fn report_timing(event: RuntimeTimingEvent) {
+        match TIMING_HANDLER.get() {
+            Some(handler) => handler(event),
+            None => warn!("cpu#{} detected {:?}", hal!().cpu().id(), event),
+        }
+    }
+
+    fn sched_table_next(&self) -> Option<(RuntimeSchedTableEntry, Range<Duration>)> {
+        let mut scheduler = self.scheduler.write();
+        scheduler.switch_sched_table_if_due();
+        scheduler
             .sched_table
             .as_ref()
             .map(|table| table.sched_next())
@@ -265,22 +480,140 @@ impl Runtime {
 
         let runtime_switch_ctx = Runtime::with_current(|rt| rt.switch_context_addr());
 
-        Runtime::with_current(|rt| rt.sched_table_start().unwrap());
+        if let Some(event) = Runtime::with_current(|rt| rt.sched_table_start().unwrap()) {
+            Runtime::report_timing(event);
+        }
+
+        loop {
+            if let Some(event) = Runtime::with_current(|rt| rt.sched_table_check_phase()) {
+                Runtime::report_timing(event);
+            }
+            let Some((entry, window)) = Runtime::with_current(|rt| rt.sched_table_next()) else {
+                break;
+            };
+
+            Runtime::run_in_gap(runtime_switch_ctx, window.start);
+
+            let now = hal!().cpu().get_time();
+            if now > window.start.saturating_add(WINDOW_START_TOLERANCE) {
+                Runtime::report_timing(RuntimeTimingEvent::WindowStartMissed {
+                    entry,
+                    expected: window.start,
+                    actual: now,
+                });
+            }
 
-        while let Some(entry) = Runtime::with_current(|rt| rt.sched_table_next()) {
             trace!("switch into inspector {:?}", entry.inspector_id);
 
             Runtime::with_current(|rt| {
                 rt.set_current_inspector(Some(entry.inspector_id));
             });
 
-            Inspector::run(runtime_switch_ctx);
+            let mut consumed = Duration::ZERO;
+            loop {
+                let run_start = hal!().cpu().get_time();
+                Inspector::run(runtime_switch_ctx);
+                consumed += hal!().cpu().get_time() - run_start;
+
+                if hal!().cpu().get_time() >= window.end
+                    || Runtime::with_current(|rt| rt.scheduler.read().sched_table.is_none())
+                {
+                    break;
+                }
+                Runtime::wait_int();
+            }
 
             Runtime::with_current(|rt| {
                 rt.set_current_inspector(None);
             });
 
             trace!("switch from inspector {:?}", entry.inspector_id);
+
+            let end = hal!().cpu().get_time();
+            Runtime::with_current(|rt| rt.account_window(&entry, &window, now..end, consumed));
+        }
+    }
+
+    /// Runs the background inspector of the sched table (if any) until `until`, idling when it
+    /// has nothing to run.
+    fn run_in_gap(runtime_switch_ctx: VirtAddr, until: Duration) {
+        let gap_start = hal!().cpu().get_time();
+        if gap_start >= until {
+            return;
+        }
+
+        let background = Runtime::with_current(|rt| {
+            rt.scheduler
+                .read()
+                .sched_table
+                .as_ref()
+                .and_then(|table| table.background())
+        });
+        let mut background_time = Duration::ZERO;
+
+        while hal!().cpu().get_time() < until {
+            if let Some(background) = background {
+                trace!("switch into background inspector {:?}", background);
+
+                let run_start = hal!().cpu().get_time();
+                Runtime::with_current(|rt| rt.set_current_inspector(Some(background)));
+                Inspector::run(runtime_switch_ctx);
+                Runtime::with_current(|rt| rt.set_current_inspector(None));
+                background_time += hal!().cpu().get_time() - run_start;
+
+                trace!("switch from background inspector {:?}", background);
+
+                if hal!().cpu().get_time() >= until {
+                    break;
+                }
+            }
+            Runtime::wait_int();
+        }
+
+        let gap = until - gap_start;
+        Runtime::with_current(|rt| {
+            let mut stats = rt.gap_stats.lock();
+            stats.background += background_time;
+            stats.idle += gap.saturating_sub(background_time);
+        });
+    }
+
+    fn account_window(
+        &self,
+        entry: &RuntimeSchedTableEntry,
+        window: &Range<Duration>,
+        actual: Range<Duration>,
+        consumed: Duration,
+    ) {
+        let jitter = actual.start.saturating_sub(window.start);
+        let overrun = actual.end.saturating_sub(window.end);
+
+        let mut window_stats = self.window_stats.lock();
+        let stats = window_stats
+            .entry((entry.inspector_id, entry.offset))
+            .or_default();
+        stats.activations += 1;
+        stats.last_start = actual.start;
+        stats.last_end = actual.end;
+        stats.last_consumed = consumed;
+        stats.total_consumed += consumed;
+        stats.max_jitter = stats.max_jitter.max(jitter);
+        stats.max_overrun = stats.max_overrun.max(overrun);
+        if !overrun.is_zero() {
+            stats.overruns += 1;
+        }
+
+        let threshold = *WINDOW_THRESHOLD.lock();
+        if jitter > threshold || overrun > threshold {
+            warn!(
+                "window of inspector {:?} at offset {:?} on cpu#{}: started {:?} late, overran by {:?}, consumed {:?}",
+                entry.inspector_id,
+                entry.offset,
+                hal!().cpu().id(),
+                jitter,
+                overrun,
+                consumed
+            );
         }
     }
 
@@ -321,6 +654,17 @@ impl Runtime {
         }
     }
 
+    /// Waits for an interrupt and handles it.
+    ///
+    /// `wfi` is issued with interrupts disabled, so an interrupt arriving just before it cannot
+    /// be missed.
+    fn wait_int() {
+        hal!().interrupt().with_saved_off(|| {
+            hal!().interrupt().wait();
+            hal!().interrupt().with_saved_on(|| {});
+        });
+    }
+
     fn halt_if_all_finished_or_ipi() {
         let status = MutexGroup::new(RUNTIME.iter().map(|rt| &rt.status));
         let guards = status.lock();
@@ -355,6 +699,26 @@ impl Runtime {
     }
 }
 
+impl RuntimeInspectorScheduler {
+    fn switch_sched_table_if_due(&mut self) {
+        let Some(table) = self.sched_table.as_ref() else {
+            return;
+        };
+        if !self
+            .pending_sched_table
+            .as_ref()
+            .is_some_and(|(_, switch_at)| table.at_frame_start() && table.get_datum() >= *switch_at)
+        {
+            return;
+        }
+
+        let (sched_table, _) = self.pending_sched_table.take().unwrap();
+        sched_table.set_datum(table.get_datum());
+        self.queue.retain(|&id| !sched_table.contains(id));
+        self.sched_table = Some(sched_table);
+    }
+}
+
 impl RuntimeSchedTable {
     pub fn new(
         frame_size: Duration,
@@ -365,6 +729,7 @@ impl RuntimeSchedTable {
         let sched_table = Self {
             frame_size,
             table,
+            background: None,
             next: AtomicUsize::new(0),
             datum: Mutex::default(),
             events: Mutex::default(),
@@ -375,37 +740,72 @@ impl RuntimeSchedTable {
         Ok(sched_table)
     }
 
-    pub fn start(&self) {
-        *self.datum.lock() = hal!().cpu().get_time();
+    /// Sets the background inspector, which runs in the gaps between the windows.
+    pub fn set_background(&mut self, background: Option<InspectorId>) {
+        self.background = background;
+    }
+
+    pub fn background(&self) -> Option<InspectorId> {
+        self.background
+    }
+
+    /// Creates a sched table with the same entries, which has not been started yet.
+    pub fn renew(&self) -> Self {
+        Self {
+            frame_size: self.frame_size,
+            table: self.table.clone(),
+            background: self.background,
+            next: AtomicUsize::new(0),
+            datum: Mutex::default(),
+            events: Mutex::default(),
+        }
+    }
+
+    /// Starts the sched table at the module-wide datum, or at the earliest major frame start
+    /// aligned to it if the datum has passed, which is returned as a late join.
+    ///
+    /// Starts at the current time if there is no module-wide datum.
+    pub fn start(&self) -> Option<RuntimeTimingEvent> {
+        let now = hal!().cpu().get_time();
+        let (datum, late_join) = match SYNC_DATUM.get() {
+            Some(&datum) if datum < now && self.frame_size != Duration::MAX => {
+                let frame_size = self.frame_size.as_nanos();
+                let elapsed = (now - datum).as_nanos();
+                let aligned = datum.as_nanos() + elapsed.div_ceil(frame_size) * frame_size;
+                (
+                    Duration::from_nanos(aligned as u64),
+                    Some(RuntimeTimingEvent::LateJoin { datum, joined: now }),
+                )
+            }
+            Some(&datum) => (datum.max(now), None),
+            None => (now, None),
+        };
+        *self.datum.lock() = datum;
+        late_join
     }
 
-    pub(crate) fn sched_next(&self) -> RuntimeSchedTableEntry {
+    pub(crate) fn sched_next(&self) -> (RuntimeSchedTableEntry, Range<Duration>) {
         self.events.lock().retain(|event| !event.retired());
 
         let next = self.table[self
             .next
             .fetch_add(1, core::sync::atomic::Ordering::Relaxed)];
 
-        if hal!().cpu().get_time() < self.get_datum() + next.offset {
+        let start = self.get_datum() + next.offset;
+        let end = start.saturating_add(next.duration);
+
+        // the background inspector running in the gap is preempted at the window start
+        if hal!().cpu().get_time() < start {
             self.events.lock().push_back(TimedEvent::create(
-                self.get_datum() + next.offset,
-                TimedEventHandler::new(|| {}, || {}),
+                start,
+                TimedEventHandler::new(preempt_current, || {}),
             ));
-            hal!().interrupt().wait();
         }
 
         if next.duration != Duration::MAX {
             self.events.lock().push_back(TimedEvent::create(
-                self.get_datum() + next.offset + next.duration,
-                TimedEventHandler::new(
-                    || {
-                        Inspector::with_current(|is| is.mark_pending().unwrap()).unwrap();
-                        hal!().interrupt().with_saved_on(|| {
-                            Runtime::switch_yield();
-                        });
-                    },
-                    || {},
-                ),
+                end,
+                TimedEventHandler::new(preempt_current, || {}),
             ));
         }
 
@@ -416,7 +816,52 @@ impl RuntimeSchedTable {
             }
         }
 
-        next
+        (next, start..end)
+    }
+
+    fn next_period_start(&self, id: InspectorId, time: Duration) -> Option<Duration> {
+        let entry = self.table.iter().find(|entry| entry.inspector_id == id)?;
+        let start = self.get_datum() + entry.offset;
+
+        if entry.period == Duration::MAX || entry.period.is_zero() {
+            return Some(start.max(time));
+        }
+
+        let period = entry.period.as_nanos();
+        let start = start.as_nanos();
+        let time = time.as_nanos();
+        let start = if start >= time {
+            start - (start - time) / period * period
+        } else {
+            start + (time - start).div_ceil(period) * period
+        };
+
+        Some(Duration::from_nanos(start as u64))
+    }
+
+    fn next_frame_start(&self, time: Duration) -> Option<Duration> {
+        if self.frame_size == Duration::MAX {
+            return None;
+        }
+
+        let datum = self.get_datum();
+        if datum >= time {
+            return Some(datum);
+        }
+
+        let frame_size = self.frame_size.as_nanos();
+        let elapsed = (time - datum).as_nanos();
+        let start = datum.as_nanos() + elapsed.div_ceil(frame_size) * frame_size;
+
+        Some(Duration::from_nanos(start as u64))
+    }
+
+    fn contains(&self, id: InspectorId) -> bool {
+        self.background == Some(id) || self.table.iter().any(|entry| entry.inspector_id == id)
+    }
+
+    fn at_frame_start(&self) -> bool {
+        self.next.load(core::sync::atomic::Ordering::Relaxed) == 0
     }
 
     fn get_datum(&self) -> Duration {
@@ -445,30 +890,24 @@ impl RuntimeSchedTable {
         table.sort_unstable_by_key(|entry| (entry.inspector_id, entry.offset));
 
         for entries in table.chunk_by(|e1, e2| e1.inspector_id == e2.inspector_id) {
-            if !match entries {
-                [head, tail @ ..] => tail
-                    .iter()
-                    .all(|e| e.period == head.period && e.duration == head.duration),
-                [] => false,
-            } {
+            let period = entries[0].period;
+            if entries.iter().any(|e| e.period != period) {
                 return false;
             }
-        }
+            if period.is_zero() || period > self.frame_size {
+                return false;
+            }
+            if period == self.frame_size {
+                continue;
+            }
 
-        for entries in table.chunk_by(|e1, e2| e1.inspector_id == e2.inspector_id) {
-            if !entries
-                .iter()
-                .chain(entries.iter().take(1))
-                .map_windows(|&[e1, e2]| {
-                    let actual_period = if e2.offset <= e1.offset {
-                        e2.offset + self.frame_size - e1.offset
-                    } else {
-                        e2.offset - e1.offset
-                    };
-                    actual_period == e1.period
-                })
-                .all(|eq| eq)
-            {
+            // the windows in a period recur in every other period of the frame
+            if !entries.iter().all(|e1| {
+                let offset = (e1.offset + period).as_nanos() % self.frame_size.as_nanos();
+                entries
+                    .iter()
+                    .any(|e2| e2.offset.as_nanos() == offset && e2.duration == e1.duration)
+            }) {
                 return false;
             }
         }
@@ -477,10 +916,21 @@ impl RuntimeSchedTable {
     }
 }
 
+fn preempt_current() {
+    let running = Inspector::with_current(|is| {
+        matches!(is.status(), InspectorStatus::Running(_)) && is.mark_pending().is_ok()
+    });
+    if running.unwrap_or(false) {
+        hal!().interrupt().with_saved_on(|| {
+            Runtime::switch_yield();
+        });
+    }
+}
+
 impl Drop for RuntimeSchedTable {
     fn drop(&mut self) {
         hal!().interrupt().with_saved_off(|| {
-            warn!("drop runtime sched table");
+            debug!("drop runtime sched table");
             for event in self.events.lock().iter() {
                 if let Err(err) = event.cancel() {
                     warn!("failed to cancel timed event: {:?}", err);
@@ -0,0 +1,31 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, ItemFn};
+
+/// Registers an `extern "C" fn()` as the entry of kernel partitions, which is found by its
+/// name through `jrinx_partdef::find`.
+#[proc_macro_attribute]
+pub fn partdef(_: TokenStream, func: TokenStream) -> TokenStream {
+    let func = parse_macro_input!(func as ItemFn);
+    let func_attrs = &func.attrs;
+    let func_vis = &func.vis;
+    let func_sig = &func.sig;
+    let func_name = &func.sig.ident;
+    let func_block = &func.block;
+
+    let caller = quote! {
+        #(#func_attrs)*
+        #func_vis #func_sig {
+            #[used(linker)]
+            #[link_section = concat!(".part.", module_path!())]
+            static __PART_DEF: &jrinx_partdef::PartDef = &jrinx_partdef::PartDef::new(
+                stringify!(#func_name),
+                #func_name,
+            );
+
+            #func_block
+        }
+    };
+
+    caller.into()
+}
@@ -0,0 +1,29 @@
+#![no_std]
+
+pub use jrinx_partdef_macro::*;
+
+#[repr(C)]
+pub struct PartDef {
+    name: &'static str,
+    entry: extern "C" fn(),
+}
+
+impl PartDef {
+    pub const fn new(name: &'static str, entry: extern "C" fn()) -> Self {
+        Self { name, entry }
+    }
+}
+
+pub fn all() -> impl Iterator<Item = &'static str> {
+    partdef_iter().map(|part_def| part_def.name)
+}
+
+pub fn find(name: &str) -> Option<extern "C" fn()> {
+    partdef_iter().find_map(|part_def| (part_def.name == name).then_some(part_def.entry))
+}
+
+fn partdef_iter() -> impl Iterator<Item = &'static PartDef> {
+    (jrinx_layout::_spart()..jrinx_layout::_epart())
+        .step_by(core::mem::size_of::<&PartDef>())
+        .map(|a| unsafe { *(a as *const &PartDef) })
+}
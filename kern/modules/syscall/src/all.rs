@@ -7,8 +7,18 @@ use jrinx_apex::*;
 use jrinx_error::{InternalError, Result};
 use jrinx_hal::{Hal, HaltReason};
 
+use crate::blackboard::BlackboardSyscallHandler;
+use crate::buffer::BufferSyscallHandler;
+use crate::event::EventSyscallHandler;
+use crate::health::HealthSyscallHandler;
+use crate::mutex::MutexSyscallHandler;
 use crate::partition::PartitionSyscallHandler;
 use crate::process::ProcessSyscallHandler;
+use crate::queuing::QueuingPortSyscallHandler;
+use crate::sampling::SamplingPortSyscallHandler;
+use crate::schedule::ScheduleSyscallHandler;
+use crate::semaphore::SemaphoreSyscallHandler;
+use crate::time::TimeSyscallHandler;
 
 pub async fn handle(sysno: usize, args: [usize; 7]) -> Result<usize> {
     let ret: core::result::Result<(), ApexReturnCode> = match sysno {
@@ -36,10 +46,288 @@ pub async fn handle(sysno: usize, args: [usize; 7]) -> Result<usize> {
             let result: &mut ApexProcessId = uptr_try_cast(args[1])?;
             ProcessSyscallHandler.create(attr).map(|id| *result = id)
         }
+        SYS_SET_PRIORITY => ProcessSyscallHandler.set_priority(args[0] as _, args[1] as _),
+        SYS_SUSPEND_SELF => ProcessSyscallHandler.suspend_self(uarg_as_time(args[0])),
+        SYS_SUSPEND => ProcessSyscallHandler.suspend(args[0] as _),
+        SYS_RESUME => ProcessSyscallHandler.resume(args[0] as _),
+        SYS_STOP_SELF => ProcessSyscallHandler.stop_self(),
+        SYS_STOP => ProcessSyscallHandler.stop(args[0] as _),
         SYS_START => ProcessSyscallHandler.start(args[0] as _),
+        SYS_DELAYED_START => {
+            ProcessSyscallHandler.delayed_start(args[0] as _, uarg_as_time(args[1]))
+        }
+        SYS_LOCK_PREEMPTION => {
+            let result: &mut ApexLockLevel = uptr_try_cast(args[0])?;
+            ProcessSyscallHandler
+                .lock_preemption()
+                .map(|lock_level| *result = lock_level)
+        }
+        SYS_UNLOCK_PREEMPTION => {
+            let result: &mut ApexLockLevel = uptr_try_cast(args[0])?;
+            ProcessSyscallHandler
+                .unlock_preemption()
+                .map(|lock_level| *result = lock_level)
+        }
         SYS_INITIALIZE_PROCESS_CORE_AFFINITY => {
             ProcessSyscallHandler.initialize_process_core_affinity(args[0] as _, args[1] as _)
         }
+        SYS_TIMED_WAIT => TimeSyscallHandler.timed_wait(uarg_as_time(args[0])),
+        SYS_PERIODIC_WAIT => TimeSyscallHandler.periodic_wait(),
+        SYS_GET_TIME => {
+            let result: &mut ApexSystemTime = uptr_try_cast(args[0])?;
+            TimeSyscallHandler.get_time().map(|time| *result = time)
+        }
+        SYS_REPLENISH => TimeSyscallHandler.replenish(uarg_as_time(args[0])),
+        SYS_CREATE_SAMPLING_PORT => {
+            let name: &ApexSamplingPortName = uptr_try_cast(args[0])?;
+            let result: &mut ApexSamplingPortId = uptr_try_cast(args[4])?;
+            SamplingPortSyscallHandler
+                .create(name, args[1] as _, args[2], uarg_as_time(args[3]))
+                .map(|id| *result = id)
+        }
+        SYS_WRITE_SAMPLING_MESSAGE => {
+            let message: &[ApexByte] = uptr_try_cast_array(args[1], args[2])?;
+            SamplingPortSyscallHandler.write(args[0] as _, message)
+        }
+        SYS_READ_SAMPLING_MESSAGE => {
+            let length: &mut ApexMessageSize = uptr_try_cast(args[2])?;
+            let validity: &mut ApexValidity = uptr_try_cast(args[3])?;
+            match SamplingPortSyscallHandler.read(args[0] as _) {
+                Ok((message, _)) if message.len() > *length as usize => {
+                    (*length, *validity) = (0, ApexValidity::Invalid);
+                    Err(ApexReturnCode::InvalidParam)
+                }
+                Ok((message, message_validity)) => {
+                    uptr_try_cast_array(args[1], message.len())?.copy_from_slice(&message);
+                    (*length, *validity) = (message.len() as _, message_validity);
+                    Ok(())
+                }
+                Err(err) => {
+                    (*length, *validity) = (0, ApexValidity::Invalid);
+                    Err(err)
+                }
+            }
+        }
+        SYS_GET_SAMPLING_PORT_ID => {
+            let name: &ApexSamplingPortName = uptr_try_cast(args[0])?;
+            let result: &mut ApexSamplingPortId = uptr_try_cast(args[1])?;
+            SamplingPortSyscallHandler
+                .get_id(name)
+                .map(|id| *result = id)
+        }
+        SYS_GET_SAMPLING_PORT_STATUS => {
+            let result: &mut ApexSamplingPortStatus = uptr_try_cast(args[1])?;
+            SamplingPortSyscallHandler
+                .get_status(args[0] as _)
+                .map(|status| *result = status)
+        }
+        SYS_CREATE_QUEUING_PORT => {
+            let name: &ApexQueuingPortName = uptr_try_cast(args[0])?;
+            let result: &mut ApexQueuingPortId = uptr_try_cast(args[5])?;
+            QueuingPortSyscallHandler
+                .create(name, args[1] as _, args[2] as _, args[3], args[4])
+                .map(|id| *result = id)
+        }
+        SYS_SEND_QUEUING_MESSAGE => {
+            let message: &[ApexByte] = uptr_try_cast_array(args[1], args[2])?;
+            QueuingPortSyscallHandler.send(args[0] as _, message, uarg_as_time(args[3]))
+        }
+        SYS_RECEIVE_QUEUING_MESSAGE => {
+            let length: &mut ApexMessageSize = uptr_try_cast(args[3])?;
+            let message: &mut [ApexByte] = uptr_try_cast_array(args[2], *length as _)?;
+            let ret =
+                QueuingPortSyscallHandler.receive(args[0] as _, uarg_as_time(args[1]), message);
+            *length = ret.unwrap_or(0);
+            ret.map(|_| ())
+        }
+        SYS_GET_QUEUING_PORT_ID => {
+            let name: &ApexQueuingPortName = uptr_try_cast(args[0])?;
+            let result: &mut ApexQueuingPortId = uptr_try_cast(args[1])?;
+            QueuingPortSyscallHandler
+                .get_id(name)
+                .map(|id| *result = id)
+        }
+        SYS_GET_QUEUING_PORT_STATUS => {
+            let result: &mut ApexQueuingPortStatus = uptr_try_cast(args[1])?;
+            QueuingPortSyscallHandler
+                .get_status(args[0] as _)
+                .map(|status| *result = status)
+        }
+        SYS_CLEAR_QUEUING_PORT => QueuingPortSyscallHandler.clear(args[0] as _),
+        SYS_CREATE_BUFFER => {
+            let name: &ApexBufferName = uptr_try_cast(args[0])?;
+            let result: &mut ApexBufferId = uptr_try_cast(args[4])?;
+            BufferSyscallHandler
+                .create(name, args[1] as _, args[2] as _, args[3])
+                .map(|id| *result = id)
+        }
+        SYS_SEND_BUFFER => {
+            let message: &[ApexByte] = uptr_try_cast_array(args[1], args[2])?;
+            BufferSyscallHandler.send(args[0] as _, message, uarg_as_time(args[3]))
+        }
+        SYS_RECEIVE_BUFFER => {
+            let length: &mut ApexMessageSize = uptr_try_cast(args[3])?;
+            let message: &mut [ApexByte] = uptr_try_cast_array(args[2], *length as _)?;
+            let ret = BufferSyscallHandler.receive(args[0] as _, uarg_as_time(args[1]), message);
+            *length = ret.unwrap_or(0);
+            ret.map(|_| ())
+        }
+        SYS_GET_BUFFER_ID => {
+            let name: &ApexBufferName = uptr_try_cast(args[0])?;
+            let result: &mut ApexBufferId = uptr_try_cast(args[1])?;
+            BufferSyscallHandler.get_id(name).map(|id| *result = id)
+        }
+        SYS_GET_BUFFER_STATUS => {
+            let result: &mut ApexBufferStatus = uptr_try_cast(args[1])?;
+            BufferSyscallHandler
+                .get_status(args[0] as _)
+                .map(|status| *result = status)
+        }
+        SYS_CREATE_BLACKBOARD => {
+            let name: &ApexBlackboardName = uptr_try_cast(args[0])?;
+            let result: &mut ApexBlackboardId = uptr_try_cast(args[2])?;
+            BlackboardSyscallHandler
+                .create(name, args[1] as _)
+                .map(|id| *result = id)
+        }
+        SYS_DISPLAY_BLACKBOARD => {
+            let message: &[ApexByte] = uptr_try_cast_array(args[1], args[2])?;
+            BlackboardSyscallHandler.display(args[0] as _, message)
+        }
+        SYS_READ_BLACKBOARD => {
+            let length: &mut ApexMessageSize = uptr_try_cast(args[3])?;
+            let message: &mut [ApexByte] = uptr_try_cast_array(args[2], *length as _)?;
+            let ret = BlackboardSyscallHandler.read(args[0] as _, uarg_as_time(args[1]), message);
+            *length = ret.unwrap_or(0);
+            ret.map(|_| ())
+        }
+        SYS_CLEAR_BLACKBOARD => BlackboardSyscallHandler.clear(args[0] as _),
+        SYS_GET_BLACKBOARD_ID => {
+            let name: &ApexBlackboardName = uptr_try_cast(args[0])?;
+            let result: &mut ApexBlackboardId = uptr_try_cast(args[1])?;
+            BlackboardSyscallHandler.get_id(name).map(|id| *result = id)
+        }
+        SYS_GET_BLACKBOARD_STATUS => {
+            let result: &mut ApexBlackboardStatus = uptr_try_cast(args[1])?;
+            BlackboardSyscallHandler
+                .get_status(args[0] as _)
+                .map(|status| *result = status)
+        }
+        SYS_CREATE_SEMAPHORE => {
+            let name: &ApexSemaphoreName = uptr_try_cast(args[0])?;
+            let result: &mut ApexSemaphoreId = uptr_try_cast(args[4])?;
+            SemaphoreSyscallHandler
+                .create(name, args[1] as _, args[2] as _, args[3])
+                .map(|id| *result = id)
+        }
+        SYS_WAIT_SEMAPHORE => SemaphoreSyscallHandler.wait(args[0] as _, uarg_as_time(args[1])),
+        SYS_SIGNAL_SEMAPHORE => SemaphoreSyscallHandler.signal(args[0] as _),
+        SYS_GET_SEMAPHORE_ID => {
+            let name: &ApexSemaphoreName = uptr_try_cast(args[0])?;
+            let result: &mut ApexSemaphoreId = uptr_try_cast(args[1])?;
+            SemaphoreSyscallHandler.get_id(name).map(|id| *result = id)
+        }
+        SYS_GET_SEMAPHORE_STATUS => {
+            let result: &mut ApexSemaphoreStatus = uptr_try_cast(args[1])?;
+            SemaphoreSyscallHandler
+                .get_status(args[0] as _)
+                .map(|status| *result = status)
+        }
+        SYS_CREATE_EVENT => {
+            let name: &ApexEventName = uptr_try_cast(args[0])?;
+            let result: &mut ApexEventId = uptr_try_cast(args[1])?;
+            EventSyscallHandler.create(name).map(|id| *result = id)
+        }
+        SYS_SET_EVENT => EventSyscallHandler.set(args[0] as _),
+        SYS_RESET_EVENT => EventSyscallHandler.reset(args[0] as _),
+        SYS_WAIT_EVENT => EventSyscallHandler.wait(args[0] as _, uarg_as_time(args[1])),
+        SYS_GET_EVENT_ID => {
+            let name: &ApexEventName = uptr_try_cast(args[0])?;
+            let result: &mut ApexEventId = uptr_try_cast(args[1])?;
+            EventSyscallHandler.get_id(name).map(|id| *result = id)
+        }
+        SYS_GET_EVENT_STATUS => {
+            let result: &mut ApexEventStatus = uptr_try_cast(args[1])?;
+            EventSyscallHandler
+                .get_status(args[0] as _)
+                .map(|status| *result = status)
+        }
+        SYS_CREATE_MUTEX => {
+            let name: &ApexMutexName = uptr_try_cast(args[0])?;
+            let result: &mut ApexMutexId = uptr_try_cast(args[3])?;
+            MutexSyscallHandler
+                .create(name, args[1] as _, args[2])
+                .map(|id| *result = id)
+        }
+        SYS_ACQUIRE_MUTEX => MutexSyscallHandler.acquire(args[0] as _, uarg_as_time(args[1])),
+        SYS_RELEASE_MUTEX => MutexSyscallHandler.release(args[0] as _),
+        SYS_RESET_MUTEX => MutexSyscallHandler.reset(args[0] as _, args[1] as _),
+        SYS_GET_MUTEX_ID => {
+            let name: &ApexMutexName = uptr_try_cast(args[0])?;
+            let result: &mut ApexMutexId = uptr_try_cast(args[1])?;
+            MutexSyscallHandler.get_id(name).map(|id| *result = id)
+        }
+        SYS_GET_MUTEX_STATUS => {
+            let result: &mut ApexMutexStatus = uptr_try_cast(args[1])?;
+            MutexSyscallHandler
+                .get_status(args[0] as _)
+                .map(|status| *result = status)
+        }
+        SYS_GET_PROCESS_MUTEX_STATE => {
+            let result: &mut ApexMutexId = uptr_try_cast(args[1])?;
+            MutexSyscallHandler
+                .get_process_mutex_state(args[0] as _)
+                .map(|id| *result = id)
+        }
+        SYS_REPORT_APPLICATION_MESSAGE => {
+            let message: &[ApexByte] = uptr_try_cast_array(args[0], args[1])?;
+            HealthSyscallHandler.report_application_message(message)
+        }
+        SYS_CREATE_ERROR_HANDLER => {
+            HealthSyscallHandler.create_error_handler(args[0], args[1] as _)
+        }
+        SYS_GET_ERROR_STATUS => {
+            let result: &mut ApexErrorStatus = uptr_try_cast(args[0])?;
+            HealthSyscallHandler
+                .get_error_status()
+                .map(|status| *result = status)
+        }
+        SYS_RAISE_APPLICATION_ERROR => {
+            let message: &[ApexByte] = uptr_try_cast_array(args[1], args[2])?;
+            HealthSyscallHandler.raise_application_error(args[0], message)
+        }
+        SYS_SET_MODULE_SCHEDULE => ScheduleSyscallHandler.set_module_schedule(args[0] as _),
+        SYS_GET_MODULE_SCHEDULE_STATUS => {
+            let result: &mut ApexScheduleStatus = uptr_try_cast(args[0])?;
+            ScheduleSyscallHandler
+                .get_module_schedule_status()
+                .map(|status| *result = status)
+        }
+        SYS_GET_MODULE_SCHEDULE_ID => {
+            let name: &ApexScheduleName = uptr_try_cast(args[0])?;
+            let result: &mut ApexScheduleId = uptr_try_cast(args[1])?;
+            ScheduleSyscallHandler
+                .get_module_schedule_id(name)
+                .map(|id| *result = id)
+        }
+        SYS_GET_WINDOW_STATUS => {
+            let result: &mut ApexWindowStatus = uptr_try_cast(args[2])?;
+            ScheduleSyscallHandler
+                .get_window_status(args[0] as _, args[1] as _)
+                .map(|status| *result = status)
+        }
+        SYS_GET_TIMING_EVENT => {
+            let result: &mut ApexTimingEvent = uptr_try_cast(args[1])?;
+            ScheduleSyscallHandler
+                .get_timing_event(args[0] as _)
+                .map(|event| *result = event)
+        }
+        SYS_GET_GAP_STATUS => {
+            let result: &mut ApexGapStatus = uptr_try_cast(args[1])?;
+            ScheduleSyscallHandler
+                .get_gap_status(args[0] as _)
+                .map(|status| *result = status)
+        }
         SYS_DEBUG_LOG => {
             let len: usize = args[1];
             let msg: &[u8] = uptr_try_cast_array(args[0], len)?;
@@ -65,6 +353,11 @@ pub async fn handle(sysno: usize, args: [usize; 7]) -> Result<usize> {
     })
 }
 
+/// Sign-extends a time argument, so that [`APEX_TIME_INFINITY`] survives on 32-bit targets.
+fn uarg_as_time(arg: usize) -> ApexSystemTime {
+    arg as isize as _
+}
+
 fn uptr_try_cast<'a, T>(ptr: usize) -> Result<&'a mut T> {
     if ptr >= usize::MAX / 2 || ptr + core::mem::size_of::<T>() > usize::MAX / 2 {
         return Err(InternalError::InvalidVirtAddr);
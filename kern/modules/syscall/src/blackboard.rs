@@ -0,0 +1,111 @@
+use jrinx_a653::{
+    blackboard::{Blackboard, BlackboardConfig},
+    partition::Partition,
+};
+use jrinx_apex::*;
+
+pub(crate) struct BlackboardSyscallHandler;
+
+impl BlackboardSyscallHandler {
+    pub(crate) fn create(
+        &self,
+        name: &ApexBlackboardName,
+        max_message_size: ApexMessageSize,
+    ) -> Result<ApexBlackboardId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+
+        if Blackboard::find_by_name(partition.identifier(), name).is_some() {
+            return Err(ApexReturnCode::NoAction);
+        }
+        if max_message_size == 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if partition.operating_mode() == ApexOperatingMode::Normal {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let blackboard = Blackboard::new(
+            partition.identifier(),
+            &BlackboardConfig {
+                name: *name,
+                max_message_size,
+            },
+        )
+        .map_err(|_| ApexReturnCode::InvalidConfig)?;
+
+        Ok(blackboard.identifier().into())
+    }
+
+    pub(crate) fn display(
+        &self,
+        id: ApexBlackboardId,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let blackboard = Blackboard::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if message.len() > blackboard.max_message_size() as usize {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if message.is_empty() {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        blackboard.display(message);
+
+        Ok(())
+    }
+
+    pub(crate) fn read(
+        &self,
+        id: ApexBlackboardId,
+        timeout: ApexSystemTime,
+        message: &mut [ApexByte],
+    ) -> Result<ApexMessageSize, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let blackboard = Blackboard::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if timeout != APEX_TIME_INFINITY && timeout < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if message.len() < blackboard.max_message_size() as usize {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        blackboard.read(message, timeout)
+    }
+
+    pub(crate) fn clear(&self, id: ApexBlackboardId) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let blackboard = Blackboard::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        blackboard.clear();
+
+        Ok(())
+    }
+
+    pub(crate) fn get_id(
+        &self,
+        name: &ApexBlackboardName,
+    ) -> Result<ApexBlackboardId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let blackboard = Blackboard::find_by_name(partition.identifier(), name)
+            .ok_or(ApexReturnCode::InvalidConfig)?;
+
+        Ok(blackboard.identifier().into())
+    }
+
+    pub(crate) fn get_status(
+        &self,
+        id: ApexBlackboardId,
+    ) -> Result<ApexBlackboardStatus, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let blackboard = Blackboard::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        Ok(blackboard.status())
+    }
+}
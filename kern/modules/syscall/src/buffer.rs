@@ -0,0 +1,107 @@
+use jrinx_a653::{
+    buffer::{Buffer, BufferConfig},
+    partition::Partition,
+};
+use jrinx_apex::*;
+
+pub(crate) struct BufferSyscallHandler;
+
+impl BufferSyscallHandler {
+    pub(crate) fn create(
+        &self,
+        name: &ApexBufferName,
+        max_message_size: ApexMessageSize,
+        max_nb_message: ApexMessageRange,
+        discipline: usize,
+    ) -> Result<ApexBufferId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+
+        if Buffer::find_by_name(partition.identifier(), name).is_some() {
+            return Err(ApexReturnCode::NoAction);
+        }
+        if max_message_size == 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if max_nb_message == 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        let discipline: ApexQueueDiscipline = (discipline as u32)
+            .try_into()
+            .map_err(|_| ApexReturnCode::InvalidParam)?;
+        if partition.operating_mode() == ApexOperatingMode::Normal {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let buffer = Buffer::new(
+            partition.identifier(),
+            &BufferConfig {
+                name: *name,
+                max_message_size,
+                max_nb_message,
+                discipline,
+            },
+        )
+        .map_err(|_| ApexReturnCode::InvalidConfig)?;
+
+        Ok(buffer.identifier().into())
+    }
+
+    pub(crate) fn send(
+        &self,
+        id: ApexBufferId,
+        message: &[ApexByte],
+        timeout: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let buffer = Buffer::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if message.len() > buffer.max_message_size() as usize {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if message.is_empty() {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if timeout != APEX_TIME_INFINITY && timeout < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        buffer.send(message, timeout)
+    }
+
+    pub(crate) fn receive(
+        &self,
+        id: ApexBufferId,
+        timeout: ApexSystemTime,
+        message: &mut [ApexByte],
+    ) -> Result<ApexMessageSize, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let buffer = Buffer::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if timeout != APEX_TIME_INFINITY && timeout < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if message.len() < buffer.max_message_size() as usize {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        buffer.receive(message, timeout)
+    }
+
+    pub(crate) fn get_id(&self, name: &ApexBufferName) -> Result<ApexBufferId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let buffer = Buffer::find_by_name(partition.identifier(), name)
+            .ok_or(ApexReturnCode::InvalidConfig)?;
+
+        Ok(buffer.identifier().into())
+    }
+
+    pub(crate) fn get_status(&self, id: ApexBufferId) -> Result<ApexBufferStatus, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let buffer = Buffer::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        Ok(buffer.status())
+    }
+}
@@ -0,0 +1,77 @@
+use jrinx_a653::{
+    event::{Event, EventConfig},
+    partition::Partition,
+};
+use jrinx_apex::*;
+
+pub(crate) struct EventSyscallHandler;
+
+impl EventSyscallHandler {
+    pub(crate) fn create(&self, name: &ApexEventName) -> Result<ApexEventId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+
+        if Event::find_by_name(partition.identifier(), name).is_some() {
+            return Err(ApexReturnCode::NoAction);
+        }
+        if partition.operating_mode() == ApexOperatingMode::Normal {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let event = Event::new(partition.identifier(), &EventConfig { name: *name })
+            .map_err(|_| ApexReturnCode::InvalidConfig)?;
+
+        Ok(event.identifier().into())
+    }
+
+    pub(crate) fn set(&self, id: ApexEventId) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let event = Event::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        event.set();
+
+        Ok(())
+    }
+
+    pub(crate) fn reset(&self, id: ApexEventId) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let event = Event::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        event.reset();
+
+        Ok(())
+    }
+
+    pub(crate) fn wait(
+        &self,
+        id: ApexEventId,
+        timeout: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let event = Event::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if timeout != APEX_TIME_INFINITY && timeout < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        event.wait(timeout)
+    }
+
+    pub(crate) fn get_id(&self, name: &ApexEventName) -> Result<ApexEventId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let event = Event::find_by_name(partition.identifier(), name)
+            .ok_or(ApexReturnCode::InvalidConfig)?;
+
+        Ok(event.identifier().into())
+    }
+
+    pub(crate) fn get_status(&self, id: ApexEventId) -> Result<ApexEventStatus, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let event = Event::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        Ok(event.status())
+    }
+}
@@ -0,0 +1,99 @@
+use alloc::string::String;
+
+use jrinx_a653::{health, partition::Partition, process::Process, A653Entry};
+use jrinx_apex::*;
+use jrinx_multitask::runtime::Runtime;
+
+pub(crate) struct HealthSyscallHandler;
+
+impl HealthSyscallHandler {
+    pub(crate) fn report_application_message(
+        &self,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::current().unwrap();
+
+        if message.len() > APEX_MAX_ERROR_MESSAGE_SIZE {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        log::info!(
+            "{:?}//{:?}: {}",
+            partition.name(),
+            process.name(),
+            String::from_utf8_lossy(message)
+        );
+
+        Ok(())
+    }
+
+    pub(crate) fn create_error_handler(
+        &self,
+        entry_point: usize,
+        stack_size: ApexStackSize,
+    ) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+
+        if partition.error_handler().is_some() {
+            return Err(ApexReturnCode::NoAction);
+        }
+        if stack_size as usize > partition.memory_free() {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if partition.operating_mode() == ApexOperatingMode::Normal {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        Process::new_error_handler(
+            partition.identifier(),
+            if partition.kernel() {
+                A653Entry::Kern(entry_point.into())
+            } else {
+                A653Entry::User(entry_point)
+            },
+            stack_size,
+        )
+        .map_err(|_| ApexReturnCode::InvalidConfig)?;
+
+        Ok(())
+    }
+
+    pub(crate) fn get_error_status(&self) -> Result<ApexErrorStatus, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::current().unwrap();
+
+        if !process.is_error_handler() {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+
+        partition.pop_error().ok_or(ApexReturnCode::NoAction)
+    }
+
+    pub(crate) fn raise_application_error(
+        &self,
+        error_code: usize,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode> {
+        let process = Process::current().unwrap();
+
+        let error_code: ApexErrorCode = (error_code as u32)
+            .try_into()
+            .map_err(|_| ApexReturnCode::InvalidParam)?;
+        if error_code != ApexErrorCode::ApplicationError {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if message.len() > APEX_MAX_ERROR_MESSAGE_SIZE {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        if let Some(handler) = health::report(&process, error_code, message, 0) {
+            handler
+                .start_error_handler()
+                .map_err(|_| ApexReturnCode::InvalidConfig)?;
+            Runtime::switch_yield();
+        }
+
+        Ok(())
+    }
+}
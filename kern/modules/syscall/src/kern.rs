@@ -0,0 +1,575 @@
+use jrinx_a653::process::Process;
+use jrinx_apex::*;
+use jrinx_multitask::{
+    executor::{Executor, ExecutorStatus},
+    inspector::Inspector,
+    runtime::Runtime,
+};
+
+use crate::blackboard::BlackboardSyscallHandler;
+use crate::buffer::BufferSyscallHandler;
+use crate::event::EventSyscallHandler;
+use crate::health::HealthSyscallHandler;
+use crate::mutex::MutexSyscallHandler;
+use crate::partition::PartitionSyscallHandler;
+use crate::process::ProcessSyscallHandler;
+use crate::queuing::QueuingPortSyscallHandler;
+use crate::sampling::SamplingPortSyscallHandler;
+use crate::schedule::ScheduleSyscallHandler;
+use crate::semaphore::SemaphoreSyscallHandler;
+use crate::time::TimeSyscallHandler;
+
+/// APEX services for processes of kernel partitions, which call the syscall handlers directly
+/// instead of trapping.
+pub struct KernApex;
+
+impl KernApex {
+    /// Runs a service for the current process of a kernel partition.
+    ///
+    /// Interrupts only preempt the process outside the services, which take the locks of the
+    /// partition, so it reports its deadline misses and gives up the core after each service if
+    /// it is stopped or should be rescheduled.
+    ///
+    /// A stopped process cannot return through its entry, so its executor finishes here, where
+    /// the services have unwound and only the frames of the entry are dropped without unwinding.
+    fn ret<T>(
+        &self,
+        service: impl FnOnce() -> Result<T, ApexReturnCode>,
+    ) -> Result<T, ApexReturnCode> {
+        if let Some(process) = Process::current() {
+            process.set_preemptible(false);
+        }
+        let ret = service();
+        if let Some(process) = Process::current() {
+            process.handle_deadline_miss();
+        }
+        if Process::current().is_some_and(|process| process.is_stale()) {
+            Executor::with_current(|ex| ex.set_status(ExecutorStatus::Finished)).unwrap();
+            Runtime::switch_yield();
+        }
+        if let Some(process) = Process::current() {
+            process.set_preemptible(true);
+        }
+        if Inspector::with_current(|is| is.take_reschedule()).unwrap_or(false) {
+            Runtime::switch_yield();
+        }
+        ret
+    }
+}
+
+impl ApexPartitionService for KernApex {
+    fn get_partition_status(&self) -> Result<ApexPartitionStatus, ApexReturnCode> {
+        self.ret(|| PartitionSyscallHandler.get_status())
+    }
+
+    fn set_partition_mode(&self, operating_mode: ApexOperatingMode) -> Result<(), ApexReturnCode> {
+        self.ret(|| PartitionSyscallHandler.set_mode(operating_mode as _))
+    }
+}
+
+impl ApexProcessService for KernApex {
+    fn get_process_id(
+        &self,
+        process_name: &ApexProcessName,
+    ) -> Result<ApexProcessId, ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.get_id(process_name))
+    }
+
+    fn get_process_status(
+        &self,
+        process_id: ApexProcessId,
+    ) -> Result<ApexProcessStatus, ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.get_status(process_id))
+    }
+
+    fn create_process(
+        &self,
+        attributes: &ApexProcessAttribute,
+    ) -> Result<ApexProcessId, ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.create(attributes))
+    }
+
+    fn set_priority(
+        &self,
+        process_id: ApexProcessId,
+        priority: ApexPriority,
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.set_priority(process_id, priority))
+    }
+
+    fn suspend_self(&self, time_out: ApexSystemTime) -> Result<(), ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.suspend_self(time_out))
+    }
+
+    fn suspend(&self, process_id: ApexProcessId) -> Result<(), ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.suspend(process_id))
+    }
+
+    fn resume(&self, process_id: ApexProcessId) -> Result<(), ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.resume(process_id))
+    }
+
+    fn stop_self(&self) -> ! {
+        let _ = self.ret(|| ProcessSyscallHandler.stop_self());
+        unreachable!("stopped process is scheduled again");
+    }
+
+    fn stop(&self, process_id: ApexProcessId) -> Result<(), ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.stop(process_id))
+    }
+
+    fn start(&self, process_id: ApexProcessId) -> Result<(), ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.start(process_id))
+    }
+
+    fn delayed_start(
+        &self,
+        process_id: ApexProcessId,
+        delay_time: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.delayed_start(process_id, delay_time))
+    }
+
+    fn lock_preemption(&self) -> Result<ApexLockLevel, ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.lock_preemption())
+    }
+
+    fn unlock_preemption(&self) -> Result<ApexLockLevel, ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.unlock_preemption())
+    }
+
+    fn get_my_id(&self) -> Result<ApexProcessId, ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.get_my_id())
+    }
+
+    fn initialize_process_core_affinity(
+        &self,
+        process_id: ApexProcessId,
+        processor_core_id: ApexProcessorCoreId,
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| {
+            ProcessSyscallHandler.initialize_process_core_affinity(process_id, processor_core_id)
+        })
+    }
+
+    fn get_my_processor_core_id(&self) -> Result<ApexProcessorCoreId, ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.get_my_processor_core_id())
+    }
+
+    fn get_my_index(&self) -> Result<ApexProcessIndex, ApexReturnCode> {
+        self.ret(|| ProcessSyscallHandler.get_my_index())
+    }
+}
+
+impl ApexTimeService for KernApex {
+    fn timed_wait(&self, delay_time: ApexSystemTime) -> Result<(), ApexReturnCode> {
+        self.ret(|| TimeSyscallHandler.timed_wait(delay_time))
+    }
+
+    fn periodic_wait(&self) -> Result<(), ApexReturnCode> {
+        self.ret(|| TimeSyscallHandler.periodic_wait())
+    }
+
+    fn get_time(&self) -> Result<ApexSystemTime, ApexReturnCode> {
+        self.ret(|| TimeSyscallHandler.get_time())
+    }
+
+    fn replenish(&self, budget_time: ApexSystemTime) -> Result<(), ApexReturnCode> {
+        self.ret(|| TimeSyscallHandler.replenish(budget_time))
+    }
+}
+
+impl ApexSamplingPortService for KernApex {
+    fn create_sampling_port(
+        &self,
+        sampling_port_name: &ApexSamplingPortName,
+        max_message_size: ApexMessageSize,
+        port_direction: ApexPortDirection,
+        refresh_period: ApexSystemTime,
+    ) -> Result<ApexSamplingPortId, ApexReturnCode> {
+        self.ret(|| {
+            SamplingPortSyscallHandler.create(
+                sampling_port_name,
+                max_message_size,
+                port_direction as _,
+                refresh_period,
+            )
+        })
+    }
+
+    fn write_sampling_message(
+        &self,
+        sampling_port_id: ApexSamplingPortId,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| SamplingPortSyscallHandler.write(sampling_port_id, message))
+    }
+
+    fn read_sampling_message(
+        &self,
+        sampling_port_id: ApexSamplingPortId,
+        message: &mut [ApexByte],
+    ) -> Result<(ApexMessageSize, ApexValidity), ApexReturnCode> {
+        self.ret(|| {
+            SamplingPortSyscallHandler
+                .read(sampling_port_id)
+                .and_then(|(data, validity)| {
+                    message
+                        .get_mut(..data.len())
+                        .ok_or(ApexReturnCode::InvalidParam)?
+                        .copy_from_slice(&data);
+                    Ok((data.len() as _, validity))
+                })
+        })
+    }
+
+    fn get_sampling_port_id(
+        &self,
+        sampling_port_name: &ApexSamplingPortName,
+    ) -> Result<ApexSamplingPortId, ApexReturnCode> {
+        self.ret(|| SamplingPortSyscallHandler.get_id(sampling_port_name))
+    }
+
+    fn get_sampling_port_status(
+        &self,
+        sampling_port_id: ApexSamplingPortId,
+    ) -> Result<ApexSamplingPortStatus, ApexReturnCode> {
+        self.ret(|| SamplingPortSyscallHandler.get_status(sampling_port_id))
+    }
+}
+
+impl ApexQueuingPortService for KernApex {
+    fn create_queuing_port(
+        &self,
+        queuing_port_name: &ApexQueuingPortName,
+        max_message_size: ApexMessageSize,
+        max_nb_message: ApexMessageRange,
+        port_direction: ApexPortDirection,
+        queuing_discipline: ApexQueueDiscipline,
+    ) -> Result<ApexQueuingPortId, ApexReturnCode> {
+        self.ret(|| {
+            QueuingPortSyscallHandler.create(
+                queuing_port_name,
+                max_message_size,
+                max_nb_message,
+                port_direction as _,
+                queuing_discipline as _,
+            )
+        })
+    }
+
+    fn send_queuing_message(
+        &self,
+        queuing_port_id: ApexQueuingPortId,
+        message: &[ApexByte],
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| QueuingPortSyscallHandler.send(queuing_port_id, message, time_out))
+    }
+
+    fn receive_queuing_message(
+        &self,
+        queuing_port_id: ApexQueuingPortId,
+        time_out: ApexSystemTime,
+        message: &mut [ApexByte],
+    ) -> Result<ApexMessageSize, ApexReturnCode> {
+        self.ret(|| QueuingPortSyscallHandler.receive(queuing_port_id, time_out, message))
+    }
+
+    fn get_queuing_port_id(
+        &self,
+        queuing_port_name: &ApexQueuingPortName,
+    ) -> Result<ApexQueuingPortId, ApexReturnCode> {
+        self.ret(|| QueuingPortSyscallHandler.get_id(queuing_port_name))
+    }
+
+    fn get_queuing_port_status(
+        &self,
+        queuing_port_id: ApexQueuingPortId,
+    ) -> Result<ApexQueuingPortStatus, ApexReturnCode> {
+        self.ret(|| QueuingPortSyscallHandler.get_status(queuing_port_id))
+    }
+
+    fn clear_queuing_port(&self, queuing_port_id: ApexQueuingPortId) -> Result<(), ApexReturnCode> {
+        self.ret(|| QueuingPortSyscallHandler.clear(queuing_port_id))
+    }
+}
+
+impl ApexBufferService for KernApex {
+    fn create_buffer(
+        &self,
+        buffer_name: &ApexBufferName,
+        max_message_size: ApexMessageSize,
+        max_nb_message: ApexMessageRange,
+        queuing_discipline: ApexQueueDiscipline,
+    ) -> Result<ApexBufferId, ApexReturnCode> {
+        self.ret(|| {
+            BufferSyscallHandler.create(
+                buffer_name,
+                max_message_size,
+                max_nb_message,
+                queuing_discipline as _,
+            )
+        })
+    }
+
+    fn send_buffer(
+        &self,
+        buffer_id: ApexBufferId,
+        message: &[ApexByte],
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| BufferSyscallHandler.send(buffer_id, message, time_out))
+    }
+
+    fn receive_buffer(
+        &self,
+        buffer_id: ApexBufferId,
+        time_out: ApexSystemTime,
+        message: &mut [ApexByte],
+    ) -> Result<ApexMessageSize, ApexReturnCode> {
+        self.ret(|| BufferSyscallHandler.receive(buffer_id, time_out, message))
+    }
+
+    fn get_buffer_id(&self, buffer_name: &ApexBufferName) -> Result<ApexBufferId, ApexReturnCode> {
+        self.ret(|| BufferSyscallHandler.get_id(buffer_name))
+    }
+
+    fn get_buffer_status(
+        &self,
+        buffer_id: ApexBufferId,
+    ) -> Result<ApexBufferStatus, ApexReturnCode> {
+        self.ret(|| BufferSyscallHandler.get_status(buffer_id))
+    }
+}
+
+impl ApexBlackboardService for KernApex {
+    fn create_blackboard(
+        &self,
+        blackboard_name: &ApexBlackboardName,
+        max_message_size: ApexMessageSize,
+    ) -> Result<ApexBlackboardId, ApexReturnCode> {
+        self.ret(|| BlackboardSyscallHandler.create(blackboard_name, max_message_size))
+    }
+
+    fn display_blackboard(
+        &self,
+        blackboard_id: ApexBlackboardId,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| BlackboardSyscallHandler.display(blackboard_id, message))
+    }
+
+    fn read_blackboard(
+        &self,
+        blackboard_id: ApexBlackboardId,
+        time_out: ApexSystemTime,
+        message: &mut [ApexByte],
+    ) -> Result<ApexMessageSize, ApexReturnCode> {
+        self.ret(|| BlackboardSyscallHandler.read(blackboard_id, time_out, message))
+    }
+
+    fn clear_blackboard(&self, blackboard_id: ApexBlackboardId) -> Result<(), ApexReturnCode> {
+        self.ret(|| BlackboardSyscallHandler.clear(blackboard_id))
+    }
+
+    fn get_blackboard_id(
+        &self,
+        blackboard_name: &ApexBlackboardName,
+    ) -> Result<ApexBlackboardId, ApexReturnCode> {
+        self.ret(|| BlackboardSyscallHandler.get_id(blackboard_name))
+    }
+
+    fn get_blackboard_status(
+        &self,
+        blackboard_id: ApexBlackboardId,
+    ) -> Result<ApexBlackboardStatus, ApexReturnCode> {
+        self.ret(|| BlackboardSyscallHandler.get_status(blackboard_id))
+    }
+}
+
+impl ApexSemaphoreService for KernApex {
+    fn create_semaphore(
+        &self,
+        semaphore_name: &ApexSemaphoreName,
+        current_value: ApexSemaphoreValue,
+        maximum_value: ApexSemaphoreValue,
+        queuing_discipline: ApexQueueDiscipline,
+    ) -> Result<ApexSemaphoreId, ApexReturnCode> {
+        self.ret(|| {
+            SemaphoreSyscallHandler.create(
+                semaphore_name,
+                current_value,
+                maximum_value,
+                queuing_discipline as _,
+            )
+        })
+    }
+
+    fn wait_semaphore(
+        &self,
+        semaphore_id: ApexSemaphoreId,
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| SemaphoreSyscallHandler.wait(semaphore_id, time_out))
+    }
+
+    fn signal_semaphore(&self, semaphore_id: ApexSemaphoreId) -> Result<(), ApexReturnCode> {
+        self.ret(|| SemaphoreSyscallHandler.signal(semaphore_id))
+    }
+
+    fn get_semaphore_id(
+        &self,
+        semaphore_name: &ApexSemaphoreName,
+    ) -> Result<ApexSemaphoreId, ApexReturnCode> {
+        self.ret(|| SemaphoreSyscallHandler.get_id(semaphore_name))
+    }
+
+    fn get_semaphore_status(
+        &self,
+        semaphore_id: ApexSemaphoreId,
+    ) -> Result<ApexSemaphoreStatus, ApexReturnCode> {
+        self.ret(|| SemaphoreSyscallHandler.get_status(semaphore_id))
+    }
+}
+
+impl ApexEventService for KernApex {
+    fn create_event(&self, event_name: &ApexEventName) -> Result<ApexEventId, ApexReturnCode> {
+        self.ret(|| EventSyscallHandler.create(event_name))
+    }
+
+    fn set_event(&self, event_id: ApexEventId) -> Result<(), ApexReturnCode> {
+        self.ret(|| EventSyscallHandler.set(event_id))
+    }
+
+    fn reset_event(&self, event_id: ApexEventId) -> Result<(), ApexReturnCode> {
+        self.ret(|| EventSyscallHandler.reset(event_id))
+    }
+
+    fn wait_event(
+        &self,
+        event_id: ApexEventId,
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| EventSyscallHandler.wait(event_id, time_out))
+    }
+
+    fn get_event_id(&self, event_name: &ApexEventName) -> Result<ApexEventId, ApexReturnCode> {
+        self.ret(|| EventSyscallHandler.get_id(event_name))
+    }
+
+    fn get_event_status(&self, event_id: ApexEventId) -> Result<ApexEventStatus, ApexReturnCode> {
+        self.ret(|| EventSyscallHandler.get_status(event_id))
+    }
+}
+
+impl ApexMutexService for KernApex {
+    fn create_mutex(
+        &self,
+        mutex_name: &ApexMutexName,
+        mutex_priority: ApexPriority,
+        queuing_discipline: ApexQueueDiscipline,
+    ) -> Result<ApexMutexId, ApexReturnCode> {
+        self.ret(|| MutexSyscallHandler.create(mutex_name, mutex_priority, queuing_discipline as _))
+    }
+
+    fn acquire_mutex(
+        &self,
+        mutex_id: ApexMutexId,
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| MutexSyscallHandler.acquire(mutex_id, time_out))
+    }
+
+    fn release_mutex(&self, mutex_id: ApexMutexId) -> Result<(), ApexReturnCode> {
+        self.ret(|| MutexSyscallHandler.release(mutex_id))
+    }
+
+    fn reset_mutex(
+        &self,
+        mutex_id: ApexMutexId,
+        process_id: ApexProcessId,
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| MutexSyscallHandler.reset(mutex_id, process_id))
+    }
+
+    fn get_mutex_id(&self, mutex_name: &ApexMutexName) -> Result<ApexMutexId, ApexReturnCode> {
+        self.ret(|| MutexSyscallHandler.get_id(mutex_name))
+    }
+
+    fn get_mutex_status(&self, mutex_id: ApexMutexId) -> Result<ApexMutexStatus, ApexReturnCode> {
+        self.ret(|| MutexSyscallHandler.get_status(mutex_id))
+    }
+
+    fn get_process_mutex_state(
+        &self,
+        process_id: ApexProcessId,
+    ) -> Result<ApexMutexId, ApexReturnCode> {
+        self.ret(|| MutexSyscallHandler.get_process_mutex_state(process_id))
+    }
+}
+
+impl ApexHealthService for KernApex {
+    fn report_application_message(&self, message: &[ApexByte]) -> Result<(), ApexReturnCode> {
+        self.ret(|| HealthSyscallHandler.report_application_message(message))
+    }
+
+    fn create_error_handler(
+        &self,
+        entry_point: ApexSystemAddress,
+        stack_size: ApexStackSize,
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| HealthSyscallHandler.create_error_handler(entry_point.into(), stack_size))
+    }
+
+    fn get_error_status(&self) -> Result<ApexErrorStatus, ApexReturnCode> {
+        self.ret(|| HealthSyscallHandler.get_error_status())
+    }
+
+    fn raise_application_error(
+        &self,
+        error_code: ApexErrorCode,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode> {
+        self.ret(|| HealthSyscallHandler.raise_application_error(error_code as _, message))
+    }
+}
+
+impl ApexScheduleService for KernApex {
+    fn set_module_schedule(&self, schedule_id: ApexScheduleId) -> Result<(), ApexReturnCode> {
+        self.ret(|| ScheduleSyscallHandler.set_module_schedule(schedule_id))
+    }
+
+    fn get_module_schedule_status(&self) -> Result<ApexScheduleStatus, ApexReturnCode> {
+        self.ret(|| ScheduleSyscallHandler.get_module_schedule_status())
+    }
+
+    fn get_module_schedule_id(
+        &self,
+        schedule_name: &ApexScheduleName,
+    ) -> Result<ApexScheduleId, ApexReturnCode> {
+        self.ret(|| ScheduleSyscallHandler.get_module_schedule_id(schedule_name))
+    }
+
+    fn get_window_status(
+        &self,
+        core_id: ApexProcessorCoreId,
+        window_index: ApexWindowIndex,
+    ) -> Result<ApexWindowStatus, ApexReturnCode> {
+        self.ret(|| ScheduleSyscallHandler.get_window_status(core_id, window_index))
+    }
+
+    fn get_gap_status(
+        &self,
+        core_id: ApexProcessorCoreId,
+    ) -> Result<ApexGapStatus, ApexReturnCode> {
+        self.ret(|| ScheduleSyscallHandler.get_gap_status(core_id))
+    }
+
+    fn get_timing_event(
+        &self,
+        event_index: ApexTimingEventIndex,
+    ) -> Result<ApexTimingEvent, ApexReturnCode> {
+        self.ret(|| ScheduleSyscallHandler.get_timing_event(event_index))
+    }
+}
@@ -1,8 +1,19 @@
 #![no_std]
 
 mod all;
+mod blackboard;
+mod buffer;
+mod event;
+mod health;
+mod kern;
+mod mutex;
 mod partition;
 mod process;
+mod queuing;
+mod sampling;
+mod schedule;
+mod semaphore;
+mod time;
 
 extern crate alloc;
 
@@ -10,3 +21,4 @@ extern crate alloc;
 extern crate jrinx_hal;
 
 pub use all::handle;
+pub use kern::KernApex;
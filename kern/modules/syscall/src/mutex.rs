@@ -0,0 +1,119 @@
+use jrinx_a653::{
+    mutex::{Mutex, MutexConfig},
+    partition::Partition,
+    process::Process,
+};
+use jrinx_apex::*;
+
+pub(crate) struct MutexSyscallHandler;
+
+impl MutexSyscallHandler {
+    pub(crate) fn create(
+        &self,
+        name: &ApexMutexName,
+        priority: ApexPriority,
+        discipline: usize,
+    ) -> Result<ApexMutexId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+
+        if Mutex::find_by_name(partition.identifier(), name).is_some() {
+            return Err(ApexReturnCode::NoAction);
+        }
+        if !(APEX_PRIORITY_MIN..=Process::MAX_PRIORITY).contains(&priority) {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        let discipline: ApexQueueDiscipline = (discipline as u32)
+            .try_into()
+            .map_err(|_| ApexReturnCode::InvalidParam)?;
+        if partition.operating_mode() == ApexOperatingMode::Normal {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let mutex = Mutex::new(
+            partition.identifier(),
+            &MutexConfig {
+                name: *name,
+                priority,
+                discipline,
+            },
+        )
+        .map_err(|_| ApexReturnCode::InvalidConfig)?;
+
+        Ok(mutex.identifier().into())
+    }
+
+    pub(crate) fn acquire(
+        &self,
+        id: ApexMutexId,
+        timeout: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::current().unwrap();
+        let mutex = Mutex::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if timeout != APEX_TIME_INFINITY && timeout < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        match process.mutex() {
+            Some(owned) if owned == mutex.identifier() => {}
+            Some(_) => return Err(ApexReturnCode::InvalidMode),
+            None => {
+                if process.curr_priority() > mutex.priority() {
+                    return Err(ApexReturnCode::InvalidConfig);
+                }
+            }
+        }
+
+        mutex.acquire(timeout)
+    }
+
+    pub(crate) fn release(&self, id: ApexMutexId) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let mutex = Mutex::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        mutex.release()
+    }
+
+    pub(crate) fn reset(
+        &self,
+        id: ApexMutexId,
+        process_id: ApexProcessId,
+    ) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let mutex = Mutex::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+        let process = Process::find_by_id(partition.identifier(), process_id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        mutex.reset(&process)
+    }
+
+    pub(crate) fn get_id(&self, name: &ApexMutexName) -> Result<ApexMutexId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let mutex = Mutex::find_by_name(partition.identifier(), name)
+            .ok_or(ApexReturnCode::InvalidConfig)?;
+
+        Ok(mutex.identifier().into())
+    }
+
+    pub(crate) fn get_status(&self, id: ApexMutexId) -> Result<ApexMutexStatus, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let mutex = Mutex::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        Ok(mutex.status())
+    }
+
+    pub(crate) fn get_process_mutex_state(
+        &self,
+        process_id: ApexProcessId,
+    ) -> Result<ApexMutexId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::find_by_id(partition.identifier(), process_id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        Ok(process.mutex().map_or(APEX_NO_MUTEX_OWNED, Into::into))
+    }
+}
@@ -23,29 +23,36 @@ impl PartitionSyscallHandler {
             return Err(ApexReturnCode::InvalidMode);
         }
 
-        partition.set_operating_mode(mode);
-
         match mode {
-            ApexOperatingMode::Idle => todo!("shutdown the partition"),
+            ApexOperatingMode::Idle => partition.shutdown(),
             ApexOperatingMode::WarmStart | ApexOperatingMode::ColdStart => {
-                todo!("inhibit process scheduling and switch back to initialization mode")
+                if let Err(err) = partition.restart(mode, ApexStartCondition::PartitionRestart) {
+                    log::warn!(
+                        "failed to restart partition {:?}: {:?}",
+                        partition.name(),
+                        err
+                    );
+                    partition.shutdown();
+                }
             }
             ApexOperatingMode::Normal => {
+                partition.set_operating_mode(mode);
+
                 // TODO:
                 //     [ ] TODO
                 //     [-] WIP
                 //     [x] DONE
                 //
-                // [-] set to READY all previously started (not delayed) aperiodic processes
+                // [x] set to READY all previously started (not delayed) aperiodic processes
                 //     (unless the process was suspended);
-                // [ ] set release point of all previously delay started aperiodic processes
+                // [x] set release point of all previously delay started aperiodic processes
                 //     to the system clock time plus their delay times;
-                // [ ] set first release points of all previously started (not delayed) periodic
+                // [x] set first release points of all previously started (not delayed) periodic
                 //     processes to the partition’s next periodic processing start;
-                // [ ]  set first release points of all previously delay started periodic processes
+                // [x] set first release points of all previously delay started periodic processes
                 //     to the partition’s next periodic processing start plus their delay times;
                 //     -- at their release points, the processes are set to READY (if not DORMANT)
-                // [ ] calculate the DEADLINE_TIME of all non-dormant processes in the partition;
+                // [x] calculate the DEADLINE_TIME of all non-dormant processes in the partition;
                 //     -- a DEADLINE_TIME calculation may cause an overflow of the underlying
                 //     -- clock. If this occurs, HM is invoked with an illegal request error code
                 // [x] set the partition’s lock level to zero;
@@ -55,7 +62,9 @@ impl PartitionSyscallHandler {
                 // [x] activate the process scheduling;
 
                 partition.run_pre_start_hooks();
-                partition.set_lock_level(APEX_LOCK_LEVEL_MIN);
+                partition
+                    .reset_preemption()
+                    .map_err(|_| ApexReturnCode::InvalidConfig)?;
                 partition.pt_sync();
                 Runtime::switch_yield();
             }
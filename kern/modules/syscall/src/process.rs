@@ -1,17 +1,13 @@
-use core::ops::Deref;
-
-use alloc::sync::Arc;
 use jrinx_a653::{
+    health,
+    mutex::Mutex,
     partition::Partition,
     process::{Process, ProcessConfig, ProcessRunner},
     A653Entry,
 };
 use jrinx_apex::*;
-use jrinx_hal::{Cpu, Hal, Interrupt};
-use jrinx_multitask::{
-    executor::ExecutorStatus,
-    runtime::{Runtime, RuntimeStatus},
-};
+use jrinx_hal::{Cpu, Hal};
+use jrinx_multitask::{executor::ExecutorStatus, runtime::Runtime};
 
 pub(crate) struct ProcessSyscallHandler;
 
@@ -86,78 +82,277 @@ impl ProcessSyscallHandler {
         Ok(process.identifier().into())
     }
 
-    pub(crate) fn start(&self, id: ApexProcessId) -> Result<(), ApexReturnCode> {
-        fn start_executor(process: Arc<Process>, status: ExecutorStatus) {
-            let mut executor = process
-                .gen_executor(ProcessRunner {
-                    syscall: crate::handle,
-                })
-                .unwrap();
-
-            executor.set_status(status);
-
-            let cpu_id = process.core_affinity().unwrap_or(hal!().cpu().id());
-            Runtime::with_spec_cpu(cpu_id, move |rt| {
-                rt.with_registry(|reg| {
-                    for (_, inspector) in reg.iter() {
-                        let that_partition: Option<Arc<Partition>> =
-                            inspector.ext().deref().downcast_ref().cloned();
-                        if let Some(that_partition) = that_partition {
-                            if that_partition.identifier() == process.partition_id() {
-                                inspector.register(executor).unwrap();
-                                break;
-                            }
-                        }
-                    }
-                })
-            })
-            .unwrap();
-            if Runtime::with_spec_cpu(cpu_id, |rt| rt.status() == RuntimeStatus::Endpoint).unwrap()
-            {
-                hal!().interrupt().send_ipi(&[cpu_id]);
-            }
+    pub(crate) fn set_priority(
+        &self,
+        id: ApexProcessId,
+        priority: ApexPriority,
+    ) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if !(0..=Process::MAX_PRIORITY).contains(&priority) {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if process.process_state() == ApexProcessState::Dormant {
+            return Err(ApexReturnCode::InvalidMode);
         }
 
+        // a process owning a mutex keeps running at the priority of the mutex, and returns to
+        // the new priority when it releases the mutex
+        if let Some(mutex) = process
+            .mutex()
+            .and_then(|mutex_id| Mutex::find_by_id(partition.identifier(), mutex_id))
+        {
+            return mutex.set_retained_priority(&process, priority);
+        }
+
+        process
+            .set_curr_priority(priority)
+            .map_err(|_| ApexReturnCode::InvalidConfig)
+    }
+
+    pub(crate) fn suspend_self(&self, timeout: ApexSystemTime) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::current().unwrap();
+
+        if partition.lock_level() != APEX_LOCK_LEVEL_MIN || process.is_error_handler() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+        if process.period() != APEX_TIME_INFINITY {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+        if timeout != APEX_TIME_INFINITY && timeout < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if timeout == 0 {
+            return Ok(());
+        }
+
+        process
+            .suspend(timeout)
+            .map_err(|_| ApexReturnCode::InvalidConfig)?;
+        Runtime::switch_yield();
+
+        if process.suspension_timed_out() {
+            Err(ApexReturnCode::TimedOut)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn suspend(&self, id: ApexProcessId) -> Result<(), ApexReturnCode> {
         let partition = Partition::current().unwrap();
         let process = Process::find_by_id(partition.identifier(), id.into())
             .ok_or(ApexReturnCode::InvalidParam)?;
+        let current = Process::current().unwrap();
+
+        if process.identifier() == current.identifier() {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if matches!(
+            process.process_state(),
+            ApexProcessState::Dormant | ApexProcessState::Faulted
+        ) {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+        if process.period() != APEX_TIME_INFINITY {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+        if process.mutex().is_some() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+        if process.is_suspended() {
+            return Err(ApexReturnCode::NoAction);
+        }
+
+        process
+            .suspend(APEX_TIME_INFINITY)
+            .map_err(|_| ApexReturnCode::InvalidConfig)?;
+
+        Ok(())
+    }
+
+    pub(crate) fn resume(&self, id: ApexProcessId) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+        let current = Process::current().unwrap();
+
+        if process.identifier() == current.identifier() {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if matches!(
+            process.process_state(),
+            ApexProcessState::Dormant | ApexProcessState::Faulted
+        ) {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+        if !process.is_suspended() {
+            return Err(ApexReturnCode::NoAction);
+        }
+
+        process
+            .resume()
+            .map_err(|_| ApexReturnCode::InvalidConfig)?;
+        if partition.lock_level() == APEX_LOCK_LEVEL_MIN {
+            Runtime::switch_yield();
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn stop_self(&self) -> Result<(), ApexReturnCode> {
+        let process = Process::current().unwrap();
+
+        process.stop().map_err(|_| ApexReturnCode::InvalidConfig)
+    }
+
+    pub(crate) fn stop(&self, id: ApexProcessId) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+        let current = Process::current().unwrap();
+
+        if process.identifier() == current.identifier() {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if process.process_state() == ApexProcessState::Dormant {
+            return Err(ApexReturnCode::NoAction);
+        }
+
+        process.stop().map_err(|_| ApexReturnCode::InvalidConfig)
+    }
+
+    pub(crate) fn start(&self, id: ApexProcessId) -> Result<(), ApexReturnCode> {
+        self.delayed_start(id, 0)
+    }
+
+    pub(crate) fn delayed_start(
+        &self,
+        id: ApexProcessId,
+        delay_time: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+        if delay_time == APEX_TIME_INFINITY || delay_time < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if process.period() != APEX_TIME_INFINITY && delay_time >= process.period() {
+            return Err(ApexReturnCode::InvalidParam);
+        }
         if process.process_state() != ApexProcessState::Dormant {
             return Err(ApexReturnCode::NoAction);
         }
-        let deadline_time = match process.time_capacity() {
-            APEX_TIME_INFINITY => APEX_TIME_INFINITY,
-            time_capacity => duration_as_time(
-                hal!()
-                    .cpu()
-                    .get_time()
-                    .checked_add(time_as_duration(time_capacity))
-                    .ok_or(ApexReturnCode::InvalidConfig)?,
-            ),
-        };
+        if process.time_capacity() != APEX_TIME_INFINITY
+            && hal!()
+                .cpu()
+                .get_time()
+                .checked_add(time_as_duration(delay_time))
+                .and_then(|time| time.checked_add(time_as_duration(process.time_capacity())))
+                .is_none()
+        {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
 
-        if process.period() == APEX_TIME_INFINITY {
-            process.set_curr_priority(process.base_priority());
-            process.set_process_state(ApexProcessState::Waiting);
-
-            let start = move || {
-                process.clone().set_process_state(ApexProcessState::Ready);
-                process.clone().set_deadline_time(deadline_time); // TODO: set timed-event for deadline
-                start_executor(process.clone(), ExecutorStatus::Runnable);
-            };
-
-            if partition.operating_mode() == ApexOperatingMode::Normal {
-                start();
-                Runtime::switch_yield();
-            } else {
-                partition.add_pre_start_hook(start);
+        process
+            .set_curr_priority(process.base_priority())
+            .map_err(|_| ApexReturnCode::InvalidConfig)?;
+        process.set_process_state(ApexProcessState::Waiting);
+
+        let start = {
+            let partition = partition.clone();
+            move || {
+                if process.process_state() == ApexProcessState::Dormant {
+                    return;
+                }
+
+                // a periodic process is first released at the partition's next periodic
+                // processing start, while an aperiodic one is released at once, both of
+                // which are postponed by the delay time
+                let release_point = if process.period() == APEX_TIME_INFINITY {
+                    duration_as_time(hal!().cpu().get_time())
+                } else {
+                    partition.next_periodic_start()
+                }
+                .saturating_add(delay_time);
+                if process.period() == APEX_TIME_INFINITY && delay_time == 0 {
+                    process.set_process_state(ApexProcessState::Ready);
+                }
+
+                // the process is never run if it fails to be released or spawned, so it is
+                // set back to dormant once the health monitor recovers from the failure
+                let fail = |what, err| {
+                    log::warn!("failed to {} process {:?}: {:?}", what, process.name(), err);
+                    health::recover(Some(&process), ApexErrorCode::IllegalRequest);
+                    process.set_process_state(ApexProcessState::Dormant);
+                };
+                if let Err(err) = process.set_release_point(release_point) {
+                    fail("release", err);
+                    return;
+                }
+                if let Err(err) = process.spawn(
+                    ProcessRunner {
+                        syscall: crate::handle,
+                    },
+                    ExecutorStatus::Runnable,
+                ) {
+                    fail("spawn", err);
+                }
             }
+        };
+
+        if partition.operating_mode() == ApexOperatingMode::Normal {
+            start();
+            Runtime::switch_yield();
         } else {
-            todo!("start periodic process");
+            partition.add_pre_start_hook(start);
         }
 
         Ok(())
     }
 
+    pub(crate) fn lock_preemption(&self) -> Result<ApexLockLevel, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::current().unwrap();
+
+        if partition.operating_mode() != ApexOperatingMode::Normal || process.is_error_handler() {
+            return Err(ApexReturnCode::NoAction);
+        }
+        if partition.lock_level() >= APEX_LOCK_LEVEL_MAX {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if !process.owns_preemption_lock() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        partition
+            .lock_preemption()
+            .map_err(|_| ApexReturnCode::InvalidConfig)
+    }
+
+    pub(crate) fn unlock_preemption(&self) -> Result<ApexLockLevel, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::current().unwrap();
+
+        if partition.operating_mode() != ApexOperatingMode::Normal || process.is_error_handler() {
+            return Err(ApexReturnCode::NoAction);
+        }
+        if partition.lock_level() == APEX_LOCK_LEVEL_MIN || !process.owns_preemption_lock() {
+            return Err(ApexReturnCode::NoAction);
+        }
+
+        let lock_level = partition
+            .unlock_preemption()
+            .map_err(|_| ApexReturnCode::InvalidConfig)?;
+        if lock_level == APEX_LOCK_LEVEL_MIN {
+            Runtime::switch_yield();
+        }
+
+        Ok(lock_level)
+    }
+
     pub(crate) fn initialize_process_core_affinity(
         &self,
         process_id: ApexProcessId,
@@ -179,4 +374,26 @@ impl ProcessSyscallHandler {
 
         Ok(())
     }
+
+    pub(crate) fn get_my_id(&self) -> Result<ApexProcessId, ApexReturnCode> {
+        let process = Process::current().unwrap();
+        if process.index().is_none() || process.is_error_handler() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        Ok(process.identifier().into())
+    }
+
+    pub(crate) fn get_my_processor_core_id(&self) -> Result<ApexProcessorCoreId, ApexReturnCode> {
+        Ok(hal!().cpu().id() as _)
+    }
+
+    pub(crate) fn get_my_index(&self) -> Result<ApexProcessIndex, ApexReturnCode> {
+        let process = Process::current().unwrap();
+        if process.is_error_handler() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        process.index().ok_or(ApexReturnCode::InvalidMode)
+    }
 }
@@ -0,0 +1,149 @@
+use jrinx_a653::{
+    channel::{Channel, ChannelPort},
+    partition::Partition,
+    queuing::{QueuingPort, QueuingPortConfig},
+};
+use jrinx_apex::*;
+
+pub(crate) struct QueuingPortSyscallHandler;
+
+impl QueuingPortSyscallHandler {
+    pub(crate) fn create(
+        &self,
+        name: &ApexQueuingPortName,
+        max_message_size: ApexMessageSize,
+        max_nb_message: ApexMessageRange,
+        direction: usize,
+        discipline: usize,
+    ) -> Result<ApexQueuingPortId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let direction: ApexPortDirection = (direction as u32)
+            .try_into()
+            .map_err(|_| ApexReturnCode::InvalidConfig)?;
+        let discipline: ApexQueueDiscipline = (discipline as u32)
+            .try_into()
+            .map_err(|_| ApexReturnCode::InvalidConfig)?;
+
+        let (channel, channel_direction) = Channel::find_by_port(&ChannelPort {
+            partition: partition.name(),
+            port: *name,
+        })
+        .ok_or(ApexReturnCode::InvalidConfig)?;
+        if !channel.is_queuing() {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if QueuingPort::find_by_name(partition.identifier(), name).is_some() {
+            return Err(ApexReturnCode::NoAction);
+        }
+        if max_message_size != channel.max_message_size() {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if Some(max_nb_message) != channel.max_nb_message() {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if direction != channel_direction {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if partition.operating_mode() == ApexOperatingMode::Normal {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let port = QueuingPort::new(
+            partition.identifier(),
+            channel,
+            &QueuingPortConfig {
+                name: *name,
+                direction,
+                discipline,
+            },
+        )
+        .map_err(|_| ApexReturnCode::InvalidConfig)?;
+
+        Ok(port.identifier().into())
+    }
+
+    pub(crate) fn send(
+        &self,
+        id: ApexQueuingPortId,
+        message: &[ApexByte],
+        timeout: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let port = QueuingPort::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if timeout != APEX_TIME_INFINITY && timeout < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if message.len() > port.max_message_size() as usize {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if message.is_empty() {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if port.direction() != ApexPortDirection::Source {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        port.send(message, timeout)
+    }
+
+    pub(crate) fn receive(
+        &self,
+        id: ApexQueuingPortId,
+        timeout: ApexSystemTime,
+        message: &mut [ApexByte],
+    ) -> Result<ApexMessageSize, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let port = QueuingPort::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if timeout != APEX_TIME_INFINITY && timeout < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if port.direction() != ApexPortDirection::Destination {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+        if message.len() < port.max_message_size() as usize {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        port.receive(message, timeout)
+    }
+
+    pub(crate) fn get_id(
+        &self,
+        name: &ApexQueuingPortName,
+    ) -> Result<ApexQueuingPortId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let port = QueuingPort::find_by_name(partition.identifier(), name)
+            .ok_or(ApexReturnCode::InvalidConfig)?;
+
+        Ok(port.identifier().into())
+    }
+
+    pub(crate) fn get_status(
+        &self,
+        id: ApexQueuingPortId,
+    ) -> Result<ApexQueuingPortStatus, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let port = QueuingPort::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        Ok(port.status())
+    }
+
+    pub(crate) fn clear(&self, id: ApexQueuingPortId) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let port = QueuingPort::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if port.direction() != ApexPortDirection::Destination {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        port.clear();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+use alloc::{vec, vec::Vec};
+use jrinx_a653::{
+    channel::{Channel, ChannelPort},
+    partition::Partition,
+    sampling::{SamplingPort, SamplingPortConfig},
+};
+use jrinx_apex::*;
+
+pub(crate) struct SamplingPortSyscallHandler;
+
+impl SamplingPortSyscallHandler {
+    pub(crate) fn create(
+        &self,
+        name: &ApexSamplingPortName,
+        max_message_size: ApexMessageSize,
+        direction: usize,
+        refresh_period: ApexSystemTime,
+    ) -> Result<ApexSamplingPortId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let direction: ApexPortDirection = (direction as u32)
+            .try_into()
+            .map_err(|_| ApexReturnCode::InvalidConfig)?;
+
+        let (channel, channel_direction) = Channel::find_by_port(&ChannelPort {
+            partition: partition.name(),
+            port: *name,
+        })
+        .ok_or(ApexReturnCode::InvalidConfig)?;
+        if !channel.is_sampling() {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if SamplingPort::find_by_name(partition.identifier(), name).is_some() {
+            return Err(ApexReturnCode::NoAction);
+        }
+        if max_message_size != channel.max_message_size() {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if direction != channel_direction {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if refresh_period != APEX_TIME_INFINITY && refresh_period < 0 {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if partition.operating_mode() == ApexOperatingMode::Normal {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let port = SamplingPort::new(
+            partition.identifier(),
+            channel,
+            &SamplingPortConfig {
+                name: *name,
+                direction,
+                refresh_period,
+            },
+        )
+        .map_err(|_| ApexReturnCode::InvalidConfig)?;
+
+        Ok(port.identifier().into())
+    }
+
+    pub(crate) fn write(
+        &self,
+        id: ApexSamplingPortId,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let port = SamplingPort::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if message.len() > port.max_message_size() as usize {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if message.is_empty() {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if port.direction() != ApexPortDirection::Source {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        port.write(message);
+
+        Ok(())
+    }
+
+    /// Reads the message of the port into a buffer of the kernel, so that the caller only
+    /// copies the message itself back, whatever the size of its own buffer.
+    pub(crate) fn read(
+        &self,
+        id: ApexSamplingPortId,
+    ) -> Result<(Vec<ApexByte>, ApexValidity), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let port = SamplingPort::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if port.direction() != ApexPortDirection::Destination {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let mut message = vec![0; port.max_message_size() as usize];
+        let (length, validity) = port.read(&mut message).ok_or(ApexReturnCode::NoAction)?;
+        message.truncate(length as _);
+
+        Ok((message, validity))
+    }
+
+    pub(crate) fn get_id(
+        &self,
+        name: &ApexSamplingPortName,
+    ) -> Result<ApexSamplingPortId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let port = SamplingPort::find_by_name(partition.identifier(), name)
+            .ok_or(ApexReturnCode::InvalidConfig)?;
+
+        Ok(port.identifier().into())
+    }
+
+    pub(crate) fn get_status(
+        &self,
+        id: ApexSamplingPortId,
+    ) -> Result<ApexSamplingPortStatus, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let port = SamplingPort::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        Ok(port.status())
+    }
+}
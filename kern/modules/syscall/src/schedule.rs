@@ -0,0 +1,162 @@
+use jrinx_a653::{
+    health::{self, ModuleHmEvent},
+    partition::Partition,
+    schedule::Schedule,
+};
+use jrinx_apex::*;
+use jrinx_hal::{Cpu, Hal};
+use jrinx_multitask::runtime::Runtime;
+
+pub(crate) struct ScheduleSyscallHandler;
+
+impl ScheduleSyscallHandler {
+    pub(crate) fn set_module_schedule(&self, id: ApexScheduleId) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let schedule = Schedule::find_by_id(id.into()).ok_or(ApexReturnCode::InvalidParam)?;
+
+        if !partition.kernel() {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+
+        schedule.request().map_err(|err| {
+            log::warn!(
+                "failed to request module schedule {:?}: {:?}",
+                schedule.name(),
+                err
+            );
+            ApexReturnCode::InvalidConfig
+        })
+    }
+
+    pub(crate) fn get_module_schedule_status(&self) -> Result<ApexScheduleStatus, ApexReturnCode> {
+        Ok(Schedule::status())
+    }
+
+    pub(crate) fn get_module_schedule_id(
+        &self,
+        name: &ApexScheduleName,
+    ) -> Result<ApexScheduleId, ApexReturnCode> {
+        Schedule::find_by_name(name)
+            .map(|schedule| schedule.identifier().into())
+            .ok_or(ApexReturnCode::InvalidConfig)
+    }
+
+    pub(crate) fn get_window_status(
+        &self,
+        core_id: ApexProcessorCoreId,
+        index: ApexWindowIndex,
+    ) -> Result<ApexWindowStatus, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+
+        if !partition.kernel() {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if core_id < 0 || core_id as usize >= hal!().cpu().nproc() || index < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        let (entry, stats) = Runtime::with_spec_cpu(core_id as _, |rt| rt.window_stats(index as _))
+            .ok()
+            .flatten()
+            .ok_or(ApexReturnCode::InvalidParam)?;
+        let partition_id = Partition::find_by_inspector(core_id as _, entry.inspector_id)
+            .ok_or(ApexReturnCode::InvalidParam)?
+            .identifier();
+
+        Ok(ApexWindowStatus {
+            partition_id: partition_id.into(),
+            window_offset: duration_as_time(entry.offset),
+            window_duration: duration_as_time(entry.duration),
+            activations: stats.activations as _,
+            last_start: duration_as_time(stats.last_start),
+            last_end: duration_as_time(stats.last_end),
+            last_consumed: duration_as_time(stats.last_consumed),
+            total_consumed: duration_as_time(stats.total_consumed),
+            max_jitter: duration_as_time(stats.max_jitter),
+            max_overrun: duration_as_time(stats.max_overrun),
+            overruns: stats.overruns as _,
+        })
+    }
+
+    pub(crate) fn get_gap_status(
+        &self,
+        core_id: ApexProcessorCoreId,
+    ) -> Result<ApexGapStatus, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+
+        if !partition.kernel() {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if core_id < 0 || core_id as usize >= hal!().cpu().nproc() {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        let stats = Runtime::with_spec_cpu(core_id as _, |rt| rt.gap_stats())
+            .map_err(|_| ApexReturnCode::InvalidParam)?;
+
+        Ok(ApexGapStatus {
+            idle_time: duration_as_time(stats.idle),
+            background_time: duration_as_time(stats.background),
+        })
+    }
+
+    /// Returns the `index`-th event (from the oldest one kept) of the module health monitor
+    /// log.
+    pub(crate) fn get_timing_event(
+        &self,
+        index: ApexTimingEventIndex,
+    ) -> Result<ApexTimingEvent, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+
+        if !partition.kernel() {
+            return Err(ApexReturnCode::InvalidConfig);
+        }
+        if index < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        let event = *health::module_hm_log()
+            .get(index as usize)
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        Ok(match event {
+            ModuleHmEvent::WindowStartMissed {
+                cpu_id,
+                partition_id,
+                expected,
+                actual,
+            } => ApexTimingEvent {
+                kind: ApexTimingEventKind::WindowStartMissed,
+                core_id: cpu_id as _,
+                partition_id: partition_id.map_or(0, ApexPartitionId::from),
+                expected_time: expected,
+                actual_time: actual,
+                ..Default::default()
+            },
+            ModuleHmEvent::LateJoin {
+                cpu_id,
+                datum,
+                joined,
+            } => ApexTimingEvent {
+                kind: ApexTimingEventKind::LateJoin,
+                core_id: cpu_id as _,
+                expected_time: datum,
+                actual_time: joined,
+                ..Default::default()
+            },
+            ModuleHmEvent::OutOfPhase {
+                cpu_id,
+                other_cpu_id,
+                datum,
+                other_datum,
+            } => ApexTimingEvent {
+                kind: ApexTimingEventKind::OutOfPhase,
+                core_id: cpu_id as _,
+                other_core_id: other_cpu_id as _,
+                expected_time: other_datum,
+                actual_time: datum,
+                ..Default::default()
+            },
+        })
+    }
+}
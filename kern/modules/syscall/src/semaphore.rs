@@ -0,0 +1,97 @@
+use jrinx_a653::{
+    partition::Partition,
+    semaphore::{Semaphore, SemaphoreConfig},
+};
+use jrinx_apex::*;
+
+pub(crate) struct SemaphoreSyscallHandler;
+
+impl SemaphoreSyscallHandler {
+    pub(crate) fn create(
+        &self,
+        name: &ApexSemaphoreName,
+        current_value: ApexSemaphoreValue,
+        maximum_value: ApexSemaphoreValue,
+        discipline: usize,
+    ) -> Result<ApexSemaphoreId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+
+        if Semaphore::find_by_name(partition.identifier(), name).is_some() {
+            return Err(ApexReturnCode::NoAction);
+        }
+        if !(0..=APEX_SEMAPHORE_VALUE_MAX).contains(&current_value) {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if !(0..=APEX_SEMAPHORE_VALUE_MAX).contains(&maximum_value) {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        if current_value > maximum_value {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+        let discipline: ApexQueueDiscipline = (discipline as u32)
+            .try_into()
+            .map_err(|_| ApexReturnCode::InvalidParam)?;
+        if partition.operating_mode() == ApexOperatingMode::Normal {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let semaphore = Semaphore::new(
+            partition.identifier(),
+            &SemaphoreConfig {
+                name: *name,
+                current_value,
+                maximum_value,
+                discipline,
+            },
+        )
+        .map_err(|_| ApexReturnCode::InvalidConfig)?;
+
+        Ok(semaphore.identifier().into())
+    }
+
+    pub(crate) fn wait(
+        &self,
+        id: ApexSemaphoreId,
+        timeout: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let semaphore = Semaphore::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        if timeout != APEX_TIME_INFINITY && timeout < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        semaphore.wait(timeout)
+    }
+
+    pub(crate) fn signal(&self, id: ApexSemaphoreId) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let semaphore = Semaphore::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        semaphore.signal()
+    }
+
+    pub(crate) fn get_id(
+        &self,
+        name: &ApexSemaphoreName,
+    ) -> Result<ApexSemaphoreId, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let semaphore = Semaphore::find_by_name(partition.identifier(), name)
+            .ok_or(ApexReturnCode::InvalidConfig)?;
+
+        Ok(semaphore.identifier().into())
+    }
+
+    pub(crate) fn get_status(
+        &self,
+        id: ApexSemaphoreId,
+    ) -> Result<ApexSemaphoreStatus, ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let semaphore = Semaphore::find_by_id(partition.identifier(), id.into())
+            .ok_or(ApexReturnCode::InvalidParam)?;
+
+        Ok(semaphore.status())
+    }
+}
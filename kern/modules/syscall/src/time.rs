@@ -0,0 +1,86 @@
+use jrinx_a653::{partition::Partition, process::Process, wait};
+use jrinx_apex::*;
+use jrinx_hal::{Cpu, Hal};
+use jrinx_multitask::runtime::Runtime;
+
+pub(crate) struct TimeSyscallHandler;
+
+impl TimeSyscallHandler {
+    pub(crate) fn timed_wait(&self, delay_time: ApexSystemTime) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::current().unwrap();
+
+        if partition.lock_level() != APEX_LOCK_LEVEL_MIN || process.is_error_handler() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+        if delay_time == APEX_TIME_INFINITY || delay_time < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        if delay_time == 0 {
+            Runtime::switch_yield();
+        } else {
+            let now = duration_as_time(hal!().cpu().get_time());
+            wait::wait_until(now.saturating_add(delay_time));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn periodic_wait(&self) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::current().unwrap();
+
+        if partition.lock_level() != APEX_LOCK_LEVEL_MIN || process.is_error_handler() {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+        if process.period() == APEX_TIME_INFINITY {
+            return Err(ApexReturnCode::InvalidMode);
+        }
+
+        let release_point = process
+            .release_point()
+            .checked_add(process.period())
+            .ok_or(ApexReturnCode::InvalidConfig)?;
+        process
+            .set_release_point(release_point)
+            .map_err(|_| ApexReturnCode::InvalidConfig)?;
+
+        wait::wait_until(release_point);
+
+        Ok(())
+    }
+
+    pub(crate) fn get_time(&self) -> Result<ApexSystemTime, ApexReturnCode> {
+        Ok(duration_as_time(hal!().cpu().get_time()))
+    }
+
+    pub(crate) fn replenish(&self, budget_time: ApexSystemTime) -> Result<(), ApexReturnCode> {
+        let partition = Partition::current().unwrap();
+        let process = Process::current().unwrap();
+
+        if partition.operating_mode() != ApexOperatingMode::Normal || process.is_error_handler() {
+            return Err(ApexReturnCode::NoAction);
+        }
+        if budget_time != APEX_TIME_INFINITY && budget_time < 0 {
+            return Err(ApexReturnCode::InvalidParam);
+        }
+
+        let deadline_time = match budget_time {
+            APEX_TIME_INFINITY => APEX_TIME_INFINITY,
+            budget_time => duration_as_time(hal!().cpu().get_time())
+                .checked_add(budget_time)
+                .ok_or(ApexReturnCode::InvalidParam)?,
+        };
+        if process.period() != APEX_TIME_INFINITY {
+            let next_release_point = process.release_point().saturating_add(process.period());
+            if deadline_time == APEX_TIME_INFINITY || deadline_time > next_release_point {
+                return Err(ApexReturnCode::InvalidMode);
+            }
+        }
+
+        process.set_deadline_time(deadline_time);
+
+        Ok(())
+    }
+}
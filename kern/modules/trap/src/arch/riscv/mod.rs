@@ -114,6 +114,21 @@ impl GenericContext for Context {
                 Exception::Breakpoint => TrapReason::Breakpoint {
                     addr: VirtAddr::new(self.sepc),
                 },
+                Exception::IllegalInstruction => TrapReason::IllegalInstruction {
+                    addr: VirtAddr::new(self.sepc),
+                },
+                Exception::LoadFault => TrapReason::AccessFault {
+                    addr: VirtAddr::new(self.stval),
+                    perm: PagePerm::R,
+                },
+                Exception::StoreFault => TrapReason::AccessFault {
+                    addr: VirtAddr::new(self.stval),
+                    perm: PagePerm::W,
+                },
+                Exception::InstructionFault => TrapReason::AccessFault {
+                    addr: VirtAddr::new(self.stval),
+                    perm: PagePerm::X,
+                },
                 Exception::LoadPageFault => TrapReason::PageFault {
                     addr: VirtAddr::new(self.stval),
                     perm: PagePerm::R,
@@ -197,8 +212,14 @@ extern "C" fn handle_kern_trap(ctx: &mut Context) {
     let reason = ctx.trap_reason();
     match reason {
         TrapReason::Breakpoint { addr: _ } => breakpoint::handle(ctx),
-        TrapReason::SoftwareInterrupt => soft_int::handle(ctx),
-        TrapReason::TimerInterrupt => timer_int::handle(ctx),
+        TrapReason::SoftwareInterrupt => {
+            soft_int::handle(ctx);
+            crate::kern_preempt();
+        }
+        TrapReason::TimerInterrupt => {
+            timer_int::handle(ctx);
+            crate::kern_preempt();
+        }
         _ => unimplemented!("{:#x?}", ctx),
     }
 }
@@ -14,6 +14,7 @@ use core::fmt::Debug;
 
 use jrinx_addr::VirtAddr;
 use jrinx_paging::PagePerm;
+use spin::Once;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TrapReason {
@@ -22,6 +23,8 @@ pub enum TrapReason {
     TimerInterrupt,
     SystemCall,
     Breakpoint { addr: VirtAddr },
+    IllegalInstruction { addr: VirtAddr },
+    AccessFault { addr: VirtAddr, perm: PagePerm },
     PageFault { addr: VirtAddr, perm: PagePerm },
     Unknown { code: usize },
 }
@@ -46,6 +49,22 @@ pub trait GenericContext: Debug + Clone + Copy {
     fn run(&mut self);
 }
 
+static KERN_PREEMPT_HANDLER: Once<fn()> = Once::new();
+
 pub fn init() {
     arch::init();
 }
+
+/// Sets the handler called after the interrupts trapped in the kernel are handled, which may
+/// preempt the interrupted executor.
+///
+/// Only the first handler set takes effect.
+pub fn set_kern_preempt_handler(handler: fn()) {
+    KERN_PREEMPT_HANDLER.call_once(|| handler);
+}
+
+pub(crate) fn kern_preempt() {
+    if let Some(handler) = KERN_PREEMPT_HANDLER.get() {
+        handler();
+    }
+}
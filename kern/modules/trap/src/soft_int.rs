@@ -5,7 +5,7 @@ use crate::{GenericContext, TrapReason};
 
 static SOFT_INT_COUNTER: RwLock<u64> = RwLock::new(0);
 
-pub(crate) fn handle(ctx: &mut impl GenericContext) {
+pub fn handle(ctx: &mut impl GenericContext) {
     let TrapReason::SoftwareInterrupt = ctx.trap_reason() else {
         panic!("not a software interrupt");
     };
@@ -57,6 +57,27 @@ impl<P: Clone + Copy + Into<FastPriority>, I> FastPriorityQueue<P, I> {
                 result
             })
     }
+
+    pub fn remove(&mut self, item: &I) -> Option<(P, I)>
+    where
+        I: PartialEq,
+    {
+        let mut bits = self.bits;
+        while bits != 0 {
+            let pri = bits.trailing_zeros() as usize;
+            bits &= !(1 << pri);
+
+            let queue = &mut self.queues[pri];
+            if let Some(index) = queue.iter().position(|(_, x)| x == item) {
+                let result = queue.remove(index);
+                if queue.is_empty() {
+                    self.bits &= !(1 << pri);
+                }
+                return result;
+            }
+        }
+        None
+    }
 }
 
 pub struct FastPriorityQueueWithLock<P: Clone + Copy + Into<FastPriority>, I> {
@@ -83,4 +104,11 @@ impl<P: Clone + Copy + Into<FastPriority>, I> FastPriorityQueueWithLock<P, I> {
     pub fn dequeue(&self) -> Option<(P, I)> {
         self.inner.lock().dequeue()
     }
+
+    pub fn remove(&self, item: &I) -> Option<(P, I)>
+    where
+        I: PartialEq,
+    {
+        self.inner.lock().remove(item)
+    }
 }
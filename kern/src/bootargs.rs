@@ -1,22 +1,19 @@
-use alloc::{borrow::ToOwned, collections::BTreeMap, format, string::String, sync::Arc, vec::Vec};
+use alloc::{borrow::ToOwned, collections::BTreeMap, format, string::String, vec::Vec};
 use core::num::ParseIntError;
 
 use getargs::{Opt, Options};
-use jrinx_a653::{
-    partition::{Partition, PartitionConfig, PartitionId, PartitionTypeConfig},
-    process::{Process, ProcessRunner},
-};
 use jrinx_apex::*;
-use jrinx_hal::{Cpu, Hal};
-use jrinx_multitask::{
-    inspector::Inspector,
-    runtime::{Runtime, RuntimeSchedTable, RuntimeSchedTableEntry},
-    spawn, yield_now,
-};
+use jrinx_hal::{Cpu, Hal, HaltReason};
+use jrinx_modconf::*;
+use jrinx_multitask::{spawn, yield_now};
 use spin::Once;
 
+use crate::modconf::{self, ConfItem};
+
 static BOOTARGS: Once<String> = Once::new();
 
+type KeyValues<'a> = Vec<(&'a str, &'a str)>;
+
 pub(super) fn set(bootargs: &str) {
     BOOTARGS
         .try_call_once::<_, ()>(|| Ok(bootargs.to_owned()))
@@ -24,93 +21,178 @@ pub(super) fn set(bootargs: &str) {
 }
 
 pub async fn execute() {
-    if let Some(bootargs) = BOOTARGS.get() {
-        let args = bootargs
-            .split_whitespace()
-            .map(|s| s.to_owned())
-            .collect::<Vec<_>>();
-        let mut opts = Options::new(args.iter().map(String::as_str));
-
-        info!("bootargs: {}", bootargs.replace("--", "\n\t--"));
-
-        let mut partitions: Vec<Arc<Partition>> = Vec::new();
-
-        while let Some(opt) = opts.next_opt().unwrap() {
-            match opt {
-                Opt::Short('h') | Opt::Long("help") => help().await,
-
-                Opt::Short('t') | Opt::Long("test") => {
-                    test(match opts.value() {
-                        Ok(opt) => opt,
-                        _ => {
-                            panic!("missing argument for option: {opt}, try '-t/--test help' for more information");
-                        }
-                    }).await;
-                }
+    let mut conf = ModuleConf::default();
+    let mut origins: BTreeMap<ConfItem, &str> = BTreeMap::new();
+    let mut tests = Vec::new();
+    let mut errors = Vec::new();
+    let mut window_threshold = None;
+
+    let args = BOOTARGS
+        .get()
+        .map(|bootargs| {
+            info!("bootargs: {}", bootargs.replace("--", "\n\t--"));
+            bootargs.split_whitespace().collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let mut opts = Options::new(args.into_iter());
+
+    loop {
+        let opt = match opts.next_opt() {
+            Ok(Some(opt)) => opt,
+            Ok(None) => break,
+            Err(err) => {
+                errors.push(format!("{}", err));
+                break;
+            }
+        };
 
-                Opt::Long("partition") => {
-                    if let Some(partition) = partition(match opts.value() {
-                        Ok(opt) => opt,
-                        _ => {
-                            panic!("missing argument for option: {opt}, try '--partition help' for more information");
-                        }
-                    }).await {
-                        partitions.push(partition);
-                    }
+        let value = match opt {
+            Opt::Short('h') | Opt::Long("help") => {
+                help().await;
+                continue;
+            }
+            Opt::Short('t')
+            | Opt::Long(
+                "test" | "partition" | "channel" | "hm" | "scheduler" | "window-threshold",
+            ) => match opts.value() {
+                Ok(value) => value,
+                Err(_) => {
+                    errors.push(format!(
+                        "missing argument for option: {opt}, try '{opt} help' for more information"
+                    ));
+                    continue;
                 }
+            },
+            Opt::Short(_) | Opt::Long(_) => {
+                errors.push(format!("unrecognized option: {}", opt));
+                continue;
+            }
+        };
 
-                Opt::Long("scheduler") => {
-                    if let Some((cpu_id, sched_table, inspectors)) = scheduler(match opts.value() {
-                        Ok(opt) => opt,
-                        _ => {
-                            panic!("missing argument for option: {opt}, try '--scheduler help' for more information");
-                        }
-                    }, &partitions).await {
-                        Runtime::with_spec_cpu(cpu_id, |rt| {
-                            for inspector in inspectors {
-                                rt.register(inspector).unwrap();
-                            }
-                            rt.enact_sched_table(sched_table).unwrap();
-                        }).unwrap();
-                    }
+        let result = match opt {
+            Opt::Short('t') | Opt::Long("test") => {
+                if value == "help" {
+                    test_help().await;
+                } else {
+                    tests.push(value);
+                }
+                Ok(())
+            }
+            Opt::Long("partition") => partition(value).await.map(|partition| {
+                if let Some(partition) = partition {
+                    origins.insert(ConfItem::Partition(conf.partitions.len()), value);
+                    conf.partitions.push(partition);
                 }
+            }),
+            Opt::Long("channel") => channel(value, &conf).await.map(|channel| {
+                if let Some(channel) = channel {
+                    origins.insert(ConfItem::Channel(conf.channels.len()), value);
+                    conf.channels.push(channel);
+                }
+            }),
+            Opt::Long("hm") => hm(value, &mut conf).await,
+            Opt::Long("scheduler") => scheduler(value, &conf).await.map(|schedule| {
+                if let Some(schedule) = schedule {
+                    origins.insert(ConfItem::Schedule(conf.schedules.len()), value);
+                    conf.schedules.push(schedule);
+                }
+            }),
+            Opt::Long("window-threshold") => parse_time_from_proper_unit(value)
+                .map(|threshold| window_threshold = Some(threshold))
+                .map_err(|err| format!("invalid threshold: {err}")),
+            _ => unreachable!(),
+        };
+        if let Err(err) = result {
+            errors.push(format!("{opt} {value}: {err}"));
+        }
+    }
 
-                Opt::Short(_) | Opt::Long(_) => panic!("unrecognized option: {}", opt),
-            };
+    if !errors.is_empty() {
+        refuse(errors);
+    }
+
+    let (mut conf, origins) = if conf.is_empty() {
+        match modconf::embedded() {
+            Ok(conf) => (conf, None),
+            Err(err) => refuse([format!("invalid module configuration: {:?}", err)]),
         }
+    } else {
+        (conf, Some(origins))
+    };
+    if window_threshold.is_some() {
+        conf.window_threshold = window_threshold;
+    }
+    let describe = |item: ConfItem| match origins.as_ref().and_then(|origins| origins.get(&item)) {
+        Some(origin) => String::from(*origin),
+        None => modconf::describe(&conf, item),
+    };
+
+    let errors = modconf::validate(&conf);
+    if !errors.is_empty() {
+        refuse(
+            errors
+                .into_iter()
+                .map(|err| format!("{}: {}", describe(err.item), err.msg)),
+        );
+    }
+
+    if let Err(err) = modconf::build(&conf) {
+        refuse([format!("{}: {}", describe(err.item), err.msg)]);
+    }
+
+    for test in tests {
+        run_test(test).await;
     }
 }
 
+/// Reports all the configuration `errors` and halts the system without booting the module.
+fn refuse(errors: impl IntoIterator<Item = String>) -> ! {
+    for err in errors {
+        error!("{}", err);
+    }
+    error!("boot refused due to the invalid configuration");
+    hal!().halt(HaltReason::SysFailure);
+}
+
 async fn help() {
     info!("boot arguments:");
     info!("       --partition <opts>  Create a partition");
     info!("                           * use '--partition help' for more information");
+    info!("       --channel <opts>    Create a channel to connect partitions");
+    info!("                           * use '--channel help' for more information");
+    info!("       --hm <opts>         Configure a health monitor recovery table");
+    info!("                           * use '--hm help' for more information");
     info!("       --scheduler <opts>  Create a scheduler to schedule partitions");
     info!("                           * use '--scheduler help' for more information");
+    info!("       --window-threshold <unsigned><unit>");
+    info!("                           Log windows starting or ending later than the threshold");
+    info!("                           * the unit can be ns, us, ms or s");
+    info!("                           * the negative one indicates never logging");
     info!("   -t, --test <test>       Run the specified test");
     info!("   -h, --help              Display this information");
+    info!("The embedded module configuration (see 'cargo conf') is ignored if any of");
+    info!("'--partition', '--channel', '--hm' or '--scheduler' is specified");
 }
 
-async fn test(args: &str) {
-    if args == "help" {
-        info!("all available tests:");
-        let mut all_tests = jrinx_testdef::all().collect::<Vec<_>>();
-        all_tests.sort();
-        all_tests.iter().for_each(|test| info!("- {test}"));
-    } else {
-        let test = args;
-        let (name, func) =
-            jrinx_testdef::find(test).unwrap_or_else(|| panic!("unrecognized test case: {}", test));
-        info!("test case {} begin", name);
-        spawn!(async move {
-            func();
-        });
-        yield_now!();
-        info!("test case {} end", name);
-    }
+async fn test_help() {
+    info!("all available tests:");
+    let mut all_tests = jrinx_testdef::all().collect::<Vec<_>>();
+    all_tests.sort();
+    all_tests.iter().for_each(|test| info!("- {test}"));
+}
+
+async fn run_test(test: &str) {
+    let (name, func) =
+        jrinx_testdef::find(test).unwrap_or_else(|| panic!("unrecognized test case: {}", test));
+    info!("test case {} begin", name);
+    spawn!(async move {
+        func();
+    });
+    yield_now!();
+    info!("test case {} end", name);
 }
 
-async fn partition(args: &str) -> Option<Arc<Partition>> {
+async fn partition(args: &str) -> Result<Option<PartitionConf>, String> {
     let nproc = hal!().cpu().nproc_valid();
 
     if args == "help" {
@@ -128,7 +210,7 @@ async fn partition(args: &str) -> Option<Arc<Partition>> {
         info!("   num_cores=<unsigned>      Specify the number of cores of the partition");
         info!("                             * up to {nproc} cores");
         info!("Required (comma-seperated) arguments to create a *kern* partition configuration:");
-        info!("   entry=<str>               Specify the entry of the kernel partition (TODO)");
+        info!("   entry=<str>               Specify the entry of the kernel partition");
         info!("Required (comma-seperated) arguments to create a *user* partition configuration:");
         info!("   program=<str>             Specify the program of the user partition");
         info!("Required kern/user property to create a kern/user partition:");
@@ -139,62 +221,185 @@ async fn partition(args: &str) -> Option<Arc<Partition>> {
         info!("               |     |");
         info!("               |     +-- partition configuration");
         info!("               +-------- kern/user property");
-        None
+        info!("all available kernel partition entries:");
+        let mut all_entries = jrinx_partdef::all().collect::<Vec<_>>();
+        all_entries.sort();
+        all_entries.iter().for_each(|entry| info!("- {entry}"));
+        Ok(None)
+    } else {
+        let (is_user, config) = parse_prop_config(args, "kern", "user")?;
+
+        let name = parse_name(parse_required(&config, "name")?)?;
+        let memory = parse_usize_from_proper_redix(parse_required(&config, "memory")?)
+            .map_err(|err| format!("invalid memory: {err}"))?;
+        let period = parse_time_from_proper_unit(parse_required(&config, "period")?)
+            .map_err(|err| format!("invalid period: {err}"))?;
+        let duration = parse_time_from_proper_unit(parse_required(&config, "duration")?)
+            .map_err(|err| format!("invalid duration: {err}"))?;
+        let num_cores: u32 = parse_required(&config, "num_cores")?
+            .parse()
+            .map_err(|err| format!("invalid num_cores: {err}"))?;
+
+        Ok(Some(PartitionConf {
+            name,
+            kind: if is_user {
+                PartitionKindConf::User {
+                    program: parse_required(&config, "program")?.into(),
+                }
+            } else {
+                PartitionKindConf::Kern {
+                    entry: parse_required(&config, "entry")?.into(),
+                }
+            },
+            memory: memory as _,
+            period,
+            duration,
+            num_cores,
+            hm_table: Vec::new(),
+        }))
+    }
+}
+
+async fn channel(args: &str, conf: &ModuleConf) -> Result<Option<ChannelConf>, String> {
+    if args == "help" {
+        info!("To create a channel, you need to specify its mode and channel configuration");
+        info!("Required (comma-seperated) arguments to create a channel configuration:");
+        info!("   name=<str>                    Specify the name of the channel");
+        info!("   max_message_size=<unsigned>   Specify the maximum message size of the channel");
+        info!("                                 * the radix is determined by the prefix");
+        info!("   source=<str>:<str>            Specify the source port by its partition and port name");
+        info!("   destination=<str>:<str>;...   Specify the destination ports by their partition and port names");
+        info!("                                 * a queuing channel has exactly one destination");
+        info!("Required (comma-seperated) arguments to create a *queuing* channel configuration:");
+        info!(
+            "   max_nb_message=<unsigned>     Specify the maximum number of messages in the queue"
+        );
+        info!("                                 * the radix is determined by the prefix");
+        info!("Required mode to create a channel:");
+        info!("   {{sampling|queuing}}//<config>  Specify the mode and channel configuration");
+        info!("Example:");
+        info!("   --channel sampling//name=ch,max_message_size=64,source=ex1:out,destination=ex2:in;ex3:in");
+        info!("             ^~~~~~~^  ^~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~^");
+        info!("             |         |");
+        info!("             |         +-- channel configuration");
+        info!("             +------------ mode");
+        help_partitions(conf);
+        Ok(None)
     } else {
-        let mut pair = args.split("//");
-        let is_user = match pair.next().unwrap() {
-            "kern" => false,
-            "user" => true,
-            _ => panic!("invalid argument: {:?}", pair),
+        let (is_queuing, config) = parse_prop_config(args, "sampling", "queuing")?;
+
+        let name = parse_name(parse_required(&config, "name")?)?;
+        let max_message_size =
+            parse_usize_from_proper_redix(parse_required(&config, "max_message_size")?)
+                .map_err(|err| format!("invalid max_message_size: {err}"))?;
+        let source = parse_channel_port(parse_required(&config, "source")?)?;
+        let destinations = parse_required(&config, "destination")?
+            .split(';')
+            .map(parse_channel_port)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let kind = if is_queuing {
+            let max_nb_message =
+                parse_usize_from_proper_redix(parse_required(&config, "max_nb_message")?)
+                    .map_err(|err| format!("invalid max_nb_message: {err}"))?;
+            ChannelKindConf::Queuing {
+                max_nb_message: max_nb_message
+                    .try_into()
+                    .map_err(|_| format!("too large max_nb_message: {max_nb_message}"))?,
+            }
+        } else {
+            ChannelKindConf::Sampling
         };
 
-        let config = iter_key_value(pair.next().unwrap())
-            .unwrap()
-            .collect::<Vec<_>>();
+        Ok(Some(ChannelConf {
+            name,
+            max_message_size: max_message_size
+                .try_into()
+                .map_err(|_| format!("too large max_message_size: {max_message_size}"))?,
+            source,
+            destinations,
+            kind,
+        }))
+    }
+}
 
-        if pair.next().is_some() {
-            panic!("invalid argument: {:?}", pair);
+async fn hm(args: &str, conf: &mut ModuleConf) -> Result<(), String> {
+    if args == "help" {
+        info!("To configure a recovery table, you need to specify its scope and recovery actions");
+        info!("Required (comma-seperated) arguments to configure a recovery table:");
+        info!("   <error>=<action>          Specify the recovery action of the error");
+        info!(
+            "                             * the error can be deadline_missed, application_error,"
+        );
+        info!("                               numeric_error, illegal_request, stack_overflow,");
+        info!("                               memory_violation, hardware_fault or power_fail");
+        info!("                             * the action can be ignore, stop_process, cold_start,");
+        info!("                               warm_start or idle (partition), or ignore or reset");
+        info!("                               (module)");
+        info!(
+            "                             * unspecified errors default to stop_process (partition)"
+        );
+        info!("                               or reset (module), except deadline_missed which the");
+        info!("                               module ignores");
+        info!("Required scope to configure a recovery table:");
+        info!("   {{module|<str>}}//<config>  Specify the module or the partition by its name");
+        info!("Example:");
+        info!("   --hm ex1//deadline_missed=ignore,application_error=warm_start");
+        info!("        ^~^  ^~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~^");
+        info!("        |    |");
+        info!("        |    +-- recovery actions");
+        info!("        +------- scope");
+        help_partitions(conf);
+        Ok(())
+    } else {
+        let (scope, config) = args
+            .split_once("//")
+            .ok_or_else(|| String::from("missing scope"))?;
+        let config = iter_key_value(config)?;
+
+        let mut table = Vec::new();
+        for &(error, action) in config.iter() {
+            let error = match error {
+                "deadline_missed" => ApexErrorCode::DeadlineMissed,
+                "application_error" => ApexErrorCode::ApplicationError,
+                "numeric_error" => ApexErrorCode::NumericError,
+                "illegal_request" => ApexErrorCode::IllegalRequest,
+                "stack_overflow" => ApexErrorCode::StackOverflow,
+                "memory_violation" => ApexErrorCode::MemoryViolation,
+                "hardware_fault" => ApexErrorCode::HardwareFault,
+                "power_fail" => ApexErrorCode::PowerFail,
+                _ => return Err(format!("unrecognized error: {}", error)),
+            };
+            let action = match action {
+                "ignore" => HmActionConf::Ignore,
+                "stop_process" => HmActionConf::StopProcess,
+                "cold_start" => HmActionConf::ColdStart,
+                "warm_start" => HmActionConf::WarmStart,
+                "idle" => HmActionConf::Idle,
+                "reset" => HmActionConf::Reset,
+                _ => return Err(format!("unrecognized recovery action: {}", action)),
+            };
+            table.push(HmConf {
+                error: error as _,
+                action,
+            });
         }
 
-        let name: &str = parse_key_value(config.iter(), "name").unwrap();
-        let memory: usize =
-            parse_usize_from_proper_redix(parse_key_value(config.iter(), "memory").unwrap())
-                .unwrap();
-        let period: ApexSystemTime =
-            parse_time_from_proper_unit(parse_key_value(config.iter(), "period").unwrap()).unwrap();
-        let duration: ApexSystemTime =
-            parse_time_from_proper_unit(parse_key_value(config.iter(), "duration").unwrap())
-                .unwrap();
-        let num_cores: u32 = parse_key_value(config.iter(), "num_cores")
-            .unwrap()
-            .parse()
-            .unwrap();
-        let program: Option<&str> = parse_key_value(config.iter(), "program");
-        if nproc < num_cores as _ {
-            panic!("number of cores should be less than or equal to {nproc}, got {num_cores}");
+        match scope {
+            "module" => conf.hm_table.extend(table),
+            name => conf
+                .partitions
+                .iter_mut()
+                .find(|partition| partition.name == name)
+                .ok_or_else(|| format!("unrecognized partition: {}", name))?
+                .hm_table
+                .extend(table),
         }
-        Some(
-            Partition::new(&PartitionConfig {
-                name: name.try_into().unwrap(),
-                memory,
-                period,
-                duration,
-                num_cores,
-                partition_type: if is_user {
-                    PartitionTypeConfig::User(jrinx_uprog::find(program.unwrap()).unwrap())
-                } else {
-                    PartitionTypeConfig::Kern // TODO
-                },
-            })
-            .unwrap(),
-        )
+        Ok(())
     }
 }
 
-async fn scheduler(
-    args: &str,
-    partitions: &[Arc<Partition>],
-) -> Option<(usize, RuntimeSchedTable, Vec<Inspector>)> {
+async fn scheduler(args: &str, conf: &ModuleConf) -> Result<Option<ScheduleConf>, String> {
     if args == "help" {
         info!("To create a scheduler, you need to specify its major-frame size, cpu-id and schedule table");
         info!("Required (comma-seperated) arguments to create a schedule table entry:");
@@ -206,15 +411,27 @@ async fn scheduler(
         info!("                                             * the negative one indicates inf. duration");
         info!("                                             * the durations from entries of the same partition");
         info!("                                               should sum up to the partition's duration");
+        info!("                                               within each of its periods");
         info!("Optional (comma-seperated) arguments to create a schedule table entry:");
         info!("   init=<bool>                               Specify whether to create initial process");
         info!("                                             * default to false");
+        info!("(Comma-seperated) arguments to create an optional background entry:");
+        info!("   background=<str>                          Specify the partition running in the gaps");
+        info!("                                             * at most one background entry in a table");
+        info!("   init=<bool>                               Specify whether to create initial process");
+        info!("                                             * default to false");
         info!("Required entries to create a schedule table:");
         info!("   <entry>;<entry>;...                       Specify the entries of the schedule table");
         info!("Required major-frame size, cpu-id and schedule table to create a scheduler:");
         info!(
               "   <unsigned><unit>#<unsigned>//<table>      Specify the major-frame size, cpu-id and schedule table"
         );
+        info!("Optional module schedule the scheduler belongs to:");
+        info!(
+            "   <str>@<scheduler>                         Specify the module schedule by its name"
+        );
+        info!("                                             * default to 'default'");
+        info!("                                             * the module schedule specified first is the initial one");
         info!("Example:");
         info!("   --scheduler 2000#0//partition=ex1,offset=0,duration=1000,init=true;partition=ex2,offset=1000,duration=1000");
         info!("               ^~~^ ^  ^~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~^");
@@ -222,119 +439,107 @@ async fn scheduler(
         info!("               |    |  +-- schedule table");
         info!("               |    +----- cpu-id");
         info!("               +---------- major-frame size");
-        if !partitions.is_empty() {
-            info!("Available (created) partitions:");
-            partitions.iter().for_each(|partition| {
-                info!("   - {}", partition.name());
-            });
-        }
-        None
+        info!("   --scheduler alt@2000#0//partition=ex1,offset=0,duration=2000");
+        help_partitions(conf);
+        Ok(None)
     } else {
-        let mut pair = args.split('#');
-        let major_frame_size: ApexSystemTime =
-            parse_time_from_proper_unit(pair.next().unwrap()).unwrap();
-
-        let mut pair = pair.next().unwrap().split("//");
-        let cpu_id: usize = pair.next().unwrap().parse().unwrap();
-
-        let entries = pair.next().unwrap().split(';');
-        if pair.next().is_some() {
-            panic!("invalid argument: {:?}", pair);
-        }
-
-        let mut duration_left: BTreeMap<PartitionId, ApexSystemTime> = BTreeMap::new();
-        let mut table = Vec::new();
-        let mut inspectors = Vec::new();
-        for entry in entries {
-            let config = iter_key_value(entry).unwrap().collect::<Vec<_>>();
-            let partition_name: &str = parse_key_value(config.iter(), "partition").unwrap();
-            let offset: ApexSystemTime =
-                parse_time_from_proper_unit(parse_key_value(config.iter(), "offset").unwrap())
-                    .unwrap();
-            if major_frame_size != APEX_TIME_INFINITY
-                && (offset == APEX_TIME_INFINITY || offset >= major_frame_size)
-            {
-                panic!("invalid offset: {:#?}", offset);
+        let (name, args) = args.split_once('@').unwrap_or(("default", args));
+        let name = parse_name(name)?;
+
+        let (major_frame, args) = args
+            .split_once('#')
+            .ok_or_else(|| String::from("missing major-frame size"))?;
+        let major_frame = parse_time_from_proper_unit(major_frame)
+            .map_err(|err| format!("invalid major-frame size: {err}"))?;
+
+        let (cpu_id, entries) = args
+            .split_once("//")
+            .ok_or_else(|| String::from("missing cpu-id"))?;
+        let cpu_id: u32 = cpu_id
+            .parse()
+            .map_err(|err| format!("invalid cpu-id: {err}"))?;
+
+        let mut windows = Vec::new();
+        let mut background = None;
+        for entry in entries.split(';') {
+            let config = iter_key_value(entry)?;
+            if let Some(partition) = parse_key_value(config.iter(), "background") {
+                if background.is_some() {
+                    return Err(String::from("duplicated background"));
+                }
+                let init: bool = parse_key_value(config.iter(), "init")
+                    .unwrap_or("false")
+                    .parse()
+                    .map_err(|err| format!("invalid init: {err}"))?;
+                background = Some(BackgroundConf {
+                    partition: partition.into(),
+                    init,
+                });
+                continue;
             }
-            let duration: ApexSystemTime =
-                parse_time_from_proper_unit(parse_key_value(config.iter(), "duration").unwrap())
-                    .unwrap();
+
+            let partition = parse_required(&config, "partition")?;
+            let offset = parse_time_from_proper_unit(parse_required(&config, "offset")?)
+                .map_err(|err| format!("invalid offset: {err}"))?;
+            let duration = parse_time_from_proper_unit(parse_required(&config, "duration")?)
+                .map_err(|err| format!("invalid duration: {err}"))?;
             let init: bool = parse_key_value(config.iter(), "init")
                 .unwrap_or("false")
                 .parse()
-                .unwrap();
-
-            let partition = Partition::find_by_name(&partition_name.try_into().unwrap()).unwrap();
-
-            partition.assign_core(cpu_id as _).unwrap();
-
-            duration_left.insert(
-                partition.identifier(),
-                match duration_left
-                    .get(&partition.identifier())
-                    .unwrap_or(&partition.duration())
-                {
-                    left if *left == APEX_TIME_INFINITY => APEX_TIME_INFINITY,
-                    left if *left < duration => {
-                        panic!("invalid duration: {:#?} (left: {:#?})", duration, left)
-                    }
-                    left => ApexSystemTime::from(left - duration),
-                },
-            );
-
-            let inspector = partition.gen_inspector().unwrap();
-            if init {
-                let process = Process::new_init(partition.identifier()).unwrap();
-                let executor = process
-                    .gen_executor(ProcessRunner {
-                        syscall: jrinx_syscall::handle,
-                    })
-                    .unwrap();
-                inspector.register(executor).unwrap();
-            }
-            table.push(RuntimeSchedTableEntry {
-                inspector_id: inspector.id(),
-                offset: time_as_duration(offset),
-                period: time_as_duration(partition.period()),
-                duration: time_as_duration(duration),
-            });
-            inspectors.push(inspector);
-        }
+                .map_err(|err| format!("invalid init: {err}"))?;
 
-        for (id, &left) in duration_left.iter() {
-            if left != 0 && left != APEX_TIME_INFINITY {
-                let partition = Partition::find_by_id(*id).unwrap();
-                let name = partition.name();
-                panic!(
-                    "duration left for partition '{:?}' is not zero: {:#?}",
-                    name, left
-                );
-            }
+            windows.push(WindowConf {
+                partition: partition.into(),
+                offset,
+                duration,
+                init,
+            });
         }
+        windows.sort_by_key(|window| window.offset);
 
-        Some((
+        Ok(Some(ScheduleConf {
+            name,
             cpu_id,
-            RuntimeSchedTable::new(time_as_duration(major_frame_size), table.into_iter()).unwrap(),
-            inspectors,
-        ))
+            major_frame,
+            windows,
+            background,
+        }))
     }
 }
 
-fn iter_key_value(args: &str) -> Result<impl Iterator<Item = (&str, &str)>, String> {
-    let result = args.split(',').map(|a| {
-        let mut a = a.split('=');
-        let key = a.next().unwrap();
-        let val = a.next().unwrap();
-        if a.next().is_some() {
-            Err(format!("invalid argument: {:?}", a))
-        } else {
-            Ok((key, val))
-        }
-    });
-    for r in result.clone() {
-        r?;
+fn help_partitions(conf: &ModuleConf) {
+    if !conf.partitions.is_empty() {
+        info!("Available (created) partitions:");
+        conf.partitions.iter().for_each(|partition| {
+            info!("   - {}", partition.name);
+        });
     }
-    Ok(result.map(|r| r.unwrap()))
+}
+
+/// Splits `args` of form `<prop>//<config>`, returning whether the property is `prop_true`.
+fn parse_prop_config<'a>(
+    args: &'a str,
+    prop_false: &str,
+    prop_true: &str,
+) -> Result<(bool, KeyValues<'a>), String> {
+    let (prop, config) = args
+        .split_once("//")
+        .ok_or_else(|| format!("missing {prop_false}/{prop_true} property"))?;
+    let prop = match prop {
+        prop if prop == prop_false => false,
+        prop if prop == prop_true => true,
+        _ => return Err(format!("invalid property: {:?}", prop)),
+    };
+    Ok((prop, iter_key_value(config)?))
+}
+
+fn iter_key_value(args: &str) -> Result<KeyValues, String> {
+    args.split(',')
+        .map(|a| match a.split_once('=') {
+            Some((key, val)) if !val.contains('=') => Ok((key, val)),
+            _ => Err(format!("invalid argument: {:?}", a)),
+        })
+        .collect()
 }
 
 fn parse_key_value<'a: 'b, 'b>(
@@ -344,6 +549,25 @@ fn parse_key_value<'a: 'b, 'b>(
     config.find(|(k, _)| *k == key).map(|(_, v)| *v)
 }
 
+fn parse_required<'a>(config: &[(&'a str, &'a str)], key: &str) -> Result<&'a str, String> {
+    parse_key_value(config.iter(), key).ok_or_else(|| format!("missing argument: {key}"))
+}
+
+fn parse_name(s: &str) -> Result<String, String> {
+    ApexName::try_from(s).map_err(|_| format!("invalid name: {:?}", s))?;
+    Ok(s.into())
+}
+
+fn parse_channel_port(s: &str) -> Result<PortConf, String> {
+    let (partition, port) = s
+        .split_once(':')
+        .ok_or_else(|| format!("invalid port: {:?}", s))?;
+    Ok(PortConf {
+        partition: parse_name(partition)?,
+        port: parse_name(port)?,
+    })
+}
+
 fn parse_usize_from_proper_redix(s: &str) -> Result<usize, ParseIntError> {
     let (radix, s) = match s {
         s if s.starts_with("0x")
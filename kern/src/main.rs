@@ -9,7 +9,7 @@
 
 use arch::BootInfo;
 use jrinx_hal::{Cpu, Hal};
-use jrinx_multitask::runtime::{self, Runtime};
+use jrinx_multitask::runtime::{self, Runtime, SYNC_DATUM_DELAY};
 use spin::Mutex;
 
 extern crate alloc;
@@ -22,7 +22,9 @@ extern crate jrinx_hal;
 
 mod arch;
 mod bootargs;
+mod modconf;
 mod panic;
+mod part;
 mod test;
 
 enum BootState {
@@ -118,8 +120,13 @@ async fn primary_task() {
         core::hint::spin_loop();
     }
 
+    jrinx_a653::health::init();
+    jrinx_a653::process::init();
+
     bootargs::execute().await;
 
+    Runtime::synchronize(hal!().cpu().get_time() + SYNC_DATUM_DELAY);
+
     boot_set_finished();
 
     while let BootState::Finished(count) = *BOOT_STATE.lock() {
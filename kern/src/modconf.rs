@@ -0,0 +1,765 @@
+use alloc::{
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
+    format,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use jrinx_a653::{
+    channel::{Channel, ChannelConfig, ChannelPort, ChannelTypeConfig},
+    health::{self, RecoveryAction, RecoveryTable},
+    partition::{Partition, PartitionConfig, PartitionId, PartitionTypeConfig},
+    process::{Process, ProcessRunner},
+    schedule::{Schedule, ScheduleConfig},
+};
+use jrinx_apex::*;
+use jrinx_error::InternalError;
+use jrinx_hal::{Cpu, Hal};
+use jrinx_modconf::*;
+use jrinx_multitask::{
+    inspector::{Inspector, InspectorId},
+    runtime::{Runtime, RuntimeSchedTable, RuntimeSchedTableEntry},
+};
+
+static MODCONF: &[u8] = include_bytes!(core::env!("MODCONF_PATH"));
+
+/// An item of the module configuration that a [`ConfError`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum ConfItem {
+    Module,
+    Partition(usize),
+    Channel(usize),
+    Schedule(usize),
+}
+
+#[derive(Debug)]
+pub(super) struct ConfError {
+    pub item: ConfItem,
+    pub msg: String,
+}
+
+/// Decodes the embedded module configuration, which is generated by `cargo conf`.
+pub(super) fn embedded() -> Result<ModuleConf, DecodeError> {
+    ModuleConf::decode(MODCONF)
+}
+
+/// Describes `item` of `conf` for the configuration not coming from the boot arguments.
+pub(super) fn describe(conf: &ModuleConf, item: ConfItem) -> String {
+    match item {
+        ConfItem::Module => "module".into(),
+        ConfItem::Partition(i) => format!("partition '{}'", conf.partitions[i].name),
+        ConfItem::Channel(i) => format!("channel '{}'", conf.channels[i].name),
+        ConfItem::Schedule(i) => format!(
+            "schedule '{}' of cpu#{}",
+            conf.schedules[i].name, conf.schedules[i].cpu_id
+        ),
+    }
+}
+
+/// Checks `conf` as a whole, returning all the violations found.
+///
+/// A configuration passing the validation is safe to [`build`].
+pub(super) fn validate(conf: &ModuleConf) -> Vec<ConfError> {
+    let mut validator = Validator {
+        conf,
+        nproc: hal!().cpu().nproc_valid(),
+        errors: Vec::new(),
+    };
+    validator.partitions();
+    validator.channels();
+    validator.schedules();
+    validator.hm(ConfItem::Module, &conf.hm_table, true);
+    validator.errors
+}
+
+/// Creates the partitions, channels, recovery tables and module schedules of `conf`, where the
+/// module schedule declared first is enacted.
+pub(super) fn build(conf: &ModuleConf) -> Result<(), ConfError> {
+    for (i, partition_conf) in conf.partitions.iter().enumerate() {
+        partition(partition_conf).map_err(|err| ConfError {
+            item: ConfItem::Partition(i),
+            msg: format!("failed to create partition: {:?}", err),
+        })?;
+    }
+
+    for (i, channel_conf) in conf.channels.iter().enumerate() {
+        channel(channel_conf).map_err(|err| ConfError {
+            item: ConfItem::Channel(i),
+            msg: format!("failed to create channel: {:?}", err),
+        })?;
+    }
+
+    let mut table = health::module_recovery_table();
+    apply_hm(&mut table, &conf.hm_table);
+    health::set_module_recovery_table(table);
+
+    if let Some(threshold) = conf.window_threshold {
+        Runtime::set_window_log_threshold(time_as_duration(conf_time(threshold)));
+    }
+
+    // all the module schedules share an inspector for each partition on each cpu
+    let mut inspectors = BTreeMap::new();
+    for (i, schedule_conf) in conf.schedules.iter().enumerate() {
+        inspector(schedule_conf, &mut inspectors).map_err(|err| ConfError {
+            item: ConfItem::Schedule(i),
+            msg: format!("failed to create scheduler: {:?}", err),
+        })?;
+    }
+
+    let mut inspector_ids = BTreeMap::new();
+    for ((partition_id, cpu_id), inspector) in inspectors {
+        inspector_ids.insert((partition_id, cpu_id), inspector.id());
+        Runtime::with_spec_cpu(cpu_id, |rt| rt.register(inspector))
+            .and_then(|result| result)
+            .map_err(|err| ConfError {
+                item: ConfItem::Module,
+                msg: format!("failed to register inspector on cpu#{cpu_id}: {:?}", err),
+            })?;
+    }
+
+    for (i, schedule_conf) in conf.schedules.iter().enumerate() {
+        if conf.schedules[..i]
+            .iter()
+            .any(|other| other.name == schedule_conf.name)
+        {
+            continue;
+        }
+        let schedule =
+            schedule(conf, &schedule_conf.name, &inspector_ids).map_err(|err| ConfError {
+                item: ConfItem::Schedule(i),
+                msg: format!("failed to create module schedule: {:?}", err),
+            })?;
+        if i == 0 {
+            schedule.enact().map_err(|err| ConfError {
+                item: ConfItem::Schedule(i),
+                msg: format!("failed to enact module schedule: {:?}", err),
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+fn partition(conf: &PartitionConf) -> jrinx_error::Result<()> {
+    let partition = Partition::new(PartitionConfig {
+        name: conf.name.as_str().try_into().unwrap(),
+        memory: conf.memory as _,
+        period: conf_time(conf.period),
+        duration: conf_time(conf.duration),
+        num_cores: conf.num_cores,
+        partition_type: match &conf.kind {
+            PartitionKindConf::Kern { entry } => PartitionTypeConfig::Kern(ApexSystemAddress::of(
+                jrinx_partdef::find(entry).ok_or(InternalError::InvalidPartitionConfig)?,
+            )),
+            PartitionKindConf::User { program } => {
+                PartitionTypeConfig::User(jrinx_uprog::find(program)?)
+            }
+        },
+    })?;
+
+    let mut table = partition.recovery_table();
+    apply_hm(&mut table, &conf.hm_table);
+    partition.set_recovery_table(table);
+    Ok(())
+}
+
+fn channel(conf: &ChannelConf) -> jrinx_error::Result<()> {
+    let port = |conf: &PortConf| ChannelPort {
+        partition: conf.partition.as_str().try_into().unwrap(),
+        port: conf.port.as_str().try_into().unwrap(),
+    };
+
+    Channel::new(&ChannelConfig {
+        name: conf.name.as_str().try_into().unwrap(),
+        max_message_size: conf.max_message_size,
+        source: port(&conf.source),
+        destinations: conf.destinations.iter().map(port).collect(),
+        channel_type: match conf.kind {
+            ChannelKindConf::Sampling => ChannelTypeConfig::Sampling,
+            ChannelKindConf::Queuing { max_nb_message } => {
+                ChannelTypeConfig::Queuing { max_nb_message }
+            }
+        },
+    })?;
+    Ok(())
+}
+
+/// Creates the inspectors of the partitions scheduled by `conf` (including its background)
+/// absent from `inspectors`.
+fn inspector(
+    conf: &ScheduleConf,
+    inspectors: &mut BTreeMap<(PartitionId, usize), Inspector>,
+) -> jrinx_error::Result<()> {
+    let cpu_id = conf.cpu_id as usize;
+
+    for window in conf.windows.iter() {
+        let partition = Partition::find_by_name(&window.partition.as_str().try_into().unwrap())
+            .ok_or(InternalError::InvalidPartitionConfig)?;
+
+        partition.assign_core(cpu_id as _)?;
+
+        let inspector = match inspectors.entry((partition.identifier(), cpu_id)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(partition.gen_inspector()?),
+        };
+        if window.init {
+            register_init(&partition, cpu_id, inspector)?;
+        }
+    }
+
+    if let Some(background) = &conf.background {
+        let partition = Partition::find_by_name(&background.partition.as_str().try_into().unwrap())
+            .ok_or(InternalError::InvalidPartitionConfig)?;
+
+        partition.assign_core(cpu_id as _)?;
+
+        let inspector = match inspectors.entry((partition.identifier(), cpu_id)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(partition.gen_inspector()?),
+        };
+        if background.init {
+            register_init(&partition, cpu_id, inspector)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates the module schedule `name` from the schedules of all cpus sharing the name.
+fn schedule(
+    conf: &ModuleConf,
+    name: &str,
+    inspector_ids: &BTreeMap<(PartitionId, usize), InspectorId>,
+) -> jrinx_error::Result<Arc<Schedule>> {
+    let mut sched_tables = BTreeMap::new();
+
+    for schedule_conf in conf.schedules.iter().filter(|conf| conf.name == name) {
+        let cpu_id = schedule_conf.cpu_id as usize;
+
+        let mut table = Vec::new();
+        for window in schedule_conf.windows.iter() {
+            let partition = Partition::find_by_name(&window.partition.as_str().try_into().unwrap())
+                .ok_or(InternalError::InvalidPartitionConfig)?;
+
+            table.push(RuntimeSchedTableEntry {
+                inspector_id: inspector_ids[&(partition.identifier(), cpu_id)],
+                offset: time_as_duration(conf_time(window.offset)),
+                period: time_as_duration(partition.period()),
+                duration: time_as_duration(conf_time(window.duration)),
+            });
+        }
+
+        let mut sched_table = RuntimeSchedTable::new(
+            time_as_duration(conf_time(schedule_conf.major_frame)),
+            table.into_iter(),
+        )?;
+        if let Some(background) = &schedule_conf.background {
+            let partition =
+                Partition::find_by_name(&background.partition.as_str().try_into().unwrap())
+                    .ok_or(InternalError::InvalidPartitionConfig)?;
+            sched_table.set_background(Some(inspector_ids[&(partition.identifier(), cpu_id)]));
+        }
+        sched_tables.insert(cpu_id, sched_table);
+    }
+
+    Schedule::new(ScheduleConfig {
+        name: name.try_into().unwrap(),
+        sched_tables,
+    })
+}
+
+/// Creates the initial process of `partition`, which runs on `cpu_id` under `inspector`.
+fn register_init(
+    partition: &Partition,
+    cpu_id: usize,
+    inspector: &Inspector,
+) -> jrinx_error::Result<()> {
+    let process = Process::new_init(partition.identifier())?;
+    let executor = process.gen_executor(ProcessRunner {
+        syscall: jrinx_syscall::handle,
+    })?;
+    process.set_executor(Some((cpu_id, executor.id())));
+    inspector.register(executor)
+}
+
+fn apply_hm(table: &mut RecoveryTable, conf: &[HmConf]) {
+    for hm in conf.iter() {
+        let action = match hm.action {
+            HmActionConf::Ignore => RecoveryAction::Ignore,
+            HmActionConf::StopProcess => RecoveryAction::StopProcess,
+            HmActionConf::ColdStart => RecoveryAction::ColdStart,
+            HmActionConf::WarmStart => RecoveryAction::WarmStart,
+            HmActionConf::Idle => RecoveryAction::Idle,
+            HmActionConf::Reset => RecoveryAction::Reset,
+        };
+        table.set_action(hm.error.try_into().unwrap(), action);
+    }
+}
+
+fn conf_time(time: ModconfTime) -> ApexSystemTime {
+    if time < 0 {
+        APEX_TIME_INFINITY
+    } else {
+        time
+    }
+}
+
+struct Time(ModconfTime);
+
+impl Display for Time {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.0 < 0 {
+            write!(f, "inf.")
+        } else {
+            write!(f, "{}ns", self.0)
+        }
+    }
+}
+
+struct Validator<'a> {
+    conf: &'a ModuleConf,
+    nproc: usize,
+    errors: Vec<ConfError>,
+}
+
+impl Validator<'_> {
+    fn partitions(&mut self) {
+        let conf = self.conf;
+        let mut memory: u64 = 0;
+
+        for (i, partition) in conf.partitions.iter().enumerate() {
+            let item = ConfItem::Partition(i);
+
+            self.name(item, &partition.name);
+            if conf.partitions[..i]
+                .iter()
+                .any(|other| other.name == partition.name)
+            {
+                self.report(item, format!("duplicated partition: {}", partition.name));
+            }
+
+            match &partition.kind {
+                PartitionKindConf::Kern { entry } => {
+                    if jrinx_partdef::find(entry).is_none() {
+                        self.report(
+                            item,
+                            format!("unrecognized kernel partition entry: {}", entry),
+                        );
+                    }
+                }
+                PartitionKindConf::User { program } => {
+                    if jrinx_uprog::find(program).is_err() {
+                        self.report(item, format!("unrecognized user program: {}", program));
+                    }
+                }
+            }
+
+            if partition.num_cores == 0 || partition.num_cores as usize > self.nproc {
+                self.report(
+                    item,
+                    format!(
+                        "number of cores should be within 1 to {}, got {}",
+                        self.nproc, partition.num_cores
+                    ),
+                );
+            }
+
+            if partition.period == 0 {
+                self.report(item, "period should be positive or infinite");
+            }
+            if partition.duration == 0
+                || partition.period > 0
+                    && (partition.duration < 0 || partition.duration > partition.period)
+            {
+                self.report(
+                    item,
+                    format!(
+                        "duration {} should be positive and within period {}",
+                        Time(partition.duration),
+                        Time(partition.period)
+                    ),
+                );
+            }
+
+            self.hm(item, &partition.hm_table, false);
+
+            memory = memory.saturating_add(partition.memory);
+        }
+
+        let available = jrinx_heap::free_size() as u64;
+        if memory > available {
+            self.report(
+                ConfItem::Module,
+                format!(
+                    "partitions require {memory} bytes of memory, but only {available} bytes are available"
+                ),
+            );
+        }
+    }
+
+    fn channels(&mut self) {
+        let conf = self.conf;
+        let mut connected = BTreeSet::new();
+
+        for (i, channel) in conf.channels.iter().enumerate() {
+            let item = ConfItem::Channel(i);
+
+            self.name(item, &channel.name);
+            if conf.channels[..i]
+                .iter()
+                .any(|other| other.name == channel.name)
+            {
+                self.report(item, format!("duplicated channel: {}", channel.name));
+            }
+
+            if channel.max_message_size == 0 {
+                self.report(item, "max message size should be positive");
+            }
+            match channel.kind {
+                ChannelKindConf::Sampling if channel.destinations.is_empty() => {
+                    self.report(item, "a sampling channel should have destinations");
+                }
+                ChannelKindConf::Queuing { max_nb_message } => {
+                    if channel.destinations.len() != 1 {
+                        self.report(
+                            item,
+                            "a queuing channel should have exactly one destination",
+                        );
+                    }
+                    if max_nb_message == 0 {
+                        self.report(item, "max number of messages should be positive");
+                    }
+                }
+                _ => {}
+            }
+
+            for port in core::iter::once(&channel.source).chain(channel.destinations.iter()) {
+                if self.find_partition(&port.partition).is_none() {
+                    self.report(item, format!("unrecognized partition: {}", port.partition));
+                }
+                self.name(item, &port.port);
+                if !connected.insert((&port.partition, &port.port)) {
+                    self.report(
+                        item,
+                        format!("port already connected: {}:{}", port.partition, port.port),
+                    );
+                }
+            }
+        }
+    }
+
+    fn schedules(&mut self) {
+        let conf = self.conf;
+        let mut cores: BTreeMap<usize, BTreeMap<&str, BTreeSet<u32>>> = BTreeMap::new();
+        let mut cpus: Vec<(&str, BTreeSet<u32>)> = Vec::new();
+        let mut inits: BTreeMap<usize, usize> = BTreeMap::new();
+
+        for (i, schedule) in conf.schedules.iter().enumerate() {
+            let item = ConfItem::Schedule(i);
+            let major_frame = schedule.major_frame;
+
+            self.name(item, &schedule.name);
+            match cpus.iter_mut().find(|(name, _)| *name == schedule.name) {
+                Some((_, cpus)) => {
+                    cpus.insert(schedule.cpu_id);
+                }
+                None => cpus.push((&schedule.name, BTreeSet::from([schedule.cpu_id]))),
+            }
+
+            if schedule.cpu_id as usize >= self.nproc {
+                self.report(
+                    item,
+                    format!(
+                        "cpu-id should be less than {}, got {}",
+                        self.nproc, schedule.cpu_id
+                    ),
+                );
+            }
+            if conf.schedules[..i]
+                .iter()
+                .any(|other| other.name == schedule.name && other.cpu_id == schedule.cpu_id)
+            {
+                self.report(
+                    item,
+                    format!("duplicated scheduler on cpu#{}", schedule.cpu_id),
+                );
+            }
+            if let Some(other) = conf.schedules[..i]
+                .iter()
+                .find(|other| other.name == schedule.name && other.major_frame != major_frame)
+            {
+                self.report(
+                    item,
+                    format!(
+                        "major frame {} differs from major frame {} on cpu#{}",
+                        Time(major_frame),
+                        Time(other.major_frame),
+                        other.cpu_id
+                    ),
+                );
+            }
+            if major_frame == 0 {
+                self.report(item, "major frame should be positive or infinite");
+                continue;
+            }
+
+            let mut slices: BTreeMap<usize, BTreeSet<(ModconfTime, ModconfTime)>> = BTreeMap::new();
+            for window in schedule.windows.iter() {
+                let Some(p) = self.find_partition(&window.partition) else {
+                    self.report(
+                        item,
+                        format!("unrecognized partition: {}", window.partition),
+                    );
+                    continue;
+                };
+                if window.offset < 0 || major_frame > 0 && window.offset >= major_frame {
+                    self.report(
+                        item,
+                        format!(
+                            "offset {} of '{}' is out of major frame {}",
+                            Time(window.offset),
+                            window.partition,
+                            Time(major_frame)
+                        ),
+                    );
+                    continue;
+                }
+                if window.duration == 0
+                    || major_frame > 0
+                        && (window.duration < 0 || window.offset + window.duration > major_frame)
+                {
+                    self.report(
+                        item,
+                        format!(
+                            "duration {} of '{}' at offset {} is out of major frame {}",
+                            Time(window.duration),
+                            window.partition,
+                            Time(window.offset),
+                            Time(major_frame)
+                        ),
+                    );
+                    continue;
+                }
+
+                cores
+                    .entry(p)
+                    .or_default()
+                    .entry(&schedule.name)
+                    .or_default()
+                    .insert(schedule.cpu_id);
+                if window.init {
+                    *inits.entry(p).or_default() += 1;
+                }
+                slices
+                    .entry(p)
+                    .or_default()
+                    .insert((window.offset, window.duration));
+            }
+
+            let mut windows = schedule.windows.iter().collect::<Vec<_>>();
+            windows.sort_by_key(|window| window.offset);
+            for pair in windows.windows(2) {
+                let (prev, next) = (pair[0], pair[1]);
+                if prev.duration < 0 || prev.offset + prev.duration > next.offset {
+                    self.report(
+                        item,
+                        format!(
+                            "window of '{}' at offset {} overlaps window of '{}' at offset {}",
+                            prev.partition,
+                            Time(prev.offset),
+                            next.partition,
+                            Time(next.offset)
+                        ),
+                    );
+                }
+            }
+
+            if schedule.windows.is_empty() {
+                self.report(item, "scheduler should have at least one window");
+            }
+
+            if let Some(background) = &schedule.background {
+                match self.find_partition(&background.partition) {
+                    Some(p) => {
+                        cores
+                            .entry(p)
+                            .or_default()
+                            .entry(&schedule.name)
+                            .or_default()
+                            .insert(schedule.cpu_id);
+                        if background.init {
+                            *inits.entry(p).or_default() += 1;
+                        }
+                    }
+                    None => self.report(
+                        item,
+                        format!(
+                            "unrecognized background partition: {}",
+                            background.partition
+                        ),
+                    ),
+                }
+            }
+
+            for (p, slices) in slices {
+                let partition = &conf.partitions[p];
+                let period = partition.period;
+
+                let base = if period < 0 {
+                    if major_frame > 0 {
+                        self.report(
+                            item,
+                            format!(
+                                "inf. period of '{}' does not match major frame {}",
+                                partition.name,
+                                Time(major_frame)
+                            ),
+                        );
+                        continue;
+                    }
+                    slices.iter().copied().collect::<Vec<_>>()
+                } else if period == 0 || major_frame < 0 || major_frame % period != 0 {
+                    self.report(
+                        item,
+                        format!(
+                            "period {} of '{}' does not divide major frame {}",
+                            Time(period),
+                            partition.name,
+                            Time(major_frame)
+                        ),
+                    );
+                    continue;
+                } else {
+                    let base = slices
+                        .iter()
+                        .filter(|&&(offset, _)| offset < period)
+                        .copied()
+                        .collect::<Vec<_>>();
+                    let repeated = (0..major_frame / period)
+                        .flat_map(|k| base.iter().map(move |&(o, d)| (o + k * period, d)))
+                        .collect::<BTreeSet<_>>();
+                    if repeated != slices {
+                        self.report(
+                            item,
+                            format!(
+                                "windows of '{}' do not recur every period {}",
+                                partition.name,
+                                Time(period)
+                            ),
+                        );
+                        continue;
+                    }
+                    base
+                };
+
+                if partition.duration > 0 {
+                    let total = base.iter().try_fold(0, |total: ModconfTime, &(_, d)| {
+                        (d >= 0).then_some(total + d)
+                    });
+                    if total != Some(partition.duration) {
+                        self.report(
+                            item,
+                            format!(
+                                "windows of '{}' sum to {} within a period, expected duration {}",
+                                partition.name,
+                                Time(total.unwrap_or(-1)),
+                                Time(partition.duration)
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        if let Some(((initial, initial_cpus), others)) = cpus.split_first() {
+            for (name, cpus) in others {
+                if cpus != initial_cpus {
+                    self.report(
+                        ConfItem::Module,
+                        format!(
+                            "module schedule '{}' runs on cpus {:?}, but '{}' runs on {:?}",
+                            name, cpus, initial, initial_cpus
+                        ),
+                    );
+                }
+            }
+        }
+
+        for (p, partition) in conf.partitions.iter().enumerate() {
+            let item = ConfItem::Partition(p);
+            let scheduled = cores.remove(&p).unwrap_or_default();
+            let all = scheduled.values().flatten().collect::<BTreeSet<_>>();
+            if scheduled.values().any(|cores| cores.len() != all.len()) {
+                self.report(
+                    item,
+                    "the partition is scheduled on different cpus by module schedules",
+                );
+            }
+            let num_cores = all.len();
+            if num_cores != partition.num_cores as usize {
+                self.report(
+                    item,
+                    format!(
+                        "number of cores is {}, but the partition is scheduled on {} cpu(s)",
+                        partition.num_cores, num_cores
+                    ),
+                );
+            }
+            if inits.get(&p).is_some_and(|&count| count > 1) {
+                self.report(
+                    item,
+                    "initial process should be created in at most one window",
+                );
+            }
+        }
+    }
+
+    fn hm(&mut self, item: ConfItem, table: &[HmConf], is_module: bool) {
+        for (i, hm) in table.iter().enumerate() {
+            let Ok(error) = ApexErrorCode::try_from(hm.error) else {
+                self.report(item, format!("unrecognized error: {}", hm.error));
+                continue;
+            };
+            if table[..i].iter().any(|other| other.error == hm.error) {
+                self.report(item, format!("duplicated recovery action for {:?}", error));
+            }
+            if is_module && !matches!(hm.action, HmActionConf::Ignore | HmActionConf::Reset) {
+                self.report(
+                    item,
+                    format!("invalid recovery action for module: {:?}", hm.action),
+                );
+            }
+            if !is_module && hm.action == HmActionConf::Reset {
+                self.report(
+                    item,
+                    format!("invalid recovery action for partition: {:?}", hm.action),
+                );
+            }
+        }
+    }
+
+    fn name(&mut self, item: ConfItem, name: &str) {
+        if name.is_empty() || ApexName::try_from(name).is_err() {
+            self.report(
+                item,
+                format!("name should be within 1 to {APEX_NAME_MAX_LEN} bytes: {name:?}"),
+            );
+        }
+    }
+
+    fn find_partition(&self, name: &str) -> Option<usize> {
+        self.conf
+            .partitions
+            .iter()
+            .position(|partition| partition.name == name)
+    }
+
+    fn report(&mut self, item: ConfItem, msg: impl Into<String>) {
+        self.errors.push(ConfError {
+            item,
+            msg: msg.into(),
+        });
+    }
+}
@@ -0,0 +1,14 @@
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+
+/// Occupies the windows of the partition without doing anything.
+#[partdef]
+extern "C" fn idle() {
+    if let Err(err) = KernApex.set_partition_mode(ApexOperatingMode::Normal) {
+        warn!("failed to start idle partition: {:?}", err);
+    }
+    loop {
+        let _ = KernApex.suspend_self(APEX_TIME_INFINITY);
+    }
+}
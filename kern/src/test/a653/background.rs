@@ -0,0 +1,93 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_hal::{Cpu, Hal};
+use jrinx_multitask::runtime::Runtime;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::Mutex;
+
+use super::MS;
+
+const FRAME: ApexSystemTime = 20 * MS;
+const WINDOW: ApexSystemTime = 10 * MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    /// Whether the background process spun only in the gaps after the window.
+    Spun(bool),
+    /// Whether the gap time of the core is spent in both the background and idling.
+    Gap(Result<(bool, bool), ApexReturnCode>),
+}
+
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Runs a background partition in the gaps between the windows, which is preempted at each
+/// window start, and accounts the gap time it takes.
+#[testdef]
+fn test() {
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Spun(true),
+            Step::Gap(Err(InvalidParam)),
+            Step::Gap(Ok((true, true))),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_foreground() {
+    super::start("checker", checker, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+#[partdef]
+extern "C" fn a653_background() {
+    super::start("spinner", spinner, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn gap(core_id: ApexProcessorCoreId) -> Step {
+    Step::Gap(KernApex.get_gap_status(core_id).map(|status| {
+        (
+            status.background_time >= 2 * (FRAME - WINDOW),
+            status.idle_time > 0,
+        )
+    }))
+}
+
+extern "C" fn checker() {
+    // the spinner is done in the gaps of the first frames, and the core idles since then
+    KernApex.timed_wait(6 * FRAME).unwrap();
+    super::record(&TRACE, gap(hal!().cpu().nproc() as _));
+    super::record(&TRACE, gap(0));
+
+    super::finish();
+}
+
+extern "C" fn spinner() {
+    let start = KernApex.get_time().unwrap();
+    let mut in_gap = true;
+    while KernApex.get_time().unwrap() - start < 3 * FRAME {
+        let now = hal!().cpu().get_time();
+        let next = Runtime::with_current(|rt| rt.next_frame_start(now)).unwrap();
+        let offset = (duration_as_time(now) - duration_as_time(next)).rem_euclid(FRAME);
+        // leaves some slack at the window boundaries, where the spinner is preempted
+        if offset > MS && offset < WINDOW - MS {
+            in_gap = false;
+        }
+        core::hint::spin_loop();
+    }
+    super::record(&TRACE, Step::Spun(in_gap));
+}
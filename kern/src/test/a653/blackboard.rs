@@ -0,0 +1,118 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Read(&'static str, Result<Vec<ApexByte>, ApexReturnCode>),
+    Elapsed(bool),
+    Waiting(ApexWaitingRange),
+    Display(Result<(), ApexReturnCode>),
+    Empty(ApexEmptyIndicator),
+}
+
+static BLACKBOARD: Once<ApexBlackboardId> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Displays a message on a blackboard, which wakes all the processes reading it at once.
+#[testdef]
+fn test() {
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Read("reader1", Err(NotAvailable)),
+            Step::Read("reader1", Err(TimedOut)),
+            Step::Elapsed(true),
+            Step::Waiting(2),
+            // both woken before the displayer returns, in the order of their priorities
+            Step::Read("reader1", Ok(b"abc".to_vec())),
+            Step::Read("reader2", Ok(b"abc".to_vec())),
+            Step::Display(Ok(())),
+            Step::Empty(ApexEmptyIndicator::Empty),
+            Step::Read("displayer", Err(NotAvailable)),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_blackboard() {
+    BLACKBOARD.call_once(|| {
+        KernApex
+            .create_blackboard(&"bb".try_into().unwrap(), 8)
+            .unwrap()
+    });
+    super::start("reader1", reader1, 3);
+    super::start("reader2", reader2, 2);
+    super::start("displayer", displayer, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn read(reader: &'static str, timeout: ApexSystemTime) -> Step {
+    let &blackboard = BLACKBOARD.get().unwrap();
+    let mut message = [0; 8];
+    Step::Read(
+        reader,
+        KernApex
+            .read_blackboard(blackboard, timeout, &mut message)
+            .map(|length| message[..length as usize].to_vec()),
+    )
+}
+
+extern "C" fn reader1() {
+    super::record(&TRACE, read("reader1", 0));
+    let (step, elapsed) = super::timed(10 * MS, |timeout| read("reader1", timeout));
+    super::record(&TRACE, step);
+    super::record(&TRACE, Step::Elapsed(elapsed));
+
+    super::record(&TRACE, read("reader1", APEX_TIME_INFINITY));
+}
+
+extern "C" fn reader2() {
+    super::record(&TRACE, read("reader2", APEX_TIME_INFINITY));
+}
+
+extern "C" fn displayer() {
+    let &blackboard = BLACKBOARD.get().unwrap();
+
+    KernApex.timed_wait(20 * MS).unwrap();
+    super::record(
+        &TRACE,
+        Step::Waiting(
+            KernApex
+                .get_blackboard_status(blackboard)
+                .unwrap()
+                .waiting_processes,
+        ),
+    );
+    super::record(
+        &TRACE,
+        Step::Display(KernApex.display_blackboard(blackboard, b"abc")),
+    );
+
+    KernApex.clear_blackboard(blackboard).unwrap();
+    super::record(
+        &TRACE,
+        Step::Empty(
+            KernApex
+                .get_blackboard_status(blackboard)
+                .unwrap()
+                .empty_indicator,
+        ),
+    );
+    super::record(&TRACE, read("displayer", 0));
+
+    super::finish();
+}
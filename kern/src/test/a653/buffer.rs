@@ -0,0 +1,108 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Receive(Result<Vec<ApexByte>, ApexReturnCode>),
+    Send(Result<(), ApexReturnCode>),
+    Elapsed(bool),
+    Status(ApexMessageRange, ApexWaitingRange),
+}
+
+static BUFFER: Once<ApexBufferId> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Sends and receives messages through a buffer of a single message.
+#[testdef]
+fn test() {
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Receive(Err(NotAvailable)),
+            Step::Receive(Err(TimedOut)),
+            Step::Elapsed(true),
+            Step::Send(Ok(())),
+            Step::Send(Err(NotAvailable)),
+            Step::Send(Err(TimedOut)),
+            Step::Elapsed(true),
+            // woken at once when the receiver takes the first message
+            Step::Send(Ok(())),
+            Step::Receive(Ok(b"a".to_vec())),
+            Step::Status(1, 0),
+            Step::Receive(Ok(b"b".to_vec())),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_buffer() {
+    BUFFER.call_once(|| {
+        KernApex
+            .create_buffer(&"buf".try_into().unwrap(), 8, 1, ApexQueueDiscipline::Fifo)
+            .unwrap()
+    });
+    super::start("sender", sender, 2);
+    super::start("receiver", receiver, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn receive(buffer: ApexBufferId, timeout: ApexSystemTime) -> Step {
+    let mut message = [0; 8];
+    Step::Receive(
+        KernApex
+            .receive_buffer(buffer, timeout, &mut message)
+            .map(|length| message[..length as usize].to_vec()),
+    )
+}
+
+extern "C" fn sender() {
+    let &buffer = BUFFER.get().unwrap();
+
+    super::record(&TRACE, receive(buffer, 0));
+    let (step, elapsed) = super::timed(10 * MS, |timeout| receive(buffer, timeout));
+    super::record(&TRACE, step);
+    super::record(&TRACE, Step::Elapsed(elapsed));
+
+    super::record(&TRACE, Step::Send(KernApex.send_buffer(buffer, b"a", 0)));
+    super::record(&TRACE, Step::Send(KernApex.send_buffer(buffer, b"b", 0)));
+    let (ret, elapsed) = super::timed(10 * MS, |timeout| {
+        KernApex.send_buffer(buffer, b"b", timeout)
+    });
+    super::record(&TRACE, Step::Send(ret));
+    super::record(&TRACE, Step::Elapsed(elapsed));
+
+    super::record(
+        &TRACE,
+        Step::Send(KernApex.send_buffer(buffer, b"b", APEX_TIME_INFINITY)),
+    );
+    // lets the receiver record its message
+    KernApex.timed_wait(MS).unwrap();
+
+    let status = KernApex.get_buffer_status(buffer).unwrap();
+    super::record(
+        &TRACE,
+        Step::Status(status.nb_message, status.waiting_processes),
+    );
+    super::record(&TRACE, receive(buffer, 0));
+
+    super::finish();
+}
+
+extern "C" fn receiver() {
+    let &buffer = BUFFER.get().unwrap();
+    super::record(&TRACE, receive(buffer, 0));
+}
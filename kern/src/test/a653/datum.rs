@@ -0,0 +1,90 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_hal::{Cpu, Hal};
+use jrinx_multitask::runtime::Runtime;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+const FRAME: ApexSystemTime = 20 * MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    /// Whether the partition first runs no earlier than the frame start next to the datum.
+    Started(bool),
+    /// The core joining late, and whether it is logged with the datum and a later join time.
+    LateJoin(ApexProcessorCoreId, bool),
+    /// Whether the last window started at its offset in a frame aligned to the datum.
+    Aligned(bool),
+}
+
+static DATUM: Once<ApexSystemTime> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Synchronizes the major frames to a module-wide datum already passed, which the sched table
+/// joins late at the next frame start aligned to it.
+#[testdef]
+fn test() {
+    let datum = hal!()
+        .cpu()
+        .get_time()
+        .saturating_sub(Duration::from_millis(5));
+    Runtime::synchronize(datum);
+    assert_eq!(Runtime::sync_datum(), Some(datum));
+    DATUM.call_once(|| duration_as_time(datum));
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Started(true),
+            Step::LateJoin(0, true),
+            Step::Aligned(true)
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_datum() {
+    let &datum = DATUM.get().unwrap();
+    super::record(
+        &TRACE,
+        Step::Started(KernApex.get_time().unwrap() >= datum + FRAME),
+    );
+
+    super::start("checker", checker, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+extern "C" fn checker() {
+    let &datum = DATUM.get().unwrap();
+
+    let late_join = (0..)
+        .map_while(|index| KernApex.get_timing_event(index).ok())
+        .find(|event| event.kind == ApexTimingEventKind::LateJoin)
+        .unwrap();
+    super::record(
+        &TRACE,
+        Step::LateJoin(
+            late_join.core_id,
+            late_join.expected_time == datum && late_join.actual_time > datum,
+        ),
+    );
+
+    KernApex.timed_wait(2 * FRAME).unwrap();
+    let window = KernApex.get_window_status(0, 0).unwrap();
+    super::record(
+        &TRACE,
+        Step::Aligned((window.last_start - datum) % FRAME <= window.max_jitter),
+    );
+
+    super::finish();
+}
@@ -0,0 +1,88 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_config::PAGE_SIZE;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    /// The reported error code, and whether the victim is the failed process.
+    Error(Result<(ApexErrorCode, bool), ApexReturnCode>),
+    State(ApexProcessState),
+    Woken(Result<(), ApexReturnCode>),
+}
+
+static VICTIM: Once<ApexProcessId> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Reports the miss of a soft deadline to the error handler, while the process keeps running.
+#[testdef]
+fn test() {
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Error(Ok((ApexErrorCode::DeadlineMissed, true))),
+            Step::Error(Err(ApexReturnCode::NoAction)),
+            Step::State(ApexProcessState::Waiting),
+            Step::Woken(Ok(())),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_deadline() {
+    VICTIM.call_once(|| {
+        KernApex
+            .create_error_handler(ApexSystemAddress::of(handler), PAGE_SIZE as _)
+            .unwrap();
+        let id = KernApex
+            .create_process(&ApexProcessAttribute {
+                period: APEX_TIME_INFINITY,
+                time_capacity: 10 * MS,
+                entry_point: ApexSystemAddress::of(victim),
+                stack_size: PAGE_SIZE as _,
+                base_priority: 1,
+                deadline: ApexDeadline::Soft,
+                name: "victim".try_into().unwrap(),
+            })
+            .unwrap();
+        KernApex.start(id).unwrap();
+        id
+    });
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+extern "C" fn victim() {
+    super::record(&TRACE, Step::Woken(KernApex.timed_wait(30 * MS)));
+    super::finish();
+}
+
+fn error() -> Step {
+    let &victim = VICTIM.get().unwrap();
+    Step::Error(
+        KernApex
+            .get_error_status()
+            .map(|status| (status.error_code, status.failed_process_id == victim)),
+    )
+}
+
+extern "C" fn handler() {
+    let &victim = VICTIM.get().unwrap();
+
+    super::record(&TRACE, error());
+    super::record(&TRACE, error());
+    super::record(
+        &TRACE,
+        Step::State(KernApex.get_process_status(victim).unwrap().process_state),
+    );
+}
@@ -0,0 +1,125 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_a653::partition::Partition;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+const PERIOD: ApexSystemTime = 20 * MS;
+const CAPACITY: ApexSystemTime = 10 * MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Start(&'static str, Result<(), ApexReturnCode>),
+    /// Whether the process is released no earlier than its delay after it is started.
+    Elapsed(&'static str, bool),
+    /// The deadline of the process, relative to the first periodic start after init.
+    Deadline(ApexSystemTime),
+}
+
+static START: Once<ApexSystemTime> = Once::new();
+static SWITCH: Once<ApexSystemTime> = Once::new();
+static STARTED: Once<ApexSystemTime> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Starts processes with delays, which count from the switch to normal mode (aperiodic) or the
+/// next periodic start (periodic) if the processes are started during init.
+#[testdef]
+fn test() {
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Start("cyclic", Err(InvalidParam)),
+            Step::Start("late", Err(InvalidParam)),
+            Step::Start("late", Ok(())),
+            Step::Start("late", Err(NoAction)),
+            Step::Start("cyclic", Ok(())),
+            Step::Start("later", Ok(())),
+            Step::Start("later", Err(NoAction)),
+            Step::Deadline(5 * MS + CAPACITY),
+            Step::Elapsed("late", true),
+            Step::Elapsed("later", true),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_delayed() {
+    let cyclic = super::create("cyclic", cyclic, 2, PERIOD, CAPACITY);
+    let late = super::create("late", late, 3, APEX_TIME_INFINITY, APEX_TIME_INFINITY);
+    super::create("later", later, 4, APEX_TIME_INFINITY, APEX_TIME_INFINITY);
+
+    // the delay of a periodic process is shorter than its period
+    super::record(
+        &TRACE,
+        Step::Start("cyclic", KernApex.delayed_start(cyclic, PERIOD)),
+    );
+    super::record(
+        &TRACE,
+        Step::Start("late", KernApex.delayed_start(late, APEX_TIME_INFINITY)),
+    );
+    for _ in 0..2 {
+        super::record(
+            &TRACE,
+            Step::Start("late", KernApex.delayed_start(late, 40 * MS)),
+        );
+    }
+    super::record(
+        &TRACE,
+        Step::Start("cyclic", KernApex.delayed_start(cyclic, 5 * MS)),
+    );
+    super::start("starter", starter, 1);
+
+    START.call_once(|| Partition::current().unwrap().next_periodic_start());
+    SWITCH.call_once(|| KernApex.get_time().unwrap());
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+extern "C" fn starter() {
+    let later = KernApex
+        .get_process_id(&"later".try_into().unwrap())
+        .unwrap();
+    STARTED.call_once(|| KernApex.get_time().unwrap());
+    for _ in 0..2 {
+        super::record(
+            &TRACE,
+            Step::Start("later", KernApex.delayed_start(later, 60 * MS)),
+        );
+    }
+}
+
+extern "C" fn cyclic() {
+    let &start = START.get().unwrap();
+    let me = KernApex.get_my_id().unwrap();
+    let deadline = KernApex.get_process_status(me).unwrap().deadline_time;
+    super::record(&TRACE, Step::Deadline(deadline - start));
+}
+
+extern "C" fn late() {
+    let &switch = SWITCH.get().unwrap();
+    super::record(
+        &TRACE,
+        Step::Elapsed("late", KernApex.get_time().unwrap() - switch >= 40 * MS),
+    );
+}
+
+extern "C" fn later() {
+    let &started = STARTED.get().unwrap();
+    super::record(
+        &TRACE,
+        Step::Elapsed("later", KernApex.get_time().unwrap() - started >= 60 * MS),
+    );
+
+    super::finish();
+}
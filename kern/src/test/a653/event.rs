@@ -0,0 +1,104 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Wait(&'static str, Result<(), ApexReturnCode>),
+    Elapsed(bool),
+    Status(ApexEventState, ApexWaitingRange),
+    Set(Result<(), ApexReturnCode>),
+    Unlock(Result<ApexLockLevel, ApexReturnCode>),
+}
+
+static EVENT: Once<ApexEventId> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Sets an event with preemption locked, which moves all its waiting processes to Ready,
+/// but lets them run only once preemption is unlocked.
+#[testdef]
+fn test() {
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Wait("waiter1", Err(NotAvailable)),
+            Step::Wait("waiter1", Err(TimedOut)),
+            Step::Elapsed(true),
+            Step::Status(ApexEventState::Down, 2),
+            Step::Set(Ok(())),
+            Step::Status(ApexEventState::Up, 0),
+            // both woken by the set, but run only once preemption is unlocked
+            Step::Wait("waiter1", Ok(())),
+            Step::Wait("waiter2", Ok(())),
+            Step::Unlock(Ok(0)),
+            Step::Wait("setter", Err(NotAvailable)),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_event() {
+    EVENT.call_once(|| KernApex.create_event(&"ev".try_into().unwrap()).unwrap());
+    super::start("waiter1", waiter1, 3);
+    super::start("waiter2", waiter2, 2);
+    super::start("setter", setter, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn status() -> Step {
+    let &event = EVENT.get().unwrap();
+    let status = KernApex.get_event_status(event).unwrap();
+    Step::Status(status.event_state, status.waiting_processes)
+}
+
+extern "C" fn waiter1() {
+    let &event = EVENT.get().unwrap();
+
+    super::record(&TRACE, Step::Wait("waiter1", KernApex.wait_event(event, 0)));
+    let (wait, elapsed) = super::timed(10 * MS, |timeout| KernApex.wait_event(event, timeout));
+    super::record(&TRACE, Step::Wait("waiter1", wait));
+    super::record(&TRACE, Step::Elapsed(elapsed));
+
+    super::record(
+        &TRACE,
+        Step::Wait("waiter1", KernApex.wait_event(event, APEX_TIME_INFINITY)),
+    );
+}
+
+extern "C" fn waiter2() {
+    let &event = EVENT.get().unwrap();
+    super::record(
+        &TRACE,
+        Step::Wait("waiter2", KernApex.wait_event(event, APEX_TIME_INFINITY)),
+    );
+}
+
+extern "C" fn setter() {
+    let &event = EVENT.get().unwrap();
+
+    KernApex.timed_wait(20 * MS).unwrap();
+    super::record(&TRACE, status());
+
+    KernApex.lock_preemption().unwrap();
+    super::record(&TRACE, Step::Set(KernApex.set_event(event)));
+    super::record(&TRACE, status());
+    super::record(&TRACE, Step::Unlock(KernApex.unlock_preemption()));
+
+    KernApex.reset_event(event).unwrap();
+    super::record(&TRACE, Step::Wait("setter", KernApex.wait_event(event, 0)));
+
+    super::finish();
+}
@@ -0,0 +1,107 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_config::PAGE_SIZE;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    CreateHandler(Result<(), ApexReturnCode>),
+    /// The reported error code, whether the raiser is the failed process, and the message.
+    Error(
+        &'static str,
+        Result<(ApexErrorCode, bool, Vec<ApexByte>), ApexReturnCode>,
+    ),
+    Raise(Result<(), ApexReturnCode>),
+}
+
+static RAISER: Once<ApexProcessId> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Reports application errors to the error handler, which is started again for each of them.
+#[testdef]
+fn test() {
+    use ApexErrorCode::*;
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::CreateHandler(Ok(())),
+            Step::CreateHandler(Err(NoAction)),
+            Step::Error("raiser", Err(InvalidConfig)),
+            Step::Raise(Err(InvalidParam)),
+            Step::Raise(Err(InvalidParam)),
+            // the error handler preempts the raiser as soon as it is started
+            Step::Error("handler", Ok((ApplicationError, true, b"oops".to_vec()))),
+            Step::Error("handler", Err(NoAction)),
+            Step::Raise(Ok(())),
+            Step::Error("handler", Ok((ApplicationError, true, b"again".to_vec()))),
+            Step::Error("handler", Err(NoAction)),
+            Step::Raise(Ok(())),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_health() {
+    for _ in 0..2 {
+        super::record(
+            &TRACE,
+            Step::CreateHandler(
+                KernApex.create_error_handler(ApexSystemAddress::of(handler), PAGE_SIZE as _),
+            ),
+        );
+    }
+    RAISER.call_once(|| super::start("raiser", raiser, 1));
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn error(name: &'static str) -> Step {
+    let &raiser = RAISER.get().unwrap();
+    Step::Error(
+        name,
+        KernApex.get_error_status().map(|status| {
+            (
+                status.error_code,
+                status.failed_process_id == raiser,
+                status.message[..status.length as usize].to_vec(),
+            )
+        }),
+    )
+}
+
+fn raise(error_code: ApexErrorCode, message: &[ApexByte]) {
+    super::record(
+        &TRACE,
+        Step::Raise(KernApex.raise_application_error(error_code, message)),
+    );
+}
+
+extern "C" fn raiser() {
+    super::record(&TRACE, error("raiser"));
+
+    raise(
+        ApexErrorCode::ApplicationError,
+        &[0; APEX_MAX_ERROR_MESSAGE_SIZE + 1],
+    );
+    raise(ApexErrorCode::DeadlineMissed, b"oops");
+
+    raise(ApexErrorCode::ApplicationError, b"oops");
+    raise(ApexErrorCode::ApplicationError, b"again");
+
+    super::finish();
+}
+
+extern "C" fn handler() {
+    super::record(&TRACE, error("handler"));
+    super::record(&TRACE, error("handler"));
+}
@@ -0,0 +1,71 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::Mutex;
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Lock(Result<ApexLockLevel, ApexReturnCode>),
+    Unlock(Result<ApexLockLevel, ApexReturnCode>),
+    Spun,
+    Released,
+}
+
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Keeps a higher-priority process released during a busy loop from running until
+/// preemption is unlocked.
+#[testdef]
+fn test() {
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Lock(Ok(1)),
+            Step::Spun,
+            Step::Lock(Ok(2)),
+            Step::Unlock(Ok(1)),
+            // released during the busy loop, but run only once preemption is unlocked
+            Step::Released,
+            Step::Unlock(Ok(0)),
+            Step::Unlock(Err(ApexReturnCode::NoAction)),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_lock() {
+    super::start("high", high, 2);
+    super::start("locker", locker, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+extern "C" fn high() {
+    KernApex.timed_wait(5 * MS).unwrap();
+    super::record(&TRACE, Step::Released);
+}
+
+extern "C" fn locker() {
+    super::record(&TRACE, Step::Lock(KernApex.lock_preemption()));
+    let start = KernApex.get_time().unwrap();
+    while KernApex.get_time().unwrap() - start < 20 * MS {
+        core::hint::spin_loop();
+    }
+    super::record(&TRACE, Step::Spun);
+
+    super::record(&TRACE, Step::Lock(KernApex.lock_preemption()));
+    for _ in 0..3 {
+        super::record(&TRACE, Step::Unlock(KernApex.unlock_preemption()));
+    }
+
+    super::finish();
+}
@@ -0,0 +1,139 @@
+pub(super) mod background;
+pub(super) mod blackboard;
+pub(super) mod buffer;
+pub(super) mod datum;
+pub(super) mod deadline;
+pub(super) mod delayed;
+pub(super) mod event;
+pub(super) mod health;
+pub(super) mod lock;
+pub(super) mod mode;
+pub(super) mod mutex;
+pub(super) mod periodic;
+pub(super) mod preempt;
+pub(super) mod priority;
+pub(super) mod queuing;
+pub(super) mod race;
+pub(super) mod recovery;
+pub(super) mod sampling;
+pub(super) mod semaphore;
+pub(super) mod stop;
+pub(super) mod suspend;
+pub(super) mod switch;
+pub(super) mod timing;
+pub(super) mod window;
+
+use core::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_config::PAGE_SIZE;
+use jrinx_hal::{Cpu, Hal, Interrupt};
+use jrinx_multitask::{
+    inspector::{Inspector, InspectorStatus},
+    runtime::Runtime,
+};
+use jrinx_syscall::KernApex;
+use jrinx_timed_event::{TimedEvent, TimedEventHandler};
+use spin::Mutex;
+
+const MS: ApexSystemTime = 1_000_000;
+
+static FINISHED: AtomicBool = AtomicBool::new(false);
+
+/// Runs the partitions of the test on the current cpu until one of their processes calls
+/// [`finish`], or until `timeout` passes, after which they never run again.
+///
+/// The partitions only run once the test gives up the cpu, since their sched table is enacted
+/// before the test begins.
+fn run(timeout: Duration) {
+    let tracker = TimedEvent::create(
+        hal!().cpu().get_time() + timeout,
+        TimedEventHandler::new(
+            || {
+                stop();
+                let running = Inspector::with_current(|is| {
+                    matches!(is.status(), InspectorStatus::Running(_)) && is.mark_pending().is_ok()
+                });
+                if running.unwrap_or(false) {
+                    hal!().interrupt().with_saved_on(Runtime::switch_yield);
+                }
+            },
+            || {},
+        ),
+    );
+
+    while !FINISHED.load(Ordering::SeqCst) {
+        Inspector::with_current(|is| is.mark_pending().unwrap()).unwrap();
+        Runtime::switch_yield();
+    }
+
+    let _ = hal!().interrupt().with_saved_off(|| tracker.cancel());
+}
+
+/// Stops the partitions of the test, and gives the cpu back to the test.
+fn finish() -> ! {
+    hal!().interrupt().with_saved_off(stop);
+    loop {
+        Inspector::with_current(|is| is.mark_pending().unwrap()).unwrap();
+        Runtime::switch_yield();
+    }
+}
+
+fn stop() {
+    FINISHED.store(true, Ordering::SeqCst);
+    let _ = Runtime::with_current(|rt| rt.revoke_sched_table());
+}
+
+/// Records `step` in `trace`, with interrupts disabled so that the process is never preempted
+/// while holding the lock of `trace`.
+fn record<T>(trace: &Mutex<Vec<T>>, step: T) {
+    hal!()
+        .interrupt()
+        .with_saved_off(|| trace.lock().push(step));
+}
+
+/// Calls `service` with `timeout`, and returns its result along with whether it returned no
+/// earlier than `timeout`.
+fn timed<T>(timeout: ApexSystemTime, service: impl FnOnce(ApexSystemTime) -> T) -> (T, bool) {
+    let start = KernApex.get_time().unwrap();
+    let ret = service(timeout);
+    (ret, KernApex.get_time().unwrap() - start >= timeout)
+}
+
+/// Creates and starts an aperiodic process of the current kernel partition.
+fn start(name: &str, entry: extern "C" fn(), priority: ApexPriority) -> ApexProcessId {
+    let id = create(
+        name,
+        entry,
+        priority,
+        APEX_TIME_INFINITY,
+        APEX_TIME_INFINITY,
+    );
+    KernApex.start(id).unwrap();
+    id
+}
+
+/// Creates a dormant process of the current kernel partition.
+fn create(
+    name: &str,
+    entry: extern "C" fn(),
+    priority: ApexPriority,
+    period: ApexSystemTime,
+    time_capacity: ApexSystemTime,
+) -> ApexProcessId {
+    KernApex
+        .create_process(&ApexProcessAttribute {
+            period,
+            time_capacity,
+            entry_point: ApexSystemAddress::of(entry),
+            stack_size: PAGE_SIZE as _,
+            base_priority: priority,
+            deadline: ApexDeadline::Soft,
+            name: name.try_into().unwrap(),
+        })
+        .unwrap()
+}
@@ -0,0 +1,76 @@
+use core::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::Mutex;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Status(ApexOperatingMode, ApexStartCondition),
+    SetMode(ApexOperatingMode, Result<(), ApexReturnCode>),
+}
+
+static INITS: AtomicUsize = AtomicUsize::new(0);
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Restarts the partition warm then cold, checking how it restarted each time.
+#[testdef]
+fn test() {
+    use ApexOperatingMode::*;
+    use ApexStartCondition::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Status(ColdStart, NormalStart),
+            Step::SetMode(Normal, Err(ApexReturnCode::NoAction)),
+            Step::Status(WarmStart, PartitionRestart),
+            Step::SetMode(Normal, Err(ApexReturnCode::NoAction)),
+            Step::Status(ColdStart, PartitionRestart),
+            // a cold started partition may not restart warm
+            Step::SetMode(WarmStart, Err(ApexReturnCode::InvalidMode)),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_mode() {
+    let status = KernApex.get_partition_status().unwrap();
+    super::record(
+        &TRACE,
+        Step::Status(status.operating_mode, status.start_condition),
+    );
+    if INITS.fetch_add(1, Ordering::SeqCst) == 2 {
+        set_mode(ApexOperatingMode::WarmStart);
+        super::finish();
+    }
+
+    super::start("restarter", restarter, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn set_mode(mode: ApexOperatingMode) {
+    super::record(
+        &TRACE,
+        Step::SetMode(mode, KernApex.set_partition_mode(mode)),
+    );
+}
+
+extern "C" fn restarter() {
+    set_mode(ApexOperatingMode::Normal);
+    match INITS.load(Ordering::SeqCst) {
+        1 => set_mode(ApexOperatingMode::WarmStart),
+        _ => set_mode(ApexOperatingMode::ColdStart),
+    }
+    unreachable!("partition not restarted");
+}
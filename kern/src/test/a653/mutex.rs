@@ -0,0 +1,172 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Acquire(&'static str, Result<(), ApexReturnCode>),
+    Release(&'static str, Result<(), ApexReturnCode>),
+    Elapsed(bool),
+    /// Whether the mutex is owned by the recording process, its state, lock count and waiters.
+    Status(bool, ApexMutexState, ApexLockCount, ApexWaitingRange),
+    Priority(&'static str, ApexPriority),
+    Holds(&'static str, bool),
+}
+
+static MUTEX: Once<ApexMutexId> = Once::new();
+static HOLDER: Once<ApexProcessId> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Contends for a mutex whose owner runs at its priority ceiling.
+#[testdef]
+fn test() {
+    use ApexMutexState::*;
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Acquire("holder", Ok(())),
+            Step::Acquire("holder", Ok(())),
+            Step::Priority("holder", 5),
+            Step::Holds("holder", true),
+            Step::Status(true, Owned, 2, 0),
+            Step::Acquire("contender", Err(NotAvailable)),
+            Step::Release("contender", Err(InvalidMode)),
+            Step::Acquire("contender", Err(TimedOut)),
+            Step::Elapsed(true),
+            Step::Status(true, Owned, 2, 1),
+            Step::Release("holder", Ok(())),
+            Step::Priority("holder", 5),
+            // the contender takes the mutex over and preempts the holder at its ceiling
+            Step::Acquire("contender", Ok(())),
+            Step::Status(true, Owned, 1, 0),
+            Step::Priority("contender", 5),
+            Step::Priority("holder", 1),
+            Step::Holds("holder", false),
+            Step::Release("contender", Ok(())),
+            Step::Priority("contender", 2),
+            Step::Status(false, Available, 0, 0),
+            Step::Release("holder", Ok(())),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_mutex() {
+    MUTEX.call_once(|| {
+        KernApex
+            .create_mutex(&"mtx".try_into().unwrap(), 5, ApexQueueDiscipline::Priority)
+            .unwrap()
+    });
+    super::start("contender", contender, 2);
+    HOLDER.call_once(|| super::start("holder", holder, 1));
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn status() -> Step {
+    let &mutex = MUTEX.get().unwrap();
+    let status = KernApex.get_mutex_status(mutex).unwrap();
+    Step::Status(
+        status.mutex_owner == KernApex.get_my_id().unwrap(),
+        status.mutex_state,
+        status.lock_count,
+        status.waiting_processes,
+    )
+}
+
+fn priority(name: &'static str, process: ApexProcessId) -> Step {
+    Step::Priority(
+        name,
+        KernApex
+            .get_process_status(process)
+            .unwrap()
+            .current_priority,
+    )
+}
+
+fn holds(name: &'static str, process: ApexProcessId) -> Step {
+    let &mutex = MUTEX.get().unwrap();
+    Step::Holds(name, KernApex.get_process_mutex_state(process) == Ok(mutex))
+}
+
+extern "C" fn holder() {
+    let &mutex = MUTEX.get().unwrap();
+    let me = KernApex.get_my_id().unwrap();
+
+    for _ in 0..2 {
+        super::record(
+            &TRACE,
+            Step::Acquire("holder", KernApex.acquire_mutex(mutex, 0)),
+        );
+    }
+    super::record(&TRACE, priority("holder", me));
+    super::record(&TRACE, holds("holder", me));
+    super::record(&TRACE, status());
+
+    KernApex.timed_wait(20 * MS).unwrap();
+    super::record(&TRACE, status());
+
+    super::record(
+        &TRACE,
+        Step::Release("holder", KernApex.release_mutex(mutex)),
+    );
+    super::record(&TRACE, priority("holder", me));
+    // hands the mutex over to the contender
+    super::record(
+        &TRACE,
+        Step::Release("holder", KernApex.release_mutex(mutex)),
+    );
+
+    super::finish();
+}
+
+extern "C" fn contender() {
+    let &mutex = MUTEX.get().unwrap();
+    let &holder = HOLDER.get().unwrap();
+    let me = KernApex.get_my_id().unwrap();
+
+    KernApex.timed_wait(5 * MS).unwrap();
+    super::record(
+        &TRACE,
+        Step::Acquire("contender", KernApex.acquire_mutex(mutex, 0)),
+    );
+    super::record(
+        &TRACE,
+        Step::Release("contender", KernApex.release_mutex(mutex)),
+    );
+    let (acquire, elapsed) =
+        super::timed(10 * MS, |timeout| KernApex.acquire_mutex(mutex, timeout));
+    super::record(&TRACE, Step::Acquire("contender", acquire));
+    super::record(&TRACE, Step::Elapsed(elapsed));
+
+    super::record(
+        &TRACE,
+        Step::Acquire(
+            "contender",
+            KernApex.acquire_mutex(mutex, APEX_TIME_INFINITY),
+        ),
+    );
+    super::record(&TRACE, status());
+    super::record(&TRACE, priority("contender", me));
+    super::record(&TRACE, priority("holder", holder));
+    super::record(&TRACE, holds("holder", holder));
+
+    super::record(
+        &TRACE,
+        Step::Release("contender", KernApex.release_mutex(mutex)),
+    );
+    super::record(&TRACE, priority("contender", me));
+    super::record(&TRACE, status());
+}
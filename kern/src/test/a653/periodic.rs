@@ -0,0 +1,85 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_a653::partition::Partition;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+const PERIOD: ApexSystemTime = 20 * MS;
+const CAPACITY: ApexSystemTime = 10 * MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    /// The deadline of the process, relative to the first periodic start after init.
+    Deadline(ApexSystemTime),
+    /// Whether the process runs between its release point and its deadline.
+    OnTime(bool),
+    Wait(&'static str, Result<(), ApexReturnCode>),
+}
+
+static START: Once<ApexSystemTime> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Releases a periodic process at the next periodic start of its partition, then once per
+/// period, with its deadline moved along each time.
+#[testdef]
+fn test() {
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Deadline(CAPACITY),
+            Step::OnTime(true),
+            Step::Wait("aperiodic", Err(ApexReturnCode::InvalidMode)),
+            Step::Wait("periodic", Ok(())),
+            Step::Deadline(PERIOD + CAPACITY),
+            Step::OnTime(true),
+            Step::Wait("periodic", Ok(())),
+            Step::Deadline(2 * PERIOD + CAPACITY),
+            Step::OnTime(true),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_periodic() {
+    START.call_once(|| Partition::current().unwrap().next_periodic_start());
+    let id = super::create("periodic", periodic, 2, PERIOD, CAPACITY);
+    KernApex.start(id).unwrap();
+    super::start("aperiodic", aperiodic, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn release() {
+    let &start = START.get().unwrap();
+    let me = KernApex.get_my_id().unwrap();
+    let deadline = KernApex.get_process_status(me).unwrap().deadline_time;
+    let now = KernApex.get_time().unwrap();
+    super::record(&TRACE, Step::Deadline(deadline - start));
+    super::record(
+        &TRACE,
+        Step::OnTime(now >= deadline - CAPACITY && now < deadline),
+    );
+}
+
+extern "C" fn periodic() {
+    release();
+    for _ in 0..2 {
+        super::record(&TRACE, Step::Wait("periodic", KernApex.periodic_wait()));
+        release();
+    }
+
+    super::finish();
+}
+
+extern "C" fn aperiodic() {
+    super::record(&TRACE, Step::Wait("aperiodic", KernApex.periodic_wait()));
+}
@@ -0,0 +1,58 @@
+use core::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_hal::{Cpu, Hal};
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::Mutex;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Spinning,
+    Released,
+    Spun,
+}
+
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+static RELEASED: AtomicBool = AtomicBool::new(false);
+
+/// Starts a process spinning without calling any service, which is preempted by a process
+/// of higher priority released by a timer.
+#[testdef]
+fn test() {
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(*TRACE.lock(), [Step::Spinning, Step::Released, Step::Spun]);
+}
+
+#[partdef]
+extern "C" fn a653_preempt() {
+    super::start("spinner", spinner, 1);
+    super::start("waker", waker, 2);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+extern "C" fn waker() {
+    KernApex.timed_wait(10 * super::MS).unwrap();
+    super::record(&TRACE, Step::Released);
+    RELEASED.store(true, Ordering::SeqCst);
+}
+
+extern "C" fn spinner() {
+    super::record(&TRACE, Step::Spinning);
+
+    let timeout = hal!().cpu().get_time() + Duration::from_secs(1);
+    while !RELEASED.load(Ordering::SeqCst) && hal!().cpu().get_time() < timeout {
+        core::hint::spin_loop();
+    }
+
+    super::record(&TRACE, Step::Spun);
+    super::finish();
+}
@@ -0,0 +1,127 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_a653::process::Process;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    SetPriority(&'static str, Result<(), ApexReturnCode>),
+    Ran(&'static str, ApexPriority),
+    Priority(&'static str, ApexPriority),
+    Release(Result<(), ApexReturnCode>),
+}
+
+static OBJECTS: Once<(ApexMutexId, ApexProcessId, ApexProcessId, ApexProcessId)> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Changes the priorities of processes, which preempt at once if they become the highest, or
+/// return to their new priorities once they release the mutexes they own.
+#[testdef]
+fn test() {
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Priority("holder", 5),
+            // raised above the setter
+            Step::Ran("target", 4),
+            Step::SetPriority("target", Ok(())),
+            Step::SetPriority("target", Err(InvalidMode)),
+            Step::SetPriority("none", Err(InvalidParam)),
+            Step::SetPriority("holder", Err(InvalidParam)),
+            // retained until the mutex is released
+            Step::SetPriority("holder", Ok(())),
+            Step::Priority("holder", 5),
+            Step::Release(Ok(())),
+            Step::Priority("holder", 1),
+            // lowered below a ready process
+            Step::Ran("low", 2),
+            Step::SetPriority("setter", Ok(())),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_priority() {
+    OBJECTS.call_once(|| {
+        (
+            KernApex
+                .create_mutex(&"mtx".try_into().unwrap(), 5, ApexQueueDiscipline::Fifo)
+                .unwrap(),
+            super::start("holder", holder, 4),
+            super::start("target", target, 1),
+            super::create("low", low, 2, APEX_TIME_INFINITY, APEX_TIME_INFINITY),
+        )
+    });
+    super::start("setter", setter, 3);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn current_priority(process: ApexProcessId) -> ApexPriority {
+    KernApex
+        .get_process_status(process)
+        .unwrap()
+        .current_priority
+}
+
+fn ran(name: &'static str) {
+    let me = KernApex.get_my_id().unwrap();
+    super::record(&TRACE, Step::Ran(name, current_priority(me)));
+}
+
+fn set_priority(name: &'static str, process: ApexProcessId, priority: ApexPriority) {
+    super::record(
+        &TRACE,
+        Step::SetPriority(name, KernApex.set_priority(process, priority)),
+    );
+}
+
+extern "C" fn holder() {
+    let &(mutex, holder, ..) = OBJECTS.get().unwrap();
+
+    KernApex.acquire_mutex(mutex, 0).unwrap();
+    super::record(&TRACE, Step::Priority("holder", current_priority(holder)));
+    KernApex.timed_wait(10 * MS).unwrap();
+
+    super::record(&TRACE, Step::Release(KernApex.release_mutex(mutex)));
+    super::record(&TRACE, Step::Priority("holder", current_priority(holder)));
+}
+
+extern "C" fn target() {
+    ran("target");
+}
+
+extern "C" fn low() {
+    ran("low");
+}
+
+extern "C" fn setter() {
+    let &(_, holder, target, low) = OBJECTS.get().unwrap();
+    let me = KernApex.get_my_id().unwrap();
+
+    set_priority("target", target, 4);
+    set_priority("target", target, 1);
+    set_priority("none", 100, 1);
+    set_priority("holder", holder, Process::MAX_PRIORITY + 1);
+
+    set_priority("holder", holder, 1);
+    super::record(&TRACE, Step::Priority("holder", current_priority(holder)));
+    KernApex.timed_wait(20 * MS).unwrap();
+
+    KernApex.start(low).unwrap();
+    set_priority("setter", me, 1);
+
+    super::finish();
+}
@@ -0,0 +1,124 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Receive(Result<Vec<ApexByte>, ApexReturnCode>),
+    Send(Result<(), ApexReturnCode>),
+    Elapsed(bool),
+    Waiting(ApexWaitingRange),
+}
+
+static PORTS: Once<(ApexQueuingPortId, ApexQueuingPortId)> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Sends and receives messages through a queuing channel of a single message, looping
+/// back to the partition itself.
+#[testdef]
+fn test() {
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Receive(Err(NotAvailable)),
+            Step::Receive(Err(TimedOut)),
+            Step::Elapsed(true),
+            // woken by the sender
+            Step::Receive(Ok(b"abc".to_vec())),
+            Step::Send(Ok(())),
+            Step::Send(Err(NotAvailable)),
+            Step::Send(Err(TimedOut)),
+            Step::Elapsed(true),
+            Step::Waiting(0),
+            Step::Receive(Ok(b"x".to_vec())),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_queuing() {
+    PORTS.call_once(|| {
+        let port = |name: &str, direction| {
+            KernApex
+                .create_queuing_port(
+                    &name.try_into().unwrap(),
+                    8,
+                    1,
+                    direction,
+                    ApexQueueDiscipline::Fifo,
+                )
+                .unwrap()
+        };
+        (
+            port("out", ApexPortDirection::Source),
+            port("in", ApexPortDirection::Destination),
+        )
+    });
+    super::start("receiver", receiver, 2);
+    super::start("sender", sender, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn receive(port: ApexQueuingPortId, timeout: ApexSystemTime) -> Step {
+    let mut message = [0; 8];
+    Step::Receive(
+        KernApex
+            .receive_queuing_message(port, timeout, &mut message)
+            .map(|length| message[..length as usize].to_vec()),
+    )
+}
+
+extern "C" fn receiver() {
+    let &(out, input) = PORTS.get().unwrap();
+
+    super::record(&TRACE, receive(input, 0));
+    let (step, elapsed) = super::timed(10 * MS, |timeout| receive(input, timeout));
+    super::record(&TRACE, step);
+    super::record(&TRACE, Step::Elapsed(elapsed));
+
+    super::record(&TRACE, receive(input, APEX_TIME_INFINITY));
+
+    super::record(
+        &TRACE,
+        Step::Send(KernApex.send_queuing_message(out, b"x", 0)),
+    );
+    super::record(
+        &TRACE,
+        Step::Send(KernApex.send_queuing_message(out, b"y", 0)),
+    );
+    let (ret, elapsed) = super::timed(10 * MS, |timeout| {
+        KernApex.send_queuing_message(out, b"y", timeout)
+    });
+    super::record(&TRACE, Step::Send(ret));
+    super::record(&TRACE, Step::Elapsed(elapsed));
+    super::record(
+        &TRACE,
+        Step::Waiting(
+            KernApex
+                .get_queuing_port_status(out)
+                .unwrap()
+                .waiting_processes,
+        ),
+    );
+    super::record(&TRACE, receive(input, 0));
+
+    super::finish();
+}
+
+extern "C" fn sender() {
+    let &(out, _) = PORTS.get().unwrap();
+    KernApex.send_queuing_message(out, b"abc", 0).unwrap();
+}
@@ -0,0 +1,116 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+const ROUNDS: u8 = 64;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Notified(ApexByte),
+    TimedOut(ApexByte),
+    Waiting(ApexWaitingRange),
+}
+
+static PORTS: Once<(ApexQueuingPortId, ApexQueuingPortId)> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Receives each message with a timeout expiring around the time it is sent, so that the
+/// timeout races the notification of the receiver, which gets every message in either case.
+#[testdef]
+fn test() {
+    super::run(Duration::from_secs(2));
+
+    let trace = TRACE.lock();
+    let messages = trace
+        .iter()
+        .filter_map(|step| match *step {
+            Step::Notified(message) | Step::TimedOut(message) => Some(message),
+            Step::Waiting(_) => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(messages, (0..ROUNDS).collect::<Vec<_>>(), "{:?}", trace);
+    assert_eq!(trace.last(), Some(&Step::Waiting(0)));
+
+    info!(
+        "{} of {} receptions timed out",
+        trace
+            .iter()
+            .filter(|step| matches!(step, Step::TimedOut(_)))
+            .count(),
+        ROUNDS
+    );
+}
+
+#[partdef]
+extern "C" fn a653_race() {
+    PORTS.call_once(|| {
+        let port = |name: &str, direction| {
+            KernApex
+                .create_queuing_port(
+                    &name.try_into().unwrap(),
+                    1,
+                    1,
+                    direction,
+                    ApexQueueDiscipline::Fifo,
+                )
+                .unwrap()
+        };
+        (
+            port("out", ApexPortDirection::Source),
+            port("in", ApexPortDirection::Destination),
+        )
+    });
+    super::start("receiver", receiver, 2);
+    super::start("sender", sender, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+extern "C" fn receiver() {
+    let &(_, input) = PORTS.get().unwrap();
+    let mut message = [0; 1];
+
+    for _ in 0..ROUNDS {
+        let step = match KernApex.receive_queuing_message(input, MS, &mut message) {
+            Ok(_) => Step::Notified(message[0]),
+            Err(ApexReturnCode::TimedOut) => {
+                KernApex
+                    .receive_queuing_message(input, APEX_TIME_INFINITY, &mut message)
+                    .unwrap();
+                Step::TimedOut(message[0])
+            }
+            Err(err) => panic!("failed to receive message: {:?}", err),
+        };
+        super::record(&TRACE, step);
+    }
+
+    super::record(
+        &TRACE,
+        Step::Waiting(
+            KernApex
+                .get_queuing_port_status(input)
+                .unwrap()
+                .waiting_processes,
+        ),
+    );
+    super::finish();
+}
+
+extern "C" fn sender() {
+    let &(out, _) = PORTS.get().unwrap();
+
+    for i in 0..ROUNDS {
+        // around the timeout of the receiver, which has just started waiting
+        let delay = MS - 50_000 + (i % 10) as ApexSystemTime * 10_000;
+        KernApex.timed_wait(delay).unwrap();
+        KernApex.send_queuing_message(out, &[i], 0).unwrap();
+    }
+}
@@ -0,0 +1,95 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_a653::{health::RecoveryAction, partition::Partition};
+use jrinx_apex::*;
+use jrinx_config::PAGE_SIZE;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Raise(Result<(), ApexReturnCode>),
+    Woken(Result<(), ApexReturnCode>),
+    State(ApexProcessState),
+}
+
+static LATE: Once<ApexProcessId> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Recovers from the errors of a partition without an error handler by its recovery table,
+/// which ignores application errors as configured, but stops a process missing its hard
+/// deadline by default.
+#[testdef]
+fn test() {
+    let table = Partition::find_by_name(&"recovery".try_into().unwrap())
+        .unwrap()
+        .recovery_table();
+    assert_eq!(
+        table.action(ApexErrorCode::ApplicationError),
+        RecoveryAction::Ignore
+    );
+    assert_eq!(
+        table.action(ApexErrorCode::DeadlineMissed),
+        RecoveryAction::StopProcess
+    );
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Raise(Ok(())),
+            // the late process is stopped at its deadline, and never woken
+            Step::State(ApexProcessState::Dormant),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_recovery() {
+    LATE.call_once(|| {
+        let id = KernApex
+            .create_process(&ApexProcessAttribute {
+                period: APEX_TIME_INFINITY,
+                time_capacity: 10 * MS,
+                entry_point: ApexSystemAddress::of(late),
+                stack_size: PAGE_SIZE as _,
+                base_priority: 2,
+                deadline: ApexDeadline::Hard,
+                name: "late".try_into().unwrap(),
+            })
+            .unwrap();
+        KernApex.start(id).unwrap();
+        id
+    });
+    super::start("raiser", raiser, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+extern "C" fn late() {
+    super::record(&TRACE, Step::Woken(KernApex.timed_wait(30 * MS)));
+}
+
+extern "C" fn raiser() {
+    let &late = LATE.get().unwrap();
+
+    super::record(
+        &TRACE,
+        Step::Raise(KernApex.raise_application_error(ApexErrorCode::ApplicationError, b"oops")),
+    );
+
+    KernApex.timed_wait(50 * MS).unwrap();
+    super::record(
+        &TRACE,
+        Step::State(KernApex.get_process_status(late).unwrap().process_state),
+    );
+
+    super::finish();
+}
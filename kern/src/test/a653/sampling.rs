@@ -0,0 +1,145 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Create(Result<(), ApexReturnCode>),
+    Write(Result<(), ApexReturnCode>),
+    Read(Result<(Vec<ApexByte>, ApexValidity), ApexReturnCode>),
+    Validity(ApexValidity),
+}
+
+static PORTS: Once<(ApexSamplingPortId, ApexSamplingPortId)> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Creates sampling ports of a channel looping back to the partition itself, and reads the
+/// message written through it before and after it is refreshed.
+#[testdef]
+fn test() {
+    use ApexReturnCode::*;
+    use ApexValidity::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            // size and direction mismatches with the channel, and no channel at all
+            Step::Create(Err(InvalidConfig)),
+            Step::Create(Err(InvalidConfig)),
+            Step::Create(Err(InvalidConfig)),
+            Step::Create(Err(NoAction)),
+            // an empty port
+            Step::Read(Err(NoAction)),
+            Step::Validity(Invalid),
+            Step::Write(Err(InvalidConfig)),
+            Step::Write(Err(InvalidParam)),
+            Step::Write(Err(InvalidMode)),
+            Step::Read(Err(InvalidMode)),
+            Step::Write(Ok(())),
+            Step::Read(Ok((b"jrinx".to_vec(), Valid))),
+            Step::Validity(Valid),
+            // after the refresh period
+            Step::Read(Ok((b"jrinx".to_vec(), Invalid))),
+            Step::Validity(Invalid),
+            Step::Write(Ok(())),
+            Step::Read(Ok((b"653".to_vec(), Valid))),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_sampling() {
+    let create = |name: &str, max_message_size, direction| {
+        KernApex.create_sampling_port(
+            &name.try_into().unwrap(),
+            max_message_size,
+            direction,
+            10 * MS,
+        )
+    };
+
+    for (name, max_message_size, direction) in [
+        ("out", 16, ApexPortDirection::Source),
+        ("out", 8, ApexPortDirection::Destination),
+        ("none", 8, ApexPortDirection::Source),
+    ] {
+        super::record(
+            &TRACE,
+            Step::Create(create(name, max_message_size, direction).map(|_| ())),
+        );
+    }
+    PORTS.call_once(|| {
+        (
+            create("out", 8, ApexPortDirection::Source).unwrap(),
+            create("in", 8, ApexPortDirection::Destination).unwrap(),
+        )
+    });
+    super::record(
+        &TRACE,
+        Step::Create(create("in", 8, ApexPortDirection::Destination).map(|_| ())),
+    );
+
+    super::start("sampler", sampler, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+extern "C" fn sampler() {
+    let &(out, input) = PORTS.get().unwrap();
+
+    let read = |port| {
+        let mut message = [0; 8];
+        Step::Read(
+            KernApex
+                .read_sampling_message(port, &mut message)
+                .map(|(length, validity)| (message[..length as usize].to_vec(), validity)),
+        )
+    };
+    let validity = || {
+        Step::Validity(
+            KernApex
+                .get_sampling_port_status(input)
+                .unwrap()
+                .last_msg_validity,
+        )
+    };
+
+    super::record(&TRACE, read(input));
+    super::record(&TRACE, validity());
+    for (port, message) in [(out, &[0; 9][..]), (out, &[]), (input, b"jrinx")] {
+        super::record(
+            &TRACE,
+            Step::Write(KernApex.write_sampling_message(port, message)),
+        );
+    }
+    super::record(&TRACE, read(out));
+
+    super::record(
+        &TRACE,
+        Step::Write(KernApex.write_sampling_message(out, b"jrinx")),
+    );
+    super::record(&TRACE, read(input));
+    super::record(&TRACE, validity());
+
+    KernApex.timed_wait(20 * MS).unwrap();
+    super::record(&TRACE, read(input));
+    super::record(&TRACE, validity());
+
+    super::record(
+        &TRACE,
+        Step::Write(KernApex.write_sampling_message(out, b"653")),
+    );
+    super::record(&TRACE, read(input));
+
+    super::finish();
+}
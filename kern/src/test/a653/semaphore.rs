@@ -0,0 +1,108 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Wait(Result<(), ApexReturnCode>),
+    Elapsed(bool),
+    Status(ApexSemaphoreValue, ApexWaitingRange),
+    Signal(&'static str, Result<(), ApexReturnCode>),
+}
+
+static SEMAPHORE: Once<ApexSemaphoreId> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Waits on a semaphore with zero, finite and infinite timeouts.
+#[testdef]
+fn test() {
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Wait(Err(NotAvailable)),
+            Step::Wait(Err(TimedOut)),
+            Step::Elapsed(true),
+            Step::Status(0, 1),
+            // the waiter preempts the signaller as soon as it is woken
+            Step::Wait(Ok(())),
+            Step::Status(0, 0),
+            Step::Signal("waiter", Ok(())),
+            Step::Signal("waiter", Err(NoAction)),
+            Step::Signal("signaller", Ok(())),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_semaphore() {
+    SEMAPHORE.call_once(|| {
+        KernApex
+            .create_semaphore(
+                &"sem".try_into().unwrap(),
+                0,
+                1,
+                ApexQueueDiscipline::Priority,
+            )
+            .unwrap()
+    });
+    super::start("waiter", waiter, 2);
+    super::start("signaller", signaller, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn status() -> Step {
+    let &semaphore = SEMAPHORE.get().unwrap();
+    let status = KernApex.get_semaphore_status(semaphore).unwrap();
+    Step::Status(status.current_value, status.waiting_processes)
+}
+
+extern "C" fn waiter() {
+    let &semaphore = SEMAPHORE.get().unwrap();
+
+    super::record(&TRACE, Step::Wait(KernApex.wait_semaphore(semaphore, 0)));
+    let (wait, elapsed) = super::timed(10 * MS, |timeout| {
+        KernApex.wait_semaphore(semaphore, timeout)
+    });
+    super::record(&TRACE, Step::Wait(wait));
+    super::record(&TRACE, Step::Elapsed(elapsed));
+
+    // woken by the signaller
+    super::record(
+        &TRACE,
+        Step::Wait(KernApex.wait_semaphore(semaphore, APEX_TIME_INFINITY)),
+    );
+    super::record(&TRACE, status());
+
+    // the second signal exceeds the maximum value
+    for _ in 0..2 {
+        super::record(
+            &TRACE,
+            Step::Signal("waiter", KernApex.signal_semaphore(semaphore)),
+        );
+    }
+}
+
+extern "C" fn signaller() {
+    let &semaphore = SEMAPHORE.get().unwrap();
+
+    super::record(&TRACE, status());
+    super::record(
+        &TRACE,
+        Step::Signal("signaller", KernApex.signal_semaphore(semaphore)),
+    );
+
+    super::finish();
+}
@@ -0,0 +1,131 @@
+use core::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Started(usize),
+    Acquire(Result<(), ApexReturnCode>),
+    Returned,
+    State(ApexProcessState),
+    /// Whether the victim owns the mutex, and its state.
+    Mutex(bool, ApexMutexState),
+    Waiting(ApexWaitingRange),
+    Stop(&'static str, Result<(), ApexReturnCode>),
+    Start(Result<(), ApexReturnCode>),
+}
+
+static OBJECTS: Once<(ApexSemaphoreId, ApexMutexId, ApexProcessId)> = Once::new();
+static STARTS: AtomicUsize = AtomicUsize::new(0);
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Stops a waiting process holding a mutex, then restarts it from its entry point.
+#[testdef]
+fn test() {
+    use ApexProcessState::*;
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Started(0),
+            Step::Acquire(Ok(())),
+            Step::State(Waiting),
+            Step::Mutex(true, ApexMutexState::Owned),
+            Step::Stop("victim", Ok(())),
+            Step::State(Dormant),
+            Step::Waiting(0),
+            Step::Mutex(false, ApexMutexState::Available),
+            Step::Stop("victim", Err(NoAction)),
+            Step::Stop("checker", Err(InvalidParam)),
+            // runs again from its entry point and stops itself, never returning
+            Step::Started(1),
+            Step::Start(Ok(())),
+            Step::State(Dormant),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_stop() {
+    OBJECTS.call_once(|| {
+        (
+            KernApex
+                .create_semaphore(&"sem".try_into().unwrap(), 0, 1, ApexQueueDiscipline::Fifo)
+                .unwrap(),
+            KernApex
+                .create_mutex(&"mtx".try_into().unwrap(), 3, ApexQueueDiscipline::Fifo)
+                .unwrap(),
+            super::start("victim", victim, 3),
+        )
+    });
+    super::start("checker", checker, 2);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+extern "C" fn victim() {
+    let &(semaphore, mutex, _) = OBJECTS.get().unwrap();
+
+    let starts = STARTS.fetch_add(1, Ordering::SeqCst);
+    super::record(&TRACE, Step::Started(starts));
+    if starts == 0 {
+        super::record(&TRACE, Step::Acquire(KernApex.acquire_mutex(mutex, 0)));
+        KernApex
+            .wait_semaphore(semaphore, APEX_TIME_INFINITY)
+            .unwrap();
+    } else {
+        KernApex.stop_self();
+    }
+    super::record(&TRACE, Step::Returned);
+}
+
+fn state() -> Step {
+    let &(_, _, victim) = OBJECTS.get().unwrap();
+    Step::State(KernApex.get_process_status(victim).unwrap().process_state)
+}
+
+fn mutex() -> Step {
+    let &(_, mutex, victim) = OBJECTS.get().unwrap();
+    let status = KernApex.get_mutex_status(mutex).unwrap();
+    Step::Mutex(status.mutex_owner == victim, status.mutex_state)
+}
+
+extern "C" fn checker() {
+    let &(semaphore, _, victim) = OBJECTS.get().unwrap();
+    let me = KernApex.get_my_id().unwrap();
+
+    super::record(&TRACE, state());
+    super::record(&TRACE, mutex());
+
+    super::record(&TRACE, Step::Stop("victim", KernApex.stop(victim)));
+    super::record(&TRACE, state());
+    super::record(
+        &TRACE,
+        Step::Waiting(
+            KernApex
+                .get_semaphore_status(semaphore)
+                .unwrap()
+                .waiting_processes,
+        ),
+    );
+    super::record(&TRACE, mutex());
+    super::record(&TRACE, Step::Stop("victim", KernApex.stop(victim)));
+    super::record(&TRACE, Step::Stop("checker", KernApex.stop(me)));
+
+    super::record(&TRACE, Step::Start(KernApex.start(victim)));
+    super::record(&TRACE, state());
+
+    super::finish();
+}
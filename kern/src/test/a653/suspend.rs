@@ -0,0 +1,133 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    SuspendSelf(&'static str, Result<(), ApexReturnCode>),
+    Suspend(&'static str, Result<(), ApexReturnCode>),
+    Resume(&'static str, Result<(), ApexReturnCode>),
+    State(&'static str, ApexProcessState),
+    Elapsed(bool),
+    Ran(&'static str),
+}
+
+static PROCESSES: Once<(ApexProcessId, ApexProcessId)> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Suspends and resumes processes, covering the error returns of both services.
+#[testdef]
+fn test() {
+    use ApexProcessState::*;
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::State("target", Waiting),
+            // the target preempts the checker as soon as it is resumed
+            Step::SuspendSelf("target", Ok(())),
+            Step::Resume("target", Ok(())),
+            Step::State("target", Dormant),
+            Step::Resume("target", Err(InvalidMode)),
+            Step::Suspend("target", Err(InvalidMode)),
+            Step::Suspend("checker", Err(InvalidParam)),
+            Step::Resume("checker", Err(InvalidParam)),
+            Step::Resume("victim", Err(NoAction)),
+            Step::Suspend("victim", Ok(())),
+            Step::State("victim", Waiting),
+            Step::Suspend("victim", Err(NoAction)),
+            Step::SuspendSelf("checker", Ok(())),
+            Step::SuspendSelf("checker", Err(TimedOut)),
+            Step::Elapsed(true),
+            Step::Resume("victim", Ok(())),
+            Step::State("victim", Ready),
+            Step::Resume("victim", Err(NoAction)),
+            Step::SuspendSelf("checker", Err(InvalidMode)),
+            Step::Ran("victim"),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_suspend() {
+    PROCESSES.call_once(|| {
+        (
+            super::start("target", target, 3),
+            super::start("victim", victim, 1),
+        )
+    });
+    super::start("checker", checker, 2);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn state(name: &'static str, process: ApexProcessId) -> Step {
+    Step::State(
+        name,
+        KernApex.get_process_status(process).unwrap().process_state,
+    )
+}
+
+extern "C" fn target() {
+    super::record(
+        &TRACE,
+        Step::SuspendSelf("target", KernApex.suspend_self(APEX_TIME_INFINITY)),
+    );
+}
+
+extern "C" fn victim() {
+    super::record(&TRACE, Step::Ran("victim"));
+    super::finish();
+}
+
+extern "C" fn checker() {
+    let &(target, victim) = PROCESSES.get().unwrap();
+    let me = KernApex.get_my_id().unwrap();
+
+    super::record(&TRACE, state("target", target));
+    super::record(&TRACE, Step::Resume("target", KernApex.resume(target)));
+    super::record(&TRACE, state("target", target));
+    super::record(&TRACE, Step::Resume("target", KernApex.resume(target)));
+    super::record(&TRACE, Step::Suspend("target", KernApex.suspend(target)));
+
+    super::record(&TRACE, Step::Suspend("checker", KernApex.suspend(me)));
+    super::record(&TRACE, Step::Resume("checker", KernApex.resume(me)));
+
+    super::record(&TRACE, Step::Resume("victim", KernApex.resume(victim)));
+    super::record(&TRACE, Step::Suspend("victim", KernApex.suspend(victim)));
+    super::record(&TRACE, state("victim", victim));
+    super::record(&TRACE, Step::Suspend("victim", KernApex.suspend(victim)));
+
+    super::record(
+        &TRACE,
+        Step::SuspendSelf("checker", KernApex.suspend_self(0)),
+    );
+    let (suspend, elapsed) = super::timed(10 * MS, |timeout| KernApex.suspend_self(timeout));
+    super::record(&TRACE, Step::SuspendSelf("checker", suspend));
+    super::record(&TRACE, Step::Elapsed(elapsed));
+
+    super::record(&TRACE, Step::Resume("victim", KernApex.resume(victim)));
+    super::record(&TRACE, state("victim", victim));
+    super::record(&TRACE, Step::Resume("victim", KernApex.resume(victim)));
+
+    KernApex.lock_preemption().unwrap();
+    super::record(
+        &TRACE,
+        Step::SuspendSelf("checker", KernApex.suspend_self(10 * MS)),
+    );
+    KernApex.unlock_preemption().unwrap();
+
+    // lets the victim run, which is ready again
+    KernApex.timed_wait(10 * MS).unwrap();
+}
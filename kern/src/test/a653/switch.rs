@@ -0,0 +1,120 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_hal::{Cpu, Hal};
+use jrinx_multitask::runtime::Runtime;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Id(Result<(), ApexReturnCode>),
+    Set(&'static str, Result<(), ApexReturnCode>),
+    /// The names of the current and the next module schedules.
+    Status(&'static str, &'static str),
+    /// Whether the last switch took place at the frame start next to the request.
+    Switched(bool),
+    /// The offset and duration of the window of the partition.
+    Window(ApexSystemTime, ApexSystemTime),
+}
+
+static SCHEDULES: Once<(ApexScheduleId, ApexScheduleId)> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Switches to another module schedule, which takes effect at the end of the major frame.
+#[testdef]
+fn test() {
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Id(Err(InvalidConfig)),
+            Step::Status("default", "default"),
+            Step::Window(0, 10 * MS),
+            Step::Set("none", Err(InvalidParam)),
+            Step::Set("alt", Ok(())),
+            // still running the windows of the current one until the frame ends
+            Step::Status("default", "alt"),
+            Step::Window(0, 10 * MS),
+            Step::Status("alt", "alt"),
+            Step::Switched(true),
+            Step::Window(10 * MS, 10 * MS),
+            Step::Set("alt", Ok(())),
+            Step::Status("alt", "alt"),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_switch() {
+    super::start("switcher", switcher, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn name(id: ApexScheduleId) -> &'static str {
+    match *SCHEDULES.get().unwrap() {
+        (default, _) if id == default => "default",
+        (_, alt) if id == alt => "alt",
+        _ => "none",
+    }
+}
+
+fn status() -> ApexScheduleStatus {
+    let status = KernApex.get_module_schedule_status().unwrap();
+    super::record(
+        &TRACE,
+        Step::Status(name(status.current_schedule), name(status.next_schedule)),
+    );
+    status
+}
+
+fn window() {
+    let status = KernApex.get_window_status(0, 0).unwrap();
+    super::record(
+        &TRACE,
+        Step::Window(status.window_offset, status.window_duration),
+    );
+}
+
+fn set(id: ApexScheduleId) {
+    super::record(
+        &TRACE,
+        Step::Set(name(id), KernApex.set_module_schedule(id)),
+    );
+}
+
+extern "C" fn switcher() {
+    let id = |name: &str| KernApex.get_module_schedule_id(&name.try_into().unwrap());
+    super::record(&TRACE, Step::Id(id("none").map(|_| ())));
+    let &(_, alt) = SCHEDULES.call_once(|| (id("default").unwrap(), id("alt").unwrap()));
+
+    status();
+    window();
+
+    set(100);
+    let boundary = Runtime::with_current(|rt| rt.next_frame_start(hal!().cpu().get_time()))
+        .map(duration_as_time);
+    set(alt);
+    status();
+    window();
+
+    KernApex.timed_wait(40 * MS).unwrap();
+    let switched = status().time_of_last_schedule_switch;
+    super::record(&TRACE, Step::Switched(Some(switched) == boundary));
+    window();
+
+    set(alt);
+    status();
+
+    super::finish();
+}
@@ -0,0 +1,126 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::{Mutex, Once};
+
+use super::MS;
+
+const PERIOD: ApexSystemTime = 20 * MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Wait(&'static str, Result<(), ApexReturnCode>),
+    Ran(&'static str),
+    Elapsed(bool),
+    Replenish(&'static str, Result<(), ApexReturnCode>),
+    /// Whether the deadline of the process is the budget of its last replenishment away.
+    Deadline(&'static str, bool),
+}
+
+static PERIODIC: Once<ApexProcessId> = Once::new();
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Waits for a while, yields to a process of the same priority, and replenishes the budgets
+/// of an aperiodic and a periodic process, the latter up to its next release point.
+#[testdef]
+fn test() {
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Wait("first", Err(InvalidParam)),
+            Step::Wait("first", Err(InvalidParam)),
+            // a zero wait yields to the ready process of the same priority
+            Step::Ran("second"),
+            Step::Wait("first", Ok(())),
+            Step::Wait("first", Ok(())),
+            Step::Elapsed(true),
+            Step::Wait("first", Err(InvalidMode)),
+            Step::Replenish("first", Ok(())),
+            Step::Deadline("first", true),
+            Step::Replenish("first", Ok(())),
+            Step::Deadline("first", true),
+            Step::Replenish("first", Err(InvalidParam)),
+            Step::Replenish("periodic", Ok(())),
+            Step::Deadline("periodic", true),
+            Step::Replenish("periodic", Err(InvalidMode)),
+            Step::Replenish("periodic", Err(InvalidMode)),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_timing() {
+    PERIODIC.call_once(|| super::create("periodic", periodic, 2, PERIOD, 10 * MS));
+    super::start("first", first, 1);
+    super::start("second", second, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn replenish(name: &'static str, budget: ApexSystemTime) {
+    super::record(&TRACE, Step::Replenish(name, KernApex.replenish(budget)));
+}
+
+fn deadline(name: &'static str, budget: ApexSystemTime) {
+    let me = KernApex.get_my_id().unwrap();
+    let deadline = KernApex.get_process_status(me).unwrap().deadline_time;
+    let now = KernApex.get_time().unwrap();
+    super::record(
+        &TRACE,
+        Step::Deadline(
+            name,
+            match budget {
+                APEX_TIME_INFINITY => deadline == APEX_TIME_INFINITY,
+                budget => now < deadline && deadline <= now + budget,
+            },
+        ),
+    );
+}
+
+extern "C" fn first() {
+    super::record(
+        &TRACE,
+        Step::Wait("first", KernApex.timed_wait(APEX_TIME_INFINITY)),
+    );
+    super::record(&TRACE, Step::Wait("first", KernApex.timed_wait(-1)));
+    super::record(&TRACE, Step::Wait("first", KernApex.timed_wait(0)));
+    let (wait, elapsed) = super::timed(10 * MS, |timeout| KernApex.timed_wait(timeout));
+    super::record(&TRACE, Step::Wait("first", wait));
+    super::record(&TRACE, Step::Elapsed(elapsed));
+
+    KernApex.lock_preemption().unwrap();
+    super::record(&TRACE, Step::Wait("first", KernApex.timed_wait(10 * MS)));
+    KernApex.unlock_preemption().unwrap();
+
+    replenish("first", 10 * MS);
+    deadline("first", 10 * MS);
+    replenish("first", APEX_TIME_INFINITY);
+    deadline("first", APEX_TIME_INFINITY);
+    replenish("first", -2);
+
+    let &periodic = PERIODIC.get().unwrap();
+    KernApex.start(periodic).unwrap();
+}
+
+extern "C" fn second() {
+    super::record(&TRACE, Step::Ran("second"));
+}
+
+extern "C" fn periodic() {
+    replenish("periodic", 5 * MS);
+    deadline("periodic", 5 * MS);
+    // past the next release point
+    replenish("periodic", PERIOD + 5 * MS);
+    replenish("periodic", APEX_TIME_INFINITY);
+
+    super::finish();
+}
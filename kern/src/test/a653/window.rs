@@ -0,0 +1,118 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use jrinx_apex::*;
+use jrinx_hal::{Cpu, Hal, Interrupt};
+use jrinx_multitask::runtime::Runtime;
+use jrinx_partdef::partdef;
+use jrinx_syscall::KernApex;
+use jrinx_testdef::testdef;
+use spin::Mutex;
+
+use super::MS;
+
+const FRAME: ApexSystemTime = 20 * MS;
+const WINDOW: ApexSystemTime = 10 * MS;
+const OVERRUN: ApexSystemTime = 3 * MS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    /// Whether the window belongs to the partition, with its offset and duration.
+    Window(Result<(bool, ApexSystemTime, ApexSystemTime), ApexReturnCode>),
+    /// Whether the windows are activated each frame and their time is consumed by the spinner.
+    Consumed(bool),
+    /// Whether the window overrun by the spinner is reported.
+    Overrun(bool),
+}
+
+static TRACE: Mutex<Vec<Step>> = Mutex::new(Vec::new());
+
+/// Accounts the time consumed in the window of a partition, which is overrun once by a process
+/// spinning with interrupts disabled past its end.
+#[testdef]
+fn test() {
+    use ApexReturnCode::*;
+
+    super::run(Duration::from_secs(2));
+
+    assert_eq!(
+        *TRACE.lock(),
+        [
+            Step::Window(Err(InvalidParam)),
+            Step::Window(Err(InvalidParam)),
+            Step::Window(Err(InvalidParam)),
+            Step::Window(Ok((true, 0, WINDOW))),
+            Step::Consumed(true),
+            Step::Overrun(true),
+        ]
+    );
+}
+
+#[partdef]
+extern "C" fn a653_window() {
+    super::start("checker", checker, 2);
+    super::start("spinner", spinner, 1);
+    KernApex
+        .set_partition_mode(ApexOperatingMode::Normal)
+        .unwrap();
+}
+
+fn window(
+    core_id: ApexProcessorCoreId,
+    index: ApexWindowIndex,
+) -> Result<ApexWindowStatus, ApexReturnCode> {
+    let partition = KernApex.get_partition_status().unwrap().identifier;
+    let status = KernApex.get_window_status(core_id, index);
+    super::record(
+        &TRACE,
+        Step::Window(status.map(|status| {
+            (
+                status.partition_id == partition,
+                status.window_offset,
+                status.window_duration,
+            )
+        })),
+    );
+    status
+}
+
+extern "C" fn checker() {
+    let _ = window(0, -1);
+    let _ = window(0, 1);
+    let _ = window(hal!().cpu().nproc() as _, 0);
+
+    // the spinner consumes the windows of the first frames, and overruns the one after
+    KernApex.timed_wait(5 * FRAME).unwrap();
+    let status = window(0, 0).unwrap();
+    super::record(
+        &TRACE,
+        Step::Consumed(
+            status.activations >= 5
+                && status.last_consumed <= status.last_end - status.last_start
+                && status.total_consumed >= 2 * (WINDOW - MS),
+        ),
+    );
+    super::record(
+        &TRACE,
+        Step::Overrun(status.overruns >= 1 && status.max_overrun >= OVERRUN - MS),
+    );
+
+    super::finish();
+}
+
+extern "C" fn spinner() {
+    let start = KernApex.get_time().unwrap();
+    while KernApex.get_time().unwrap() - start < 2 * FRAME {
+        core::hint::spin_loop();
+    }
+
+    let now = hal!().cpu().get_time();
+    let next = Runtime::with_current(|rt| rt.next_frame_start(now)).unwrap();
+    let offset = (duration_as_time(now) - duration_as_time(next)).rem_euclid(FRAME);
+    let end = now + time_as_duration(WINDOW - offset + OVERRUN);
+    hal!().interrupt().with_saved_off(|| {
+        while hal!().cpu().get_time() < end {
+            core::hint::spin_loop();
+        }
+    });
+}
@@ -1,3 +1,4 @@
+mod a653;
 mod heap;
 mod mm;
 mod stack;
@@ -0,0 +1,450 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::*;
+
+const MAGIC: &[u8; 4] = b"JRMC";
+const VERSION: u32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    InvalidMagic,
+    InvalidVersion(u32),
+    UnexpectedEnd,
+    InvalidUtf8,
+    InvalidTag(u8),
+    TrailingBytes,
+}
+
+pub(crate) fn encode(conf: &ModuleConf) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(MAGIC);
+    VERSION.encode(&mut buf);
+    Encode::encode(conf, &mut buf);
+    buf
+}
+
+pub(crate) fn decode(blob: &[u8]) -> Result<ModuleConf, DecodeError> {
+    let mut reader = Reader(blob);
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(DecodeError::InvalidMagic);
+    }
+    match u32::decode(&mut reader)? {
+        VERSION => {}
+        version => return Err(DecodeError::InvalidVersion(version)),
+    }
+    let conf = <ModuleConf as Decode>::decode(&mut reader)?;
+    if !reader.0.is_empty() {
+        return Err(DecodeError::TrailingBytes);
+    }
+    Ok(conf)
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.0.len() < len {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+}
+
+trait Encode {
+    fn encode(&self, buf: &mut Vec<u8>);
+}
+
+trait Decode: Sized {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError>;
+}
+
+macro_rules! impl_codec_for_int {
+    ($($ty:ty),*) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, buf: &mut Vec<u8>) {
+                    buf.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+                    Ok(Self::from_le_bytes(reader.take_array()?))
+                }
+            }
+        )*
+    };
+}
+
+impl_codec_for_int!(u8, u32, u64, i64);
+
+impl Encode for bool {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        (*self as u8).encode(buf);
+    }
+}
+
+impl Decode for bool {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        match u8::decode(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        (self.len() as u32).encode(buf);
+        buf.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Decode for String {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        let len = u32::decode(reader)? as usize;
+        let bytes = reader.take(len)?;
+        core::str::from_utf8(bytes)
+            .map(String::from)
+            .map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        (self.len() as u32).encode(buf);
+        self.iter().for_each(|item| item.encode(buf));
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        let len = u32::decode(reader)? as usize;
+        (0..len).map(|_| T::decode(reader)).collect()
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            None => 0u8.encode(buf),
+            Some(value) => {
+                1u8.encode(buf);
+                value.encode(buf);
+            }
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        match u8::decode(reader)? {
+            0 => Ok(None),
+            1 => Ok(Some(T::decode(reader)?)),
+            tag => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+}
+
+macro_rules! impl_codec_for_struct {
+    ($ty:ident { $($field:ident),* $(,)? }) => {
+        impl Encode for $ty {
+            fn encode(&self, buf: &mut Vec<u8>) {
+                $(self.$field.encode(buf);)*
+            }
+        }
+
+        impl Decode for $ty {
+            fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+                Ok(Self {
+                    $($field: Decode::decode(reader)?,)*
+                })
+            }
+        }
+    };
+}
+
+impl_codec_for_struct!(ModuleConf {
+    partitions,
+    channels,
+    schedules,
+    hm_table,
+    window_threshold,
+});
+
+impl_codec_for_struct!(PartitionConf {
+    name,
+    kind,
+    memory,
+    period,
+    duration,
+    num_cores,
+    hm_table,
+});
+
+impl_codec_for_struct!(ChannelConf {
+    name,
+    max_message_size,
+    source,
+    destinations,
+    kind,
+});
+
+impl_codec_for_struct!(PortConf { partition, port });
+
+impl_codec_for_struct!(ScheduleConf {
+    name,
+    cpu_id,
+    major_frame,
+    windows,
+    background,
+});
+
+impl_codec_for_struct!(WindowConf {
+    partition,
+    offset,
+    duration,
+    init,
+});
+
+impl_codec_for_struct!(BackgroundConf { partition, init });
+
+impl_codec_for_struct!(HmConf { error, action });
+
+impl Encode for PartitionKindConf {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Kern { entry } => {
+                0u8.encode(buf);
+                entry.encode(buf);
+            }
+            Self::User { program } => {
+                1u8.encode(buf);
+                program.encode(buf);
+            }
+        }
+    }
+}
+
+impl Decode for PartitionKindConf {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        match u8::decode(reader)? {
+            0 => Ok(Self::Kern {
+                entry: String::decode(reader)?,
+            }),
+            1 => Ok(Self::User {
+                program: String::decode(reader)?,
+            }),
+            tag => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+}
+
+impl Encode for ChannelKindConf {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Sampling => 0u8.encode(buf),
+            Self::Queuing { max_nb_message } => {
+                1u8.encode(buf);
+                max_nb_message.encode(buf);
+            }
+        }
+    }
+}
+
+impl Decode for ChannelKindConf {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        match u8::decode(reader)? {
+            0 => Ok(Self::Sampling),
+            1 => Ok(Self::Queuing {
+                max_nb_message: u32::decode(reader)?,
+            }),
+            tag => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+}
+
+impl Encode for HmActionConf {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        (*self as u8).encode(buf);
+    }
+}
+
+impl Decode for HmActionConf {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        u8::decode(reader)?
+            .try_into()
+            .map_err(DecodeError::InvalidTag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use super::*;
+
+    fn conf() -> ModuleConf {
+        ModuleConf {
+            partitions: vec![
+                PartitionConf {
+                    name: "p1".to_string(),
+                    kind: PartitionKindConf::Kern {
+                        entry: "idle".to_string(),
+                    },
+                    memory: 0x8000,
+                    period: 2_000_000,
+                    duration: 1_000_000,
+                    num_cores: 1,
+                    hm_table: vec![HmConf {
+                        error: 1,
+                        action: HmActionConf::WarmStart,
+                    }],
+                },
+                PartitionConf {
+                    name: "p2".to_string(),
+                    kind: PartitionKindConf::User {
+                        program: "idle".to_string(),
+                    },
+                    memory: 0x10000,
+                    period: -1,
+                    duration: -1,
+                    num_cores: 2,
+                    hm_table: vec![],
+                },
+            ],
+            channels: vec![
+                ChannelConf {
+                    name: "sampling".to_string(),
+                    max_message_size: 64,
+                    source: PortConf {
+                        partition: "p1".to_string(),
+                        port: "out".to_string(),
+                    },
+                    destinations: vec![PortConf {
+                        partition: "p2".to_string(),
+                        port: "in".to_string(),
+                    }],
+                    kind: ChannelKindConf::Sampling,
+                },
+                ChannelConf {
+                    name: "queuing".to_string(),
+                    max_message_size: 32,
+                    source: PortConf {
+                        partition: "p2".to_string(),
+                        port: "out".to_string(),
+                    },
+                    destinations: vec![PortConf {
+                        partition: "p1".to_string(),
+                        port: "in".to_string(),
+                    }],
+                    kind: ChannelKindConf::Queuing { max_nb_message: 8 },
+                },
+            ],
+            schedules: vec![ScheduleConf {
+                name: "default".to_string(),
+                cpu_id: 0,
+                major_frame: 2_000_000,
+                windows: vec![WindowConf {
+                    partition: "p1".to_string(),
+                    offset: 0,
+                    duration: 1_000_000,
+                    init: true,
+                }],
+                background: Some(BackgroundConf {
+                    partition: "p2".to_string(),
+                    init: false,
+                }),
+            }],
+            hm_table: vec![HmConf {
+                error: 0,
+                action: HmActionConf::Reset,
+            }],
+            window_threshold: Some(100_000),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(decode(&encode(&conf())), Ok(conf()));
+        assert_eq!(
+            decode(&encode(&ModuleConf::default())),
+            Ok(ModuleConf::default())
+        );
+    }
+
+    #[test]
+    fn invalid_magic() {
+        let mut blob = encode(&conf());
+        blob[0] = b'X';
+        assert_eq!(decode(&blob), Err(DecodeError::InvalidMagic));
+        assert_eq!(decode(b""), Err(DecodeError::UnexpectedEnd));
+    }
+
+    #[test]
+    fn invalid_version() {
+        let mut blob = encode(&conf());
+        blob[MAGIC.len()..MAGIC.len() + 4].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        assert_eq!(decode(&blob), Err(DecodeError::InvalidVersion(VERSION + 1)));
+    }
+
+    #[test]
+    fn truncated() {
+        let blob = encode(&conf());
+        for len in 0..blob.len() {
+            assert!(decode(&blob[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn trailing_bytes() {
+        let mut blob = encode(&conf());
+        blob.push(0);
+        assert_eq!(decode(&blob), Err(DecodeError::TrailingBytes));
+    }
+
+    #[test]
+    fn invalid_tag() {
+        let conf = ModuleConf {
+            hm_table: vec![HmConf {
+                error: 0,
+                action: HmActionConf::Reset,
+            }],
+            ..Default::default()
+        };
+        let mut blob = encode(&conf);
+        // the window threshold (`None`) follows the action of the only recovery action
+        let action = blob.len() - 2;
+        blob[action] = 6;
+        assert_eq!(decode(&blob), Err(DecodeError::InvalidTag(6)));
+
+        let mut blob = encode(&conf);
+        *blob.last_mut().unwrap() = 2;
+        assert_eq!(decode(&blob), Err(DecodeError::InvalidTag(2)));
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let conf = ModuleConf {
+            schedules: vec![ScheduleConf {
+                name: "s".to_string(),
+                cpu_id: 0,
+                major_frame: 1,
+                windows: vec![],
+                background: None,
+            }],
+            ..Default::default()
+        };
+        let mut blob = encode(&conf);
+        let name = blob.iter().position(|&byte| byte == b's').unwrap();
+        blob[name] = 0xff;
+        assert_eq!(decode(&blob), Err(DecodeError::InvalidUtf8));
+    }
+}
@@ -0,0 +1,146 @@
+//! The module configuration of Jrinx, which is converted from the ARINC 653 XML configuration
+//! by `xtask` and embedded in the kernel image as a compact binary blob.
+
+#![no_std]
+
+extern crate alloc;
+
+mod codec;
+
+use alloc::{string::String, vec::Vec};
+
+pub use codec::DecodeError;
+
+/// Time in nanoseconds, where a negative value indicates an infinite time.
+pub type ModconfTime = i64;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ModuleConf {
+    pub partitions: Vec<PartitionConf>,
+    pub channels: Vec<ChannelConf>,
+    pub schedules: Vec<ScheduleConf>,
+    pub hm_table: Vec<HmConf>,
+    /// The lateness of a window start or end beyond which the window is logged.
+    pub window_threshold: Option<ModconfTime>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionConf {
+    pub name: String,
+    pub kind: PartitionKindConf,
+    pub memory: u64,
+    pub period: ModconfTime,
+    pub duration: ModconfTime,
+    pub num_cores: u32,
+    pub hm_table: Vec<HmConf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartitionKindConf {
+    Kern { entry: String },
+    User { program: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelConf {
+    pub name: String,
+    pub max_message_size: u32,
+    pub source: PortConf,
+    pub destinations: Vec<PortConf>,
+    pub kind: ChannelKindConf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelKindConf {
+    Sampling,
+    Queuing { max_nb_message: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortConf {
+    pub partition: String,
+    pub port: String,
+}
+
+/// The schedule of a core, which repeats its windows every major frame.
+///
+/// The schedules of all cores sharing a name make up a module schedule, and the module
+/// schedule declared first is the initial one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleConf {
+    pub name: String,
+    pub cpu_id: u32,
+    pub major_frame: ModconfTime,
+    pub windows: Vec<WindowConf>,
+    pub background: Option<BackgroundConf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowConf {
+    pub partition: String,
+    pub offset: ModconfTime,
+    pub duration: ModconfTime,
+    /// Whether the initial process of the partition runs in this window.
+    pub init: bool,
+}
+
+/// The partition running in the gaps between the windows of a core.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackgroundConf {
+    pub partition: String,
+    /// Whether the initial process of the partition runs in the background.
+    pub init: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HmConf {
+    /// The `ApexErrorCode` of the error.
+    pub error: u32,
+    pub action: HmActionConf,
+}
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HmActionConf {
+    Ignore = 0,
+    StopProcess = 1,
+    ColdStart = 2,
+    WarmStart = 3,
+    Idle = 4,
+    Reset = 5,
+}
+
+impl ModuleConf {
+    /// Returns whether there is no partition, channel, schedule or recovery action, regardless
+    /// of the module-wide settings.
+    pub fn is_empty(&self) -> bool {
+        self.partitions.is_empty()
+            && self.channels.is_empty()
+            && self.schedules.is_empty()
+            && self.hm_table.is_empty()
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        codec::encode(self)
+    }
+
+    pub fn decode(blob: &[u8]) -> Result<Self, DecodeError> {
+        codec::decode(blob)
+    }
+}
+
+impl TryFrom<u8> for HmActionConf {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Ignore),
+            1 => Ok(Self::StopProcess),
+            2 => Ok(Self::ColdStart),
+            3 => Ok(Self::WarmStart),
+            4 => Ok(Self::Idle),
+            5 => Ok(Self::Reset),
+            _ => Err(value),
+        }
+    }
+}
@@ -144,7 +144,10 @@ class Test:
                 },
                 inc_dirs=[dir for dir in include_dirs if dir.is_dir()],
             )
-            return Test(conf, f'-t {test_name}')
+            bootargs = f'-t {test_name}'
+            if (extra := conf.get('bootargs')) is not None:
+                bootargs += ' ' + extra
+            return Test(conf, bootargs)
         else:
             raise NotImplementedError()
 
@@ -0,0 +1,54 @@
+use jrinx_abi::sysfn::*;
+use jrinx_apex::*;
+
+pub struct Blackboard;
+
+impl ApexBlackboardService for Blackboard {
+    fn create_blackboard(
+        &self,
+        blackboard_name: &ApexBlackboardName,
+        max_message_size: ApexMessageSize,
+    ) -> Result<ApexBlackboardId, ApexReturnCode> {
+        let mut id = ApexBlackboardId::default();
+        sys_create_blackboard(blackboard_name, max_message_size, &mut id).as_result(id)
+    }
+
+    fn display_blackboard(
+        &self,
+        blackboard_id: ApexBlackboardId,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode> {
+        sys_display_blackboard(blackboard_id, message.as_ptr(), message.len() as _).into()
+    }
+
+    fn read_blackboard(
+        &self,
+        blackboard_id: ApexBlackboardId,
+        time_out: ApexSystemTime,
+        message: &mut [ApexByte],
+    ) -> Result<ApexMessageSize, ApexReturnCode> {
+        let mut length = message.len() as ApexMessageSize;
+        sys_read_blackboard(blackboard_id, time_out, message.as_mut_ptr(), &mut length)
+            .as_result(length)
+    }
+
+    fn clear_blackboard(&self, blackboard_id: ApexBlackboardId) -> Result<(), ApexReturnCode> {
+        sys_clear_blackboard(blackboard_id).into()
+    }
+
+    fn get_blackboard_id(
+        &self,
+        blackboard_name: &ApexBlackboardName,
+    ) -> Result<ApexBlackboardId, ApexReturnCode> {
+        let mut id = ApexBlackboardId::default();
+        sys_get_blackboard_id(blackboard_name, &mut id).as_result(id)
+    }
+
+    fn get_blackboard_status(
+        &self,
+        blackboard_id: ApexBlackboardId,
+    ) -> Result<ApexBlackboardStatus, ApexReturnCode> {
+        let mut status = ApexBlackboardStatus::default();
+        sys_get_blackboard_status(blackboard_id, &mut status).as_result(status)
+    }
+}
@@ -0,0 +1,56 @@
+use jrinx_abi::sysfn::*;
+use jrinx_apex::*;
+
+pub struct Buffer;
+
+impl ApexBufferService for Buffer {
+    fn create_buffer(
+        &self,
+        buffer_name: &ApexBufferName,
+        max_message_size: ApexMessageSize,
+        max_nb_message: ApexMessageRange,
+        queuing_discipline: ApexQueueDiscipline,
+    ) -> Result<ApexBufferId, ApexReturnCode> {
+        let mut id = ApexBufferId::default();
+        sys_create_buffer(
+            buffer_name,
+            max_message_size,
+            max_nb_message,
+            queuing_discipline,
+            &mut id,
+        )
+        .as_result(id)
+    }
+
+    fn send_buffer(
+        &self,
+        buffer_id: ApexBufferId,
+        message: &[ApexByte],
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        sys_send_buffer(buffer_id, message.as_ptr(), message.len() as _, time_out).into()
+    }
+
+    fn receive_buffer(
+        &self,
+        buffer_id: ApexBufferId,
+        time_out: ApexSystemTime,
+        message: &mut [ApexByte],
+    ) -> Result<ApexMessageSize, ApexReturnCode> {
+        let mut length = message.len() as ApexMessageSize;
+        sys_receive_buffer(buffer_id, time_out, message.as_mut_ptr(), &mut length).as_result(length)
+    }
+
+    fn get_buffer_id(&self, buffer_name: &ApexBufferName) -> Result<ApexBufferId, ApexReturnCode> {
+        let mut id = ApexBufferId::default();
+        sys_get_buffer_id(buffer_name, &mut id).as_result(id)
+    }
+
+    fn get_buffer_status(
+        &self,
+        buffer_id: ApexBufferId,
+    ) -> Result<ApexBufferStatus, ApexReturnCode> {
+        let mut status = ApexBufferStatus::default();
+        sys_get_buffer_status(buffer_id, &mut status).as_result(status)
+    }
+}
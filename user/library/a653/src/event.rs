@@ -0,0 +1,37 @@
+use jrinx_abi::sysfn::*;
+use jrinx_apex::*;
+
+pub struct Event;
+
+impl ApexEventService for Event {
+    fn create_event(&self, event_name: &ApexEventName) -> Result<ApexEventId, ApexReturnCode> {
+        let mut id = ApexEventId::default();
+        sys_create_event(event_name, &mut id).as_result(id)
+    }
+
+    fn set_event(&self, event_id: ApexEventId) -> Result<(), ApexReturnCode> {
+        sys_set_event(event_id).into()
+    }
+
+    fn reset_event(&self, event_id: ApexEventId) -> Result<(), ApexReturnCode> {
+        sys_reset_event(event_id).into()
+    }
+
+    fn wait_event(
+        &self,
+        event_id: ApexEventId,
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        sys_wait_event(event_id, time_out).into()
+    }
+
+    fn get_event_id(&self, event_name: &ApexEventName) -> Result<ApexEventId, ApexReturnCode> {
+        let mut id = ApexEventId::default();
+        sys_get_event_id(event_name, &mut id).as_result(id)
+    }
+
+    fn get_event_status(&self, event_id: ApexEventId) -> Result<ApexEventStatus, ApexReturnCode> {
+        let mut status = ApexEventStatus::default();
+        sys_get_event_status(event_id, &mut status).as_result(status)
+    }
+}
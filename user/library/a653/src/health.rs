@@ -0,0 +1,31 @@
+use jrinx_abi::sysfn::*;
+use jrinx_apex::*;
+
+pub struct Health;
+
+impl ApexHealthService for Health {
+    fn report_application_message(&self, message: &[ApexByte]) -> Result<(), ApexReturnCode> {
+        sys_report_application_message(message.as_ptr(), message.len() as _).into()
+    }
+
+    fn create_error_handler(
+        &self,
+        entry_point: ApexSystemAddress,
+        stack_size: ApexStackSize,
+    ) -> Result<(), ApexReturnCode> {
+        sys_create_error_handler(entry_point.into(), stack_size).into()
+    }
+
+    fn get_error_status(&self) -> Result<ApexErrorStatus, ApexReturnCode> {
+        let mut status = ApexErrorStatus::default();
+        sys_get_error_status(&mut status).as_result(status)
+    }
+
+    fn raise_application_error(
+        &self,
+        error_code: ApexErrorCode,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode> {
+        sys_raise_application_error(error_code, message.as_ptr(), message.len() as _).into()
+    }
+}
@@ -1,6 +1,16 @@
 #![no_std]
 
+mod blackboard;
+mod buffer;
+mod event;
+mod health;
+mod mutex;
 mod partition;
 mod process;
+mod queuing;
+mod sampling;
+mod schedule;
+mod semaphore;
+mod time;
 
 pub mod prelude;
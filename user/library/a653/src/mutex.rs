@@ -0,0 +1,54 @@
+use jrinx_abi::sysfn::*;
+use jrinx_apex::*;
+
+pub struct Mutex;
+
+impl ApexMutexService for Mutex {
+    fn create_mutex(
+        &self,
+        mutex_name: &ApexMutexName,
+        mutex_priority: ApexPriority,
+        queuing_discipline: ApexQueueDiscipline,
+    ) -> Result<ApexMutexId, ApexReturnCode> {
+        let mut id = ApexMutexId::default();
+        sys_create_mutex(mutex_name, mutex_priority, queuing_discipline, &mut id).as_result(id)
+    }
+
+    fn acquire_mutex(
+        &self,
+        mutex_id: ApexMutexId,
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        sys_acquire_mutex(mutex_id, time_out).into()
+    }
+
+    fn release_mutex(&self, mutex_id: ApexMutexId) -> Result<(), ApexReturnCode> {
+        sys_release_mutex(mutex_id).into()
+    }
+
+    fn reset_mutex(
+        &self,
+        mutex_id: ApexMutexId,
+        process_id: ApexProcessId,
+    ) -> Result<(), ApexReturnCode> {
+        sys_reset_mutex(mutex_id, process_id).into()
+    }
+
+    fn get_mutex_id(&self, mutex_name: &ApexMutexName) -> Result<ApexMutexId, ApexReturnCode> {
+        let mut id = ApexMutexId::default();
+        sys_get_mutex_id(mutex_name, &mut id).as_result(id)
+    }
+
+    fn get_mutex_status(&self, mutex_id: ApexMutexId) -> Result<ApexMutexStatus, ApexReturnCode> {
+        let mut status = ApexMutexStatus::default();
+        sys_get_mutex_status(mutex_id, &mut status).as_result(status)
+    }
+
+    fn get_process_mutex_state(
+        &self,
+        process_id: ApexProcessId,
+    ) -> Result<ApexMutexId, ApexReturnCode> {
+        let mut id = ApexMutexId::default();
+        sys_get_process_mutex_state(process_id, &mut id).as_result(id)
+    }
+}
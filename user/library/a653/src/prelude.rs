@@ -1,4 +1,13 @@
+pub use crate::blackboard::*;
+pub use crate::buffer::*;
+pub use crate::event::*;
+pub use crate::health::*;
+pub use crate::mutex::*;
 pub use crate::partition::*;
 pub use crate::process::*;
+pub use crate::queuing::*;
+pub use crate::sampling::*;
+pub use crate::semaphore::*;
+pub use crate::time::*;
 
 pub use jrinx_apex::*;
@@ -30,30 +30,30 @@ impl ApexProcessService for Process {
 
     fn set_priority(
         &self,
-        _process_id: ApexProcessId,
-        _priority: ApexPriority,
+        process_id: ApexProcessId,
+        priority: ApexPriority,
     ) -> Result<(), ApexReturnCode> {
-        todo!()
+        sys_set_priority(process_id, priority).into()
     }
 
-    fn suspend_self(&self, _time_out: ApexSystemTime) -> Result<(), ApexReturnCode> {
-        todo!()
+    fn suspend_self(&self, time_out: ApexSystemTime) -> Result<(), ApexReturnCode> {
+        sys_suspend_self(time_out).into()
     }
 
-    fn suspend(&self, _process_id: ApexProcessId) -> Result<(), ApexReturnCode> {
-        todo!()
+    fn suspend(&self, process_id: ApexProcessId) -> Result<(), ApexReturnCode> {
+        sys_suspend(process_id).into()
     }
 
-    fn resume(&self, _process_id: ApexProcessId) -> Result<(), ApexReturnCode> {
-        todo!()
+    fn resume(&self, process_id: ApexProcessId) -> Result<(), ApexReturnCode> {
+        sys_resume(process_id).into()
     }
 
     fn stop_self(&self) -> ! {
-        todo!()
+        sys_stop_self()
     }
 
-    fn stop(&self, _process_id: ApexProcessId) -> Result<(), ApexReturnCode> {
-        todo!()
+    fn stop(&self, process_id: ApexProcessId) -> Result<(), ApexReturnCode> {
+        sys_stop(process_id).into()
     }
 
     fn start(&self, process_id: ApexProcessId) -> Result<(), ApexReturnCode> {
@@ -62,18 +62,20 @@ impl ApexProcessService for Process {
 
     fn delayed_start(
         &self,
-        _process_id: ApexProcessId,
-        _delay_time: ApexSystemTime,
+        process_id: ApexProcessId,
+        delay_time: ApexSystemTime,
     ) -> Result<(), ApexReturnCode> {
-        todo!()
+        sys_delayed_start(process_id, delay_time).into()
     }
 
     fn lock_preemption(&self) -> Result<ApexLockLevel, ApexReturnCode> {
-        todo!()
+        let mut lock_level = ApexLockLevel::default();
+        sys_lock_preemption(&mut lock_level).as_result(lock_level)
     }
 
     fn unlock_preemption(&self) -> Result<ApexLockLevel, ApexReturnCode> {
-        todo!()
+        let mut lock_level = ApexLockLevel::default();
+        sys_unlock_preemption(&mut lock_level).as_result(lock_level)
     }
 
     fn get_my_id(&self) -> Result<ApexProcessId, ApexReturnCode> {
@@ -0,0 +1,72 @@
+use jrinx_abi::sysfn::*;
+use jrinx_apex::*;
+
+pub struct QueuingPort;
+
+impl ApexQueuingPortService for QueuingPort {
+    fn create_queuing_port(
+        &self,
+        queuing_port_name: &ApexQueuingPortName,
+        max_message_size: ApexMessageSize,
+        max_nb_message: ApexMessageRange,
+        port_direction: ApexPortDirection,
+        queuing_discipline: ApexQueueDiscipline,
+    ) -> Result<ApexQueuingPortId, ApexReturnCode> {
+        let mut id = ApexQueuingPortId::default();
+        sys_create_queuing_port(
+            queuing_port_name,
+            max_message_size,
+            max_nb_message,
+            port_direction,
+            queuing_discipline,
+            &mut id,
+        )
+        .as_result(id)
+    }
+
+    fn send_queuing_message(
+        &self,
+        queuing_port_id: ApexQueuingPortId,
+        message: &[ApexByte],
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        sys_send_queuing_message(
+            queuing_port_id,
+            message.as_ptr(),
+            message.len() as _,
+            time_out,
+        )
+        .into()
+    }
+
+    fn receive_queuing_message(
+        &self,
+        queuing_port_id: ApexQueuingPortId,
+        time_out: ApexSystemTime,
+        message: &mut [ApexByte],
+    ) -> Result<ApexMessageSize, ApexReturnCode> {
+        let mut length = message.len() as ApexMessageSize;
+        sys_receive_queuing_message(queuing_port_id, time_out, message.as_mut_ptr(), &mut length)
+            .as_result(length)
+    }
+
+    fn get_queuing_port_id(
+        &self,
+        queuing_port_name: &ApexQueuingPortName,
+    ) -> Result<ApexQueuingPortId, ApexReturnCode> {
+        let mut id = ApexQueuingPortId::default();
+        sys_get_queuing_port_id(queuing_port_name, &mut id).as_result(id)
+    }
+
+    fn get_queuing_port_status(
+        &self,
+        queuing_port_id: ApexQueuingPortId,
+    ) -> Result<ApexQueuingPortStatus, ApexReturnCode> {
+        let mut status = ApexQueuingPortStatus::default();
+        sys_get_queuing_port_status(queuing_port_id, &mut status).as_result(status)
+    }
+
+    fn clear_queuing_port(&self, queuing_port_id: ApexQueuingPortId) -> Result<(), ApexReturnCode> {
+        sys_clear_queuing_port(queuing_port_id).into()
+    }
+}
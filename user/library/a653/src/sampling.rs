@@ -0,0 +1,66 @@
+use jrinx_abi::sysfn::*;
+use jrinx_apex::*;
+
+pub struct SamplingPort;
+
+impl ApexSamplingPortService for SamplingPort {
+    fn create_sampling_port(
+        &self,
+        sampling_port_name: &ApexSamplingPortName,
+        max_message_size: ApexMessageSize,
+        port_direction: ApexPortDirection,
+        refresh_period: ApexSystemTime,
+    ) -> Result<ApexSamplingPortId, ApexReturnCode> {
+        let mut id = ApexSamplingPortId::default();
+        sys_create_sampling_port(
+            sampling_port_name,
+            max_message_size,
+            port_direction,
+            refresh_period,
+            &mut id,
+        )
+        .as_result(id)
+    }
+
+    fn write_sampling_message(
+        &self,
+        sampling_port_id: ApexSamplingPortId,
+        message: &[ApexByte],
+    ) -> Result<(), ApexReturnCode> {
+        sys_write_sampling_message(sampling_port_id, message.as_ptr(), message.len() as _).into()
+    }
+
+    fn read_sampling_message(
+        &self,
+        sampling_port_id: ApexSamplingPortId,
+        message: &mut [ApexByte],
+    ) -> Result<(ApexMessageSize, ApexValidity), ApexReturnCode> {
+        // The length is passed in as the size of the buffer, which only has to hold the
+        // message currently in the port, and comes back as the size of that message.
+        let mut length = message.len() as ApexMessageSize;
+        let mut validity = ApexValidity::default();
+        sys_read_sampling_message(
+            sampling_port_id,
+            message.as_mut_ptr(),
+            &mut length,
+            &mut validity,
+        )
+        .as_result((length, validity))
+    }
+
+    fn get_sampling_port_id(
+        &self,
+        sampling_port_name: &ApexSamplingPortName,
+    ) -> Result<ApexSamplingPortId, ApexReturnCode> {
+        let mut id = ApexSamplingPortId::default();
+        sys_get_sampling_port_id(sampling_port_name, &mut id).as_result(id)
+    }
+
+    fn get_sampling_port_status(
+        &self,
+        sampling_port_id: ApexSamplingPortId,
+    ) -> Result<ApexSamplingPortStatus, ApexReturnCode> {
+        let mut status = ApexSamplingPortStatus::default();
+        sys_get_sampling_port_status(sampling_port_id, &mut status).as_result(status)
+    }
+}
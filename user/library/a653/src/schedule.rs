@@ -0,0 +1,48 @@
+use jrinx_abi::sysfn::*;
+use jrinx_apex::*;
+
+pub struct Schedule;
+
+impl ApexScheduleService for Schedule {
+    fn set_module_schedule(&self, schedule_id: ApexScheduleId) -> Result<(), ApexReturnCode> {
+        sys_set_module_schedule(schedule_id).into()
+    }
+
+    fn get_module_schedule_status(&self) -> Result<ApexScheduleStatus, ApexReturnCode> {
+        let mut status = ApexScheduleStatus::default();
+        sys_get_module_schedule_status(&mut status).as_result(status)
+    }
+
+    fn get_module_schedule_id(
+        &self,
+        schedule_name: &ApexScheduleName,
+    ) -> Result<ApexScheduleId, ApexReturnCode> {
+        let mut id = ApexScheduleId::default();
+        sys_get_module_schedule_id(schedule_name, &mut id).as_result(id)
+    }
+
+    fn get_window_status(
+        &self,
+        core_id: ApexProcessorCoreId,
+        window_index: ApexWindowIndex,
+    ) -> Result<ApexWindowStatus, ApexReturnCode> {
+        let mut status = ApexWindowStatus::default();
+        sys_get_window_status(core_id, window_index, &mut status).as_result(status)
+    }
+
+    fn get_gap_status(
+        &self,
+        core_id: ApexProcessorCoreId,
+    ) -> Result<ApexGapStatus, ApexReturnCode> {
+        let mut status = ApexGapStatus::default();
+        sys_get_gap_status(core_id, &mut status).as_result(status)
+    }
+
+    fn get_timing_event(
+        &self,
+        event_index: ApexTimingEventIndex,
+    ) -> Result<ApexTimingEvent, ApexReturnCode> {
+        let mut event = ApexTimingEvent::default();
+        sys_get_timing_event(event_index, &mut event).as_result(event)
+    }
+}
@@ -0,0 +1,52 @@
+use jrinx_abi::sysfn::*;
+use jrinx_apex::*;
+
+pub struct Semaphore;
+
+impl ApexSemaphoreService for Semaphore {
+    fn create_semaphore(
+        &self,
+        semaphore_name: &ApexSemaphoreName,
+        current_value: ApexSemaphoreValue,
+        maximum_value: ApexSemaphoreValue,
+        queuing_discipline: ApexQueueDiscipline,
+    ) -> Result<ApexSemaphoreId, ApexReturnCode> {
+        let mut id = ApexSemaphoreId::default();
+        sys_create_semaphore(
+            semaphore_name,
+            current_value,
+            maximum_value,
+            queuing_discipline,
+            &mut id,
+        )
+        .as_result(id)
+    }
+
+    fn wait_semaphore(
+        &self,
+        semaphore_id: ApexSemaphoreId,
+        time_out: ApexSystemTime,
+    ) -> Result<(), ApexReturnCode> {
+        sys_wait_semaphore(semaphore_id, time_out).into()
+    }
+
+    fn signal_semaphore(&self, semaphore_id: ApexSemaphoreId) -> Result<(), ApexReturnCode> {
+        sys_signal_semaphore(semaphore_id).into()
+    }
+
+    fn get_semaphore_id(
+        &self,
+        semaphore_name: &ApexSemaphoreName,
+    ) -> Result<ApexSemaphoreId, ApexReturnCode> {
+        let mut id = ApexSemaphoreId::default();
+        sys_get_semaphore_id(semaphore_name, &mut id).as_result(id)
+    }
+
+    fn get_semaphore_status(
+        &self,
+        semaphore_id: ApexSemaphoreId,
+    ) -> Result<ApexSemaphoreStatus, ApexReturnCode> {
+        let mut status = ApexSemaphoreStatus::default();
+        sys_get_semaphore_status(semaphore_id, &mut status).as_result(status)
+    }
+}
@@ -0,0 +1,23 @@
+use jrinx_abi::sysfn::*;
+use jrinx_apex::*;
+
+pub struct Time;
+
+impl ApexTimeService for Time {
+    fn timed_wait(&self, delay_time: ApexSystemTime) -> Result<(), ApexReturnCode> {
+        sys_timed_wait(delay_time).into()
+    }
+
+    fn periodic_wait(&self) -> Result<(), ApexReturnCode> {
+        sys_periodic_wait().into()
+    }
+
+    fn get_time(&self) -> Result<ApexSystemTime, ApexReturnCode> {
+        let mut time = ApexSystemTime::default();
+        sys_get_time(&mut time).as_result(time)
+    }
+
+    fn replenish(&self, budget_time: ApexSystemTime) -> Result<(), ApexReturnCode> {
+        sys_replenish(budget_time).into()
+    }
+}
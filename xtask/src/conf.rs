@@ -0,0 +1,1277 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    fs,
+    path::Path,
+    process::ExitStatus,
+};
+
+use clap::Args;
+use jrinx_apex::{ApexErrorCode, APEX_NAME_MAX_LEN};
+use jrinx_modconf::*;
+use roxmltree::{Document, Node};
+
+#[derive(Debug, Args, Clone)]
+pub struct ConfArg {
+    #[clap(short = 's', env = "MODCONF_XML")]
+    pub xml_path: Option<String>,
+
+    #[clap(
+        short = 'd',
+        env = "MODCONF_PATH",
+        default_value_t =
+            std::env::current_dir().unwrap()
+                .join("modconf.jrc")
+                .to_str()
+                .unwrap()
+                .to_string()
+    )]
+    pub modconf_path: String,
+}
+
+#[must_use]
+pub fn run(arg: &ConfArg) -> Option<ExitStatus> {
+    let ConfArg {
+        ref xml_path,
+        ref modconf_path,
+    } = arg.clone();
+
+    convert(xml_path.as_deref().map(Path::new), Path::new(modconf_path))?;
+
+    Some(ExitStatus::default())
+}
+
+/// Makes sure the module configuration at `modconf_path` is up to date before it is built
+/// into the kernel.
+///
+/// If an XML configuration is given (`MODCONF_XML`), it is converted again, so that a stale
+/// blob is never shipped. Otherwise an empty configuration is written if there is none, and
+/// an existing blob is kept as it is.
+#[must_use]
+pub fn ensure(modconf_path: &Path) -> Option<()> {
+    let xml_path = std::env::var_os("MODCONF_XML");
+    let xml_path = xml_path.as_deref().map(Path::new);
+
+    if xml_path.is_none() && Path::exists(modconf_path) {
+        return Some(());
+    }
+
+    convert(xml_path, modconf_path)
+}
+
+fn convert(xml_path: Option<&Path>, modconf_path: &Path) -> Option<()> {
+    let conf = match xml_path {
+        Some(xml_path) => {
+            let xml = fs::read_to_string(xml_path).unwrap();
+            match parse(&xml) {
+                Ok(conf) => conf,
+                Err(errors) => {
+                    for error in errors {
+                        eprintln!("{}:{error}", xml_path.display());
+                    }
+                    return None;
+                }
+            }
+        }
+        None => ModuleConf::default(),
+    };
+
+    fs::write(modconf_path, conf.encode()).unwrap();
+
+    Some(())
+}
+
+fn parse(xml: &str) -> Result<ModuleConf, Vec<String>> {
+    let doc = Document::parse(xml).map_err(|err| vec![format!(" {err}")])?;
+    let mut parser = Parser {
+        doc: &doc,
+        errors: Vec::new(),
+    };
+
+    let root = doc.root_element();
+    if root.tag_name().name() != "ARINC_653_Module" {
+        parser.error(root, "is not an ARINC 653 module configuration");
+        return Err(parser.errors);
+    }
+
+    let conf = parser.module(root);
+    if parser.errors.is_empty() {
+        Ok(conf)
+    } else {
+        Err(parser.errors)
+    }
+}
+
+struct PortDecl {
+    kind: ChannelKindConf,
+    is_source: bool,
+    max_message_size: u32,
+}
+
+struct Parser<'a, 'input> {
+    doc: &'a Document<'input>,
+    errors: Vec<String>,
+}
+
+impl<'a, 'input> Parser<'a, 'input> {
+    fn module(&mut self, root: Node<'a, 'input>) -> ModuleConf {
+        let mut partitions = Vec::new();
+        let mut ports = BTreeMap::new();
+
+        for node in children(root, "Partition") {
+            if let Some(partition) = self.partition(node, &partitions, &mut ports) {
+                partitions.push(partition);
+            }
+        }
+
+        for node in children(root, "Partition_Memory") {
+            let Some(partition) = self.find_partition(node, &mut partitions) else {
+                continue;
+            };
+            for req in children(node, "Memory_Requirements") {
+                if let Some(size) = self.size(req, "SizeBytes") {
+                    partition.memory += size;
+                }
+            }
+        }
+
+        let mut schedules = Vec::new();
+        let mut names = BTreeSet::new();
+        let mut cores = BTreeMap::new();
+        for node in children(root, "Module_Schedule") {
+            let name = match node.attribute("ScheduleName") {
+                Some(_) => self.name(node, "ScheduleName"),
+                None => Some("default".to_string()),
+            };
+            let Some(name) = name else {
+                continue;
+            };
+            if !names.insert(name.clone()) {
+                self.error(node, format!("duplicates module schedule '{name}'"));
+                continue;
+            }
+            schedules.extend(self.schedule(node, name, &mut partitions, &mut cores));
+        }
+
+        let mut hm_table = Vec::new();
+        for node in children(root, "Module_HM_Table") {
+            hm_table.extend(self.hm_table(node, true));
+        }
+        for node in children(root, "Partition_HM_Table") {
+            let table = self.hm_table(node, false);
+            if let Some(partition) = self.find_partition(node, &mut partitions) {
+                partition.hm_table.extend(table);
+            }
+        }
+
+        let mut channels = Vec::new();
+        let mut connected = BTreeSet::new();
+        for node in children(root, "Connection_Table").flat_map(|n| children(n, "Channel")) {
+            if let Some(channel) = self.channel(node, &channels, &ports, &mut connected) {
+                channels.push(channel);
+            }
+        }
+
+        for partition in partitions.iter() {
+            if partition.memory == 0 {
+                self.error(
+                    root,
+                    format!("partition '{}' has no memory requirements", partition.name),
+                );
+            }
+            if partition.num_cores == 0 {
+                self.error(
+                    root,
+                    format!("partition '{}' is not scheduled", partition.name),
+                );
+            }
+        }
+
+        let window_threshold = root
+            .attribute("WindowThresholdSeconds")
+            .and_then(|_| self.time(root, "WindowThresholdSeconds"));
+
+        ModuleConf {
+            partitions,
+            channels,
+            schedules,
+            hm_table,
+            window_threshold,
+        }
+    }
+
+    fn partition(
+        &mut self,
+        node: Node<'a, 'input>,
+        partitions: &[PartitionConf],
+        ports: &mut BTreeMap<(String, String), PortDecl>,
+    ) -> Option<PartitionConf> {
+        let name = self.name(node, "PartitionName");
+        let entry = self.attr(node, "EntryPoint");
+        let (name, entry) = (name?, entry?);
+
+        if partitions.iter().any(|partition| partition.name == name) {
+            self.error(node, format!("duplicates partition '{name}'"));
+            return None;
+        }
+
+        let kind = match node.attribute("SystemPartition").unwrap_or("false") {
+            "true" => PartitionKindConf::Kern {
+                entry: entry.to_string(),
+            },
+            "false" => PartitionKindConf::User {
+                program: entry.to_string(),
+            },
+            value => {
+                self.error(node, format!("has invalid SystemPartition: {value:?}"));
+                return None;
+            }
+        };
+
+        for port in node
+            .children()
+            .filter(|n| matches!(n.tag_name().name(), "Sampling_Port" | "Queuing_Port"))
+        {
+            let Some((port_name, decl)) = self.port(port) else {
+                continue;
+            };
+            if ports
+                .insert((name.clone(), port_name.clone()), decl)
+                .is_some()
+            {
+                self.error(port, format!("duplicates port '{port_name}'"));
+            }
+        }
+
+        Some(PartitionConf {
+            name,
+            kind,
+            memory: 0,
+            period: 0,
+            duration: 0,
+            num_cores: 0,
+            hm_table: Vec::new(),
+        })
+    }
+
+    fn port(&mut self, node: Node<'a, 'input>) -> Option<(String, PortDecl)> {
+        let name = self.name(node, "Name");
+        let max_message_size = self.count(node, "MaxMessageSize");
+        let is_source = match self.attr(node, "Direction") {
+            Some("SOURCE") => Some(true),
+            Some("DESTINATION") => Some(false),
+            Some(value) => {
+                self.error(node, format!("has invalid Direction: {value:?}"));
+                None
+            }
+            None => None,
+        };
+        let kind = match node.tag_name().name() {
+            "Queuing_Port" => self
+                .count(node, "MaxNbMessages")
+                .map(|max_nb_message| ChannelKindConf::Queuing { max_nb_message }),
+            _ => Some(ChannelKindConf::Sampling),
+        };
+
+        Some((
+            name?,
+            PortDecl {
+                kind: kind?,
+                is_source: is_source?,
+                max_message_size: max_message_size?,
+            },
+        ))
+    }
+
+    /// Parses a module schedule named `name`, where `cores` records the cores each partition is
+    /// scheduled on by the module schedules parsed before.
+    fn schedule(
+        &mut self,
+        node: Node<'a, 'input>,
+        name: String,
+        partitions: &mut [PartitionConf],
+        cores: &mut BTreeMap<String, BTreeSet<u32>>,
+    ) -> Vec<ScheduleConf> {
+        let Some(major_frame) = self.time(node, "MajorFrameSeconds") else {
+            return Vec::new();
+        };
+        if major_frame == 0 {
+            self.error(node, "has zero major frame");
+            return Vec::new();
+        }
+
+        let mut windows: BTreeMap<u32, Vec<WindowConf>> = BTreeMap::new();
+        let mut scheduled = BTreeSet::new();
+
+        for node in children(node, "Partition_Schedule") {
+            let period = self.time(node, "PeriodSeconds");
+            let duration = self.time(node, "PeriodDurationSeconds");
+            let Some(partition) = self.find_partition(node, partitions) else {
+                continue;
+            };
+            if !scheduled.insert(partition.name.clone()) {
+                self.error(node, format!("duplicates partition '{}'", partition.name));
+                continue;
+            }
+            let (Some(period), Some(duration)) = (period, duration) else {
+                continue;
+            };
+
+            if major_frame < 0 && period >= 0
+                || major_frame > 0 && (period <= 0 || major_frame % period != 0)
+            {
+                self.error(
+                    node,
+                    format!("has period {period}ns not dividing major frame {major_frame}ns"),
+                );
+                continue;
+            }
+            if period >= 0 && (duration < 0 || duration > period) {
+                self.error(
+                    node,
+                    format!("has duration {duration}ns exceeding period {period}ns"),
+                );
+                continue;
+            }
+
+            let mut slices: BTreeMap<u32, BTreeSet<(ModconfTime, ModconfTime)>> = BTreeMap::new();
+            for node in children(node, "Window_Schedule") {
+                let offset = self.time(node, "WindowStartSeconds");
+                let length = self.time(node, "WindowDurationSeconds");
+                let cpu_id = self.core_id(node);
+                let (Some(offset), Some(length), Some(cpu_id)) = (offset, length, cpu_id) else {
+                    continue;
+                };
+
+                if offset < 0 || major_frame > 0 && offset >= major_frame {
+                    self.error(node, format!("has start {offset}ns out of major frame"));
+                    continue;
+                }
+                if length == 0
+                    || length < 0 && major_frame > 0
+                    || major_frame > 0 && offset + length > major_frame
+                {
+                    self.error(node, format!("has duration {length}ns out of major frame"));
+                    continue;
+                }
+                slices.entry(cpu_id).or_default().insert((offset, length));
+            }
+
+            if slices.is_empty() {
+                self.error(node, format!("has no window for '{}'", partition.name));
+                continue;
+            }
+
+            for (&cpu_id, slices) in slices.iter() {
+                let base = slices
+                    .iter()
+                    .filter(|&&(offset, _)| period < 0 || offset < period)
+                    .copied()
+                    .collect::<Vec<_>>();
+                if period > 0 {
+                    let repeated = (0..major_frame / period)
+                        .flat_map(|k| base.iter().map(move |&(o, l)| (o + k * period, l)))
+                        .collect::<BTreeSet<_>>();
+                    if repeated != *slices {
+                        self.error(
+                            node,
+                            format!(
+                                "has windows for '{}' not repeated every period on core {cpu_id}",
+                                partition.name
+                            ),
+                        );
+                    }
+                }
+                let total = base.iter().map(|&(_, l)| l).sum::<ModconfTime>();
+                if duration >= 0 && total != duration {
+                    self.error(
+                        node,
+                        format!(
+                            "has windows for '{}' summing to {total}ns within a period on core {cpu_id}, expected {duration}ns",
+                            partition.name
+                        ),
+                    );
+                }
+            }
+
+            let scheduled_cores = slices.keys().copied().collect::<BTreeSet<_>>();
+            // the initial process runs in the first window of the first module schedule
+            let init = match cores.get(&partition.name) {
+                None => true,
+                Some(prev) => {
+                    if partition.period != period || partition.duration != duration {
+                        self.error(
+                            node,
+                            format!(
+                                "has period and duration for '{}' differing from the former module schedule",
+                                partition.name
+                            ),
+                        );
+                    }
+                    if *prev != scheduled_cores {
+                        self.error(
+                            node,
+                            format!(
+                                "has cores for '{}' differing from the former module schedule",
+                                partition.name
+                            ),
+                        );
+                    }
+                    false
+                }
+            };
+
+            partition.period = period;
+            partition.duration = duration;
+            partition.num_cores = slices.len() as _;
+            cores.insert(partition.name.clone(), scheduled_cores);
+
+            for (i, (&cpu_id, slices)) in slices.iter().enumerate() {
+                for (j, &(offset, duration)) in slices.iter().enumerate() {
+                    windows.entry(cpu_id).or_default().push(WindowConf {
+                        partition: partition.name.clone(),
+                        offset,
+                        duration,
+                        init: init && i == 0 && j == 0,
+                    });
+                }
+            }
+        }
+
+        let mut backgrounds = BTreeMap::new();
+        for node in children(node, "Background_Schedule") {
+            let cpu_id = self.core_id(node);
+            let Some(partition) = self.find_partition(node, partitions) else {
+                continue;
+            };
+            let Some(cpu_id) = cpu_id else {
+                continue;
+            };
+            if !windows.contains_key(&cpu_id) {
+                self.error(
+                    node,
+                    format!("has background on core {cpu_id} without windows"),
+                );
+                continue;
+            }
+            if backgrounds.contains_key(&cpu_id) {
+                self.error(node, format!("duplicates background on core {cpu_id}"));
+                continue;
+            }
+
+            // a partition only running in the background has neither period nor duration
+            if !scheduled.contains(&partition.name) && partition.period == 0 {
+                partition.period = -1;
+                partition.duration = -1;
+            }
+            let scheduled_cores = cores.entry(partition.name.clone()).or_default();
+            let init = scheduled_cores.is_empty();
+            scheduled_cores.insert(cpu_id);
+            partition.num_cores = scheduled_cores.len() as _;
+
+            backgrounds.insert(
+                cpu_id,
+                BackgroundConf {
+                    partition: partition.name.clone(),
+                    init,
+                },
+            );
+        }
+
+        let mut schedules = Vec::new();
+        for (cpu_id, mut windows) in windows {
+            windows.sort_by_key(|window| window.offset);
+            for pair in windows.windows(2) {
+                if pair[0].duration < 0 || pair[0].offset + pair[0].duration > pair[1].offset {
+                    self.error(
+                        node,
+                        format!(
+                            "has overlapping windows for '{}' and '{}' on core {cpu_id}",
+                            pair[0].partition, pair[1].partition
+                        ),
+                    );
+                }
+            }
+            schedules.push(ScheduleConf {
+                name: name.clone(),
+                cpu_id,
+                major_frame,
+                windows,
+                background: backgrounds.remove(&cpu_id),
+            });
+        }
+        schedules
+    }
+
+    fn core_id(&mut self, node: Node<'a, 'input>) -> Option<u32> {
+        match node.attribute("ProcessorCoreId") {
+            Some(value) => value.parse::<u32>().ok().or_else(|| {
+                self.error(node, format!("has invalid ProcessorCoreId: {value:?}"));
+                None
+            }),
+            None => Some(0),
+        }
+    }
+
+    fn hm_table(&mut self, node: Node<'a, 'input>, is_module: bool) -> Vec<HmConf> {
+        let mut table: Vec<HmConf> = Vec::new();
+        for node in node
+            .descendants()
+            .filter(|n| n.tag_name().name() == "Error_ID_Action")
+        {
+            let error = match self.attr(node, "ErrorIdentifier") {
+                Some("DEADLINE_MISSED") => ApexErrorCode::DeadlineMissed,
+                Some("APPLICATION_ERROR") => ApexErrorCode::ApplicationError,
+                Some("NUMERIC_ERROR") => ApexErrorCode::NumericError,
+                Some("ILLEGAL_REQUEST") => ApexErrorCode::IllegalRequest,
+                Some("STACK_OVERFLOW") => ApexErrorCode::StackOverflow,
+                Some("MEMORY_VIOLATION") => ApexErrorCode::MemoryViolation,
+                Some("HARDWARE_FAULT") => ApexErrorCode::HardwareFault,
+                Some("POWER_FAIL") => ApexErrorCode::PowerFail,
+                Some(value) => {
+                    self.error(node, format!("has invalid ErrorIdentifier: {value:?}"));
+                    continue;
+                }
+                None => continue,
+            } as u32;
+            let action = match self.attr(node, "Action") {
+                Some("IGNORE") => HmActionConf::Ignore,
+                Some("STOP_PROCESS") => HmActionConf::StopProcess,
+                Some("COLD_START") => HmActionConf::ColdStart,
+                Some("WARM_START") => HmActionConf::WarmStart,
+                Some("IDLE") => HmActionConf::Idle,
+                Some("RESET") => HmActionConf::Reset,
+                Some(value) => {
+                    self.error(node, format!("has invalid Action: {value:?}"));
+                    continue;
+                }
+                None => continue,
+            };
+
+            if is_module && !matches!(action, HmActionConf::Ignore | HmActionConf::Reset) {
+                self.error(
+                    node,
+                    format!("has invalid module recovery action: {action:?}"),
+                );
+            } else if !is_module && action == HmActionConf::Reset {
+                self.error(
+                    node,
+                    format!("has invalid partition recovery action: {action:?}"),
+                );
+            } else if table.iter().any(|entry| entry.error == error) {
+                self.error(node, "duplicates ErrorIdentifier");
+            } else {
+                table.push(HmConf { error, action });
+            }
+        }
+        table
+    }
+
+    fn channel(
+        &mut self,
+        node: Node<'a, 'input>,
+        channels: &[ChannelConf],
+        ports: &BTreeMap<(String, String), PortDecl>,
+        connected: &mut BTreeSet<(String, String)>,
+    ) -> Option<ChannelConf> {
+        let name = self.name(node, "ChannelName")?;
+        if channels.iter().any(|channel| channel.name == name) {
+            self.error(node, format!("duplicates channel '{name}'"));
+            return None;
+        }
+
+        let sources = children(node, "Source")
+            .filter_map(|n| self.port_ref(n))
+            .collect::<Vec<_>>();
+        let destinations = children(node, "Destination")
+            .filter_map(|n| self.port_ref(n))
+            .collect::<Vec<_>>();
+
+        let [source] = &sources[..] else {
+            self.error(node, "should have exactly one source");
+            return None;
+        };
+        if destinations.is_empty() {
+            self.error(node, "should have at least one destination");
+            return None;
+        }
+
+        let mut decls = Vec::new();
+        for (port, is_source) in
+            core::iter::once((source, true)).chain(destinations.iter().map(|port| (port, false)))
+        {
+            let key = (port.partition.clone(), port.port.clone());
+            match ports.get(&key) {
+                Some(decl) if decl.is_source == is_source => decls.push(decl),
+                Some(_) => self.error(
+                    node,
+                    format!(
+                        "connects '{}:{}' in wrong direction",
+                        port.partition, port.port
+                    ),
+                ),
+                None => self.error(
+                    node,
+                    format!(
+                        "connects undeclared port '{}:{}'",
+                        port.partition, port.port
+                    ),
+                ),
+            }
+            if !connected.insert(key) {
+                self.error(
+                    node,
+                    format!(
+                        "connects '{}:{}' already connected",
+                        port.partition, port.port
+                    ),
+                );
+            }
+        }
+        if decls.len() != destinations.len() + 1 {
+            return None;
+        }
+
+        let (head, tail) = decls.split_first().unwrap();
+        if tail
+            .iter()
+            .any(|decl| decl.kind != head.kind || decl.max_message_size != head.max_message_size)
+        {
+            self.error(node, "connects ports of different kinds or message sizes");
+            return None;
+        }
+        if matches!(head.kind, ChannelKindConf::Queuing { .. }) && destinations.len() != 1 {
+            self.error(
+                node,
+                "should have exactly one destination as a queuing channel",
+            );
+            return None;
+        }
+
+        Some(ChannelConf {
+            name,
+            max_message_size: head.max_message_size,
+            source: source.clone(),
+            destinations,
+            kind: head.kind,
+        })
+    }
+
+    fn port_ref(&mut self, node: Node<'a, 'input>) -> Option<PortConf> {
+        let Some(node) = children(node, "Standard_Partition").next() else {
+            self.error(node, "should contain a <Standard_Partition>");
+            return None;
+        };
+        let partition = self.name(node, "PartitionName");
+        let port = self.name(node, "PortName");
+        Some(PortConf {
+            partition: partition?,
+            port: port?,
+        })
+    }
+
+    fn find_partition<'p>(
+        &mut self,
+        node: Node<'a, 'input>,
+        partitions: &'p mut [PartitionConf],
+    ) -> Option<&'p mut PartitionConf> {
+        let name = self.attr(node, "PartitionName")?;
+        let partition = partitions.iter_mut().find(|p| p.name == name);
+        if partition.is_none() {
+            self.error(node, format!("refers to unrecognized partition '{name}'"));
+        }
+        partition
+    }
+
+    fn attr(&mut self, node: Node<'a, 'input>, attr: &str) -> Option<&'a str> {
+        let value = node.attribute(attr);
+        if value.is_none() {
+            self.error(node, format!("is missing attribute {attr}"));
+        }
+        value
+    }
+
+    fn name(&mut self, node: Node<'a, 'input>, attr: &str) -> Option<String> {
+        let value = self.attr(node, attr)?;
+        if value.is_empty() || value.len() > APEX_NAME_MAX_LEN {
+            self.error(
+                node,
+                format!("has {attr} {value:?} not within 1 to {APEX_NAME_MAX_LEN} bytes"),
+            );
+            return None;
+        }
+        Some(value.to_string())
+    }
+
+    fn time(&mut self, node: Node<'a, 'input>, attr: &str) -> Option<ModconfTime> {
+        let value = self.attr(node, attr)?;
+        let time = parse_seconds(value);
+        if time.is_none() {
+            self.error(node, format!("has invalid {attr}: {value:?}"));
+        }
+        time
+    }
+
+    fn size(&mut self, node: Node<'a, 'input>, attr: &str) -> Option<u64> {
+        let value = self.attr(node, attr)?;
+        let (radix, digits) = match value {
+            s if s.starts_with("0x") || s.starts_with("0X") => (16, &s[2..]),
+            s => (10, s),
+        };
+        let size = u64::from_str_radix(digits, radix).ok();
+        if size.is_none() {
+            self.error(node, format!("has invalid {attr}: {value:?}"));
+        }
+        size
+    }
+
+    fn count(&mut self, node: Node<'a, 'input>, attr: &str) -> Option<u32> {
+        let size = self.size(node, attr)?;
+        let count = u32::try_from(size).ok();
+        if count.is_none() {
+            self.error(node, format!("has too large {attr}: {size}"));
+        }
+        count
+    }
+
+    fn error(&mut self, node: Node<'a, 'input>, msg: impl Display) {
+        let pos = self.doc.text_pos_at(node.range().start);
+        self.errors.push(format!(
+            "{}:{}: <{}> {}",
+            pos.row,
+            pos.col,
+            node.tag_name().name(),
+            msg
+        ));
+    }
+}
+
+fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(move |n| n.tag_name().name() == tag)
+}
+
+/// Parses decimal seconds into nanoseconds, where a negative value indicates an infinite time.
+fn parse_seconds(s: &str) -> Option<ModconfTime> {
+    if let Some(s) = s.strip_prefix('-') {
+        return parse_seconds(s).map(|_| -1);
+    }
+
+    let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+    if int.is_empty() && frac.is_empty()
+        || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit())
+        || frac.chars().skip(9).any(|c| c != '0')
+    {
+        return None;
+    }
+
+    let int: ModconfTime = if int.is_empty() { 0 } else { int.parse().ok()? };
+    let frac = format!("{:0<9}", &frac[..frac.len().min(9)])
+        .parse::<ModconfTime>()
+        .ok()?;
+    int.checked_mul(1_000_000_000)?.checked_add(frac)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULE: &str = r#"
+<ARINC_653_Module WindowThresholdSeconds="0.0001">
+  <Partition PartitionName="p1" EntryPoint="idle" SystemPartition="true">
+    <Sampling_Port Name="out" MaxMessageSize="64" Direction="SOURCE"/>
+    <Queuing_Port Name="in" MaxMessageSize="32" Direction="DESTINATION" MaxNbMessages="8"/>
+  </Partition>
+  <Partition PartitionName="p2" EntryPoint="idle">
+    <Sampling_Port Name="in" MaxMessageSize="64" Direction="DESTINATION"/>
+    <Queuing_Port Name="out" MaxMessageSize="32" Direction="SOURCE" MaxNbMessages="8"/>
+  </Partition>
+  <Partition_Memory PartitionName="p1">
+    <Memory_Requirements SizeBytes="0x4000"/>
+    <Memory_Requirements SizeBytes="0x4000"/>
+  </Partition_Memory>
+  <Partition_Memory PartitionName="p2">
+    <Memory_Requirements SizeBytes="65536"/>
+  </Partition_Memory>
+  <Module_Schedule MajorFrameSeconds="0.002">
+    <Partition_Schedule PartitionName="p1" PeriodSeconds="0.002" PeriodDurationSeconds="0.001">
+      <Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.001"/>
+    </Partition_Schedule>
+    <Partition_Schedule PartitionName="p2" PeriodSeconds="0.002" PeriodDurationSeconds="0.0005">
+      <Window_Schedule WindowStartSeconds="0.001" WindowDurationSeconds="0.0005"/>
+      <Window_Schedule WindowStartSeconds="0.0005" WindowDurationSeconds="0.0005" ProcessorCoreId="1"/>
+    </Partition_Schedule>
+  </Module_Schedule>
+  <Module_HM_Table>
+    <Error_ID_Action ErrorIdentifier="POWER_FAIL" Action="RESET"/>
+  </Module_HM_Table>
+  <Partition_HM_Table PartitionName="p1">
+    <Error_ID_Action ErrorIdentifier="DEADLINE_MISSED" Action="WARM_START"/>
+  </Partition_HM_Table>
+  <Connection_Table>
+    <Channel ChannelName="sampling">
+      <Source><Standard_Partition PartitionName="p1" PortName="out"/></Source>
+      <Destination><Standard_Partition PartitionName="p2" PortName="in"/></Destination>
+    </Channel>
+    <Channel ChannelName="queuing">
+      <Source><Standard_Partition PartitionName="p2" PortName="out"/></Source>
+      <Destination><Standard_Partition PartitionName="p1" PortName="in"/></Destination>
+    </Channel>
+  </Connection_Table>
+</ARINC_653_Module>
+"#;
+
+    /// Parses `MODULE` with `from` replaced by `to`, and returns the errors.
+    fn errors_of(from: &str, to: &str) -> Vec<String> {
+        assert!(MODULE.contains(from));
+        parse(&MODULE.replacen(from, to, 1)).unwrap_err()
+    }
+
+    fn assert_error(from: &str, to: &str, msg: &str) {
+        let errors = errors_of(from, to);
+        assert!(
+            errors.iter().any(|err| err.contains(msg)),
+            "{msg:?} not in {errors:?}"
+        );
+    }
+
+    #[test]
+    fn module() {
+        let conf = parse(MODULE).unwrap();
+
+        assert_eq!(conf.partitions.len(), 2);
+        let p1 = &conf.partitions[0];
+        assert_eq!(p1.name, "p1");
+        assert_eq!(
+            p1.kind,
+            PartitionKindConf::Kern {
+                entry: "idle".to_string()
+            }
+        );
+        assert_eq!(p1.memory, 0x8000);
+        assert_eq!(
+            (p1.period, p1.duration, p1.num_cores),
+            (2_000_000, 1_000_000, 1)
+        );
+        assert_eq!(
+            p1.hm_table,
+            [HmConf {
+                error: ApexErrorCode::DeadlineMissed as _,
+                action: HmActionConf::WarmStart,
+            }]
+        );
+        let p2 = &conf.partitions[1];
+        assert_eq!(
+            p2.kind,
+            PartitionKindConf::User {
+                program: "idle".to_string()
+            }
+        );
+        assert_eq!(p2.memory, 0x10000);
+        assert_eq!(
+            (p2.period, p2.duration, p2.num_cores),
+            (2_000_000, 500_000, 2)
+        );
+
+        assert_eq!(conf.channels.len(), 2);
+        assert_eq!(conf.channels[0].kind, ChannelKindConf::Sampling);
+        assert_eq!(conf.channels[0].max_message_size, 64);
+        assert_eq!(
+            conf.channels[1].kind,
+            ChannelKindConf::Queuing { max_nb_message: 8 }
+        );
+        assert_eq!(conf.channels[1].destinations[0].partition, "p1");
+
+        assert_eq!(
+            conf.hm_table,
+            [HmConf {
+                error: ApexErrorCode::PowerFail as _,
+                action: HmActionConf::Reset,
+            }]
+        );
+        assert_eq!(conf.window_threshold, Some(100_000));
+
+        assert_eq!(ModuleConf::decode(&conf.encode()), Ok(conf));
+    }
+
+    #[test]
+    fn not_a_module() {
+        assert!(parse("<ARINC_653_Module>").is_err());
+        assert_eq!(
+            parse("<Module/>").unwrap_err(),
+            ["1:1: <Module> is not an ARINC 653 module configuration"]
+        );
+    }
+
+    #[test]
+    fn partition() {
+        assert_error(
+            r#"PartitionName="p2" EntryPoint="idle">"#,
+            r#"PartitionName="p1" EntryPoint="idle">"#,
+            "duplicates partition 'p1'",
+        );
+        assert_error(
+            r#"EntryPoint="idle" SystemPartition="true""#,
+            r#"EntryPoint="idle" SystemPartition="yes""#,
+            "has invalid SystemPartition",
+        );
+        assert_error(
+            r#"PartitionName="p2" EntryPoint="idle""#,
+            r#"PartitionName="" EntryPoint="idle""#,
+            "has PartitionName \"\" not within",
+        );
+        assert_error(
+            r#"<Memory_Requirements SizeBytes="65536"/>"#,
+            "",
+            "partition 'p2' has no memory requirements",
+        );
+        assert_error(
+            r#"SizeBytes="65536""#,
+            r#"SizeBytes="64K""#,
+            "has invalid SizeBytes",
+        );
+        assert_error(
+            r#"<Partition_Memory PartitionName="p2">"#,
+            r#"<Partition_Memory PartitionName="p3">"#,
+            "refers to unrecognized partition 'p3'",
+        );
+    }
+
+    #[test]
+    fn port() {
+        assert_error(
+            r#"Name="in" MaxMessageSize="32""#,
+            r#"Name="out" MaxMessageSize="32""#,
+            "duplicates port 'out'",
+        );
+        assert_error(
+            r#"Direction="SOURCE"/>"#,
+            r#"Direction="OUT"/>"#,
+            "has invalid Direction",
+        );
+        assert_error(
+            r#"MaxNbMessages="8"/>"#,
+            r#"MaxNbMessages="0x100000000"/>"#,
+            "has too large MaxNbMessages",
+        );
+    }
+
+    #[test]
+    fn channel() {
+        assert_error(
+            r#"ChannelName="queuing""#,
+            r#"ChannelName="sampling""#,
+            "duplicates channel 'sampling'",
+        );
+        assert_error(
+            r#"PartitionName="p2" PortName="in""#,
+            r#"PartitionName="p2" PortName="out""#,
+            "connects 'p2:out' in wrong direction",
+        );
+        assert_error(
+            r#"PartitionName="p1" PortName="in""#,
+            r#"PartitionName="p1" PortName="none""#,
+            "connects undeclared port 'p1:none'",
+        );
+        assert_error(
+            r#"<Destination><Standard_Partition PartitionName="p2" PortName="in"/></Destination>"#,
+            "",
+            "should have at least one destination",
+        );
+        assert_error(
+            r#"<Source><Standard_Partition PartitionName="p2" PortName="out"/></Source>"#,
+            r#"<Source><Standard_Partition PartitionName="p1" PortName="out"/></Source>"#,
+            "connects 'p1:out' already connected",
+        );
+        assert_error(
+            r#"<Sampling_Port Name="in" MaxMessageSize="64""#,
+            r#"<Sampling_Port Name="in" MaxMessageSize="16""#,
+            "connects ports of different kinds or message sizes",
+        );
+    }
+
+    #[test]
+    fn hm_table() {
+        assert_error(
+            r#"ErrorIdentifier="POWER_FAIL" Action="RESET""#,
+            r#"ErrorIdentifier="POWER_FAIL" Action="WARM_START""#,
+            "has invalid module recovery action",
+        );
+        assert_error(
+            r#"ErrorIdentifier="DEADLINE_MISSED" Action="WARM_START""#,
+            r#"ErrorIdentifier="DEADLINE_MISSED" Action="RESET""#,
+            "has invalid partition recovery action",
+        );
+        assert_error(
+            r#"ErrorIdentifier="DEADLINE_MISSED""#,
+            r#"ErrorIdentifier="DEADLINE""#,
+            "has invalid ErrorIdentifier",
+        );
+        assert_error(
+            r#"<Error_ID_Action ErrorIdentifier="POWER_FAIL" Action="RESET"/>"#,
+            r#"<Error_ID_Action ErrorIdentifier="POWER_FAIL" Action="RESET"/><Error_ID_Action ErrorIdentifier="POWER_FAIL" Action="IGNORE"/>"#,
+            "duplicates ErrorIdentifier",
+        );
+    }
+
+    #[test]
+    fn schedule() {
+        assert_error(
+            r#"MajorFrameSeconds="0.002""#,
+            r#"MajorFrameSeconds="0""#,
+            "has zero major frame",
+        );
+        assert_error(
+            r#"PartitionName="p1" PeriodSeconds="0.002""#,
+            r#"PartitionName="p1" PeriodSeconds="0.003""#,
+            "has period 3000000ns not dividing major frame 2000000ns",
+        );
+        assert_error(
+            r#"PeriodSeconds="0.002" PeriodDurationSeconds="0.001""#,
+            r#"PeriodSeconds="0.002" PeriodDurationSeconds="0.003""#,
+            "has duration 3000000ns exceeding period 2000000ns",
+        );
+        assert_error(
+            r#"<Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.001"/>"#,
+            r#"<Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.0005"/>"#,
+            "has windows for 'p1' summing to 500000ns within a period on core 0, expected 1000000ns",
+        );
+        assert_error(
+            r#"<Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.001"/>"#,
+            r#"<Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.0015"/>"#,
+            "has overlapping windows for 'p1' and 'p2' on core 0",
+        );
+        assert_error(
+            r#"<Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.001"/>"#,
+            r#"<Window_Schedule WindowStartSeconds="0.002" WindowDurationSeconds="0.001"/>"#,
+            "has start 2000000ns out of major frame",
+        );
+        assert_error(
+            r#"<Window_Schedule WindowStartSeconds="0.001" WindowDurationSeconds="0.0005"/>"#,
+            r#"<Window_Schedule WindowStartSeconds="0.0018" WindowDurationSeconds="0.0005"/>"#,
+            "has duration 500000ns out of major frame",
+        );
+        assert_error(
+            r#"<Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.001"/>"#,
+            "",
+            "has no window for 'p1'",
+        );
+        assert_error(
+            r#"ProcessorCoreId="1""#,
+            r#"ProcessorCoreId="one""#,
+            "has invalid ProcessorCoreId",
+        );
+        assert_error(
+            r#"<Partition_Schedule PartitionName="p2""#,
+            r#"<Partition_Schedule PartitionName="p1""#,
+            "duplicates partition 'p1'",
+        );
+    }
+
+    #[test]
+    fn unscheduled() {
+        let errors = errors_of(
+            r#"<Window_Schedule WindowStartSeconds="0.001" WindowDurationSeconds="0.0005"/>
+      <Window_Schedule WindowStartSeconds="0.0005" WindowDurationSeconds="0.0005" ProcessorCoreId="1"/>"#,
+            "",
+        );
+        assert!(errors
+            .iter()
+            .any(|err| err.contains("has no window for 'p2'")));
+        assert!(errors
+            .iter()
+            .any(|err| err.contains("partition 'p2' is not scheduled")));
+    }
+
+    #[test]
+    fn period() {
+        let module = MODULE.replacen(
+            r#"PeriodSeconds="0.002" PeriodDurationSeconds="0.001">
+      <Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.001"/>"#,
+            r#"PeriodSeconds="0.001" PeriodDurationSeconds="0.0005">
+      <Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.0005"/>"#,
+            1,
+        );
+        let errors = parse(&module).unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|err| err.contains("has windows for 'p1' not repeated every period on core 0")));
+
+        let module = module
+            .replacen(
+                r#"<Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.0005"/>"#,
+                r#"<Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.0005"/>
+      <Window_Schedule WindowStartSeconds="0.001" WindowDurationSeconds="0.0005"/>"#,
+                1,
+            )
+            .replacen(
+                r#"<Window_Schedule WindowStartSeconds="0.001" WindowDurationSeconds="0.0005"/>
+      <Window_Schedule WindowStartSeconds="0.0005""#,
+                r#"<Window_Schedule WindowStartSeconds="0.0005" WindowDurationSeconds="0.0005"/>
+      <Window_Schedule WindowStartSeconds="0.0005""#,
+                1,
+            );
+        let conf = parse(&module).unwrap();
+        assert_eq!(conf.partitions[0].period, 1_000_000);
+        assert_eq!(conf.schedules[0].windows.len(), 3);
+    }
+
+    /// Returns `MODULE` with another module schedule named `name` after the default one,
+    /// scheduling p1 with `p1` and p2 with `p2` as its partition schedules.
+    fn with_schedule(name: &str, p1: &str, p2: &str) -> String {
+        MODULE.replacen(
+            "  <Module_HM_Table>",
+            &format!(
+                r#"  <Module_Schedule ScheduleName="{name}" MajorFrameSeconds="0.004">
+    <Partition_Schedule PartitionName="p1" {p1}
+    </Partition_Schedule>
+    <Partition_Schedule PartitionName="p2" {p2}
+    </Partition_Schedule>
+  </Module_Schedule>
+  <Module_HM_Table>"#
+            ),
+            1,
+        )
+    }
+
+    const P1: &str = r#"PeriodSeconds="0.002" PeriodDurationSeconds="0.001">
+      <Window_Schedule WindowStartSeconds="0.001" WindowDurationSeconds="0.001"/>
+      <Window_Schedule WindowStartSeconds="0.003" WindowDurationSeconds="0.001"/>"#;
+    const P2: &str = r#"PeriodSeconds="0.002" PeriodDurationSeconds="0.0005">
+      <Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.0005"/>
+      <Window_Schedule WindowStartSeconds="0.002" WindowDurationSeconds="0.0005"/>
+      <Window_Schedule WindowStartSeconds="0" WindowDurationSeconds="0.0005" ProcessorCoreId="1"/>
+      <Window_Schedule WindowStartSeconds="0.002" WindowDurationSeconds="0.0005" ProcessorCoreId="1"/>"#;
+
+    #[test]
+    fn module_schedules() {
+        let conf = parse(&with_schedule("alt", P1, P2)).unwrap();
+
+        let schedules = conf
+            .schedules
+            .iter()
+            .map(|schedule| (schedule.name.as_str(), schedule.cpu_id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            schedules,
+            [("default", 0), ("default", 1), ("alt", 0), ("alt", 1)]
+        );
+        // the initial process of each partition runs in one window of the initial module
+        // schedule
+        let inits = |schedules: &[ScheduleConf]| {
+            schedules
+                .iter()
+                .flat_map(|schedule| schedule.windows.iter())
+                .filter(|window| window.init)
+                .count()
+        };
+        assert_eq!(inits(&conf.schedules[..2]), 2);
+        assert_eq!(inits(&conf.schedules[2..]), 0);
+        assert_eq!(conf.partitions[1].num_cores, 2);
+    }
+
+    #[test]
+    fn module_schedule_mismatch() {
+        assert!(parse(&with_schedule("default", P1, P2))
+            .unwrap_err()
+            .iter()
+            .any(|err| err.contains("duplicates module schedule 'default'")));
+
+        let p1 = P1.replacen(
+            r#"PeriodSeconds="0.002" PeriodDurationSeconds="0.001""#,
+            r#"PeriodSeconds="0.004" PeriodDurationSeconds="0.002""#,
+            1,
+        );
+        assert!(parse(&with_schedule("alt", &p1, P2))
+            .unwrap_err()
+            .iter()
+            .any(|err| err.contains(
+                "has period and duration for 'p1' differing from the former module schedule"
+            )));
+
+        let p2 = P2.replace(r#" ProcessorCoreId="1""#, r#" ProcessorCoreId="2""#);
+        assert!(parse(&with_schedule("alt", P1, &p2))
+            .unwrap_err()
+            .iter()
+            .any(
+                |err| err.contains("has cores for 'p2' differing from the former module schedule")
+            ));
+    }
+
+    #[test]
+    fn background() {
+        let module = MODULE
+            .replacen(
+                r#"<Partition PartitionName="p2" EntryPoint="idle">"#,
+                r#"<Partition PartitionName="p3" EntryPoint="idle"/>
+  <Partition PartitionName="p2" EntryPoint="idle">"#,
+                1,
+            )
+            .replacen(
+                r#"<Partition_Memory PartitionName="p2">"#,
+                r#"<Partition_Memory PartitionName="p3">
+    <Memory_Requirements SizeBytes="0x1000"/>
+  </Partition_Memory>
+  <Partition_Memory PartitionName="p2">"#,
+                1,
+            )
+            .replacen(
+                "  </Module_Schedule>",
+                r#"    <Background_Schedule PartitionName="p3"/>
+    <Background_Schedule PartitionName="p2" ProcessorCoreId="1"/>
+  </Module_Schedule>"#,
+                1,
+            );
+        let conf = parse(&module).unwrap();
+
+        let p3 = &conf.partitions[1];
+        assert_eq!(p3.name, "p3");
+        // a partition only running in the background has neither period nor duration
+        assert_eq!((p3.period, p3.duration, p3.num_cores), (-1, -1, 1));
+        assert_eq!(
+            conf.schedules[0].background,
+            Some(BackgroundConf {
+                partition: "p3".to_string(),
+                init: true,
+            })
+        );
+        // p2 already runs its initial process in a window
+        assert_eq!(
+            conf.schedules[1].background,
+            Some(BackgroundConf {
+                partition: "p2".to_string(),
+                init: false,
+            })
+        );
+        assert_eq!(conf.partitions[2].num_cores, 2);
+
+        assert_error(
+            "  </Module_Schedule>",
+            r#"    <Background_Schedule PartitionName="p1" ProcessorCoreId="2"/>
+  </Module_Schedule>"#,
+            "has background on core 2 without windows",
+        );
+        assert_error(
+            "  </Module_Schedule>",
+            r#"    <Background_Schedule PartitionName="p1"/>
+    <Background_Schedule PartitionName="p2"/>
+  </Module_Schedule>"#,
+            "duplicates background on core 0",
+        );
+    }
+
+    #[test]
+    fn seconds() {
+        assert_eq!(parse_seconds("1"), Some(1_000_000_000));
+        assert_eq!(parse_seconds("0.5"), Some(500_000_000));
+        assert_eq!(parse_seconds(".000000001"), Some(1));
+        assert_eq!(parse_seconds("1.0000000010"), Some(1_000_000_001));
+        assert_eq!(parse_seconds("-1"), Some(-1));
+        assert_eq!(parse_seconds("0.0000000001"), None);
+        assert_eq!(parse_seconds("."), None);
+        assert_eq!(parse_seconds("1e3"), None);
+        assert_eq!(parse_seconds("99999999999"), None);
+    }
+}
@@ -23,6 +23,7 @@ pub fn setup(arg: &MakeArg) {
     export_env! {
         "ARCH" ?= arch.to_string(),
         "UPROG_PATH" ?= std::env::current_dir().unwrap().join("uprog.jrz").to_str().unwrap(),
+        "MODCONF_PATH" ?= std::env::current_dir().unwrap().join("modconf.jrc").to_str().unwrap(),
         "BUILD_MODE" ?= if debug { "debug" } else { "release" },
         "BUILD_TIME" ?= chrono::offset::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
         "BUILD_HOST" ?= {
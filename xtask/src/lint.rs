@@ -1,7 +1,7 @@
 use std::{env, fs, path::Path, process::ExitStatus};
 
 use crate::{
-    envs,
+    conf, envs,
     make::{self, MakeArg},
     util::cargo::Cargo,
 };
@@ -16,6 +16,10 @@ pub fn run(arg: &MakeArg) -> Option<ExitStatus> {
         fs::write(uprog_path, "MOCK UPROG BINARY").unwrap();
     }
 
+    conf::ensure(Path::new(
+        env::var_os("MODCONF_PATH").unwrap().to_str().unwrap(),
+    ))?;
+
     let mut cmd = Cargo::new("clippy");
 
     make::kernel(&mut cmd, arg);
@@ -1,5 +1,6 @@
 mod ar;
 mod arch;
+mod conf;
 mod envs;
 mod lint;
 mod make;
@@ -13,6 +14,7 @@ use std::process::ExitCode;
 use clap::{Parser, Subcommand};
 
 use ar::ArchiveArg;
+use conf::ConfArg;
 use make::MakeArg;
 use qemu::QemuArg;
 use uprog::UprogArg;
@@ -36,6 +38,7 @@ enum Cmd {
     Uprog(UprogArg),
     Ulint(UprogArg),
     Ar(ArchiveArg),
+    Conf(ConfArg),
 }
 
 fn main() -> ExitCode {
@@ -46,6 +49,7 @@ fn main() -> ExitCode {
         Cmd::Uprog(ref arg) => uprog::run(arg),
         Cmd::Ulint(ref arg) => ulint::run(arg),
         Cmd::Ar(ref arg) => ar::run(arg),
+        Cmd::Conf(ref arg) => conf::run(arg),
     } {
         if code.success() {
             return ExitCode::SUCCESS;
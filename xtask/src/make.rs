@@ -1,5 +1,6 @@
 use std::{
     env, fs,
+    path::Path,
     process::{Command, ExitStatus},
 };
 
@@ -7,7 +8,7 @@ use clap::Args;
 
 use crate::{
     arch::ArchArg,
-    envs,
+    conf, envs,
     util::{cargo::Cargo, CmdOptional},
 };
 
@@ -59,6 +60,10 @@ pub fn run(arg: &MakeArg) -> Option<ExitStatus> {
         return None;
     }
 
+    conf::ensure(Path::new(
+        env::var_os("MODCONF_PATH").unwrap().to_str().unwrap(),
+    ))?;
+
     let mut cmd = Cargo::new("build");
     kernel(&mut cmd, arg);
 